@@ -0,0 +1,71 @@
+use crate::{Die, ProbabilityDistribution};
+use std::fmt::Write;
+
+/// The views offered by [AnyDice](https://anydice.com), see [`Die::to_anydice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnyDiceView {
+    /// Chance to roll exactly each value.
+    #[default]
+    Normal,
+    /// Chance to roll each value or higher.
+    AtLeast,
+    /// Chance to roll each value or lower.
+    AtMost,
+}
+
+impl Die {
+    /// Exports this die in the same layout as the export of AnyDice, to diff results against it.
+    ///
+    /// The first row holds the quoted name, mean, standard deviation, min and max, followed by a
+    /// `#,%` header and one row per value with its chance in percent, rounded to two decimals.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ AnyDiceView, Die, NormalInitializer };
+    /// assert_eq!(
+    ///     Die::new(4).to_anydice("output 1", AnyDiceView::AtLeast),
+    ///     "\"output 1\",2.50,1.12,1,4\n#,%\n1,100.00\n2,75.00\n3,50.00\n4,25.00\n"
+    /// );
+    /// ```
+    pub fn to_anydice(&self, name: &str, view: AnyDiceView) -> String {
+        let mut out = format!(
+            "\"{}\",{:.2},{:.2},{},{}\n#,%\n",
+            name.replace('"', "\"\""),
+            self.get_mean(),
+            self.get_standard_deviation(),
+            self.get_min(),
+            self.get_max()
+        );
+        let rows: Vec<(i32, f64)> = match view {
+            AnyDiceView::Normal => self.as_pairs().collect(),
+            AnyDiceView::AtLeast => self
+                .iter()
+                .zip(self.survival_iter())
+                .map(|(prob, (value, survival))| (value, survival + prob.chance))
+                .collect(),
+            AnyDiceView::AtMost => self.cdf_iter().collect(),
+        };
+        for (value, chance) in rows {
+            let _ = writeln!(out, "{},{:.2}", value, chance * 100.0);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn exporting() {
+        let die = Die::from_values(&[1, 2, 2]);
+        assert_eq!(
+            die.to_anydice("a \"quoted\" name", AnyDiceView::Normal),
+            "\"a \"\"quoted\"\" name\",1.67,0.47,1,2\n#,%\n1,33.33\n2,66.67\n"
+        );
+        assert!(die
+            .to_anydice("", AnyDiceView::AtMost)
+            .ends_with("#,%\n1,33.33\n2,100.00\n"));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::common::*;
+use crate::{FormatOptions, Probability};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+
+/// Adapter rendering the results of a distribution like
+/// [`get_results`][`crate::ProbabilityDistribution::get_results`], but with ANSI colors for
+/// terminals, as returned by
+/// [`display_colored`][`crate::ProbabilityDistribution::display_colored`].
+///
+/// Each bar is colored on a gradient from blue for the least to red for the most likely value.
+/// The rows of the median and the value closest to the mean are printed bold and marked.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, FormatOptions, NormalInitializer, ProbabilityDistribution };
+/// let colored = Die::new(6).repeat(2).display_colored(FormatOptions::default()).to_string();
+/// assert!(colored.contains("\x1b[38;2;"));
+/// assert_eq!(colored.lines().filter(|line| line.contains("mean")).count(), 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Colored<'a, T> {
+    probabilities: &'a [Probability<T>],
+    options: FormatOptions,
+    mean_index: Option<usize>,
+    median_index: Option<usize>,
+}
+
+impl<'a, T> Colored<'a, T>
+where
+    T: Copy,
+    f64: From<T>,
+{
+    /// Prepares the given probabilities to be displayed in color with the given options.
+    pub fn new(probabilities: &'a [Probability<T>], options: FormatOptions) -> Self {
+        let mean = calc_mean(probabilities);
+        let mean_index = (0..probabilities.len()).min_by(|&a, &b| {
+            let distance = |index: usize| (f64::from(probabilities[index].value) - mean).abs();
+            distance(a).total_cmp(&distance(b))
+        });
+        let mut cumulative = 0.0;
+        let median_index = probabilities.iter().position(|prob| {
+            cumulative += prob.chance;
+            cumulative >= 0.5 - ALLOWED_ERROR
+        });
+        Colored {
+            probabilities,
+            options,
+            mean_index,
+            median_index,
+        }
+    }
+}
+
+impl<T> std::fmt::Display for Colored<'_, T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let max_chance = self
+            .probabilities
+            .iter()
+            .map(|prob| prob.chance)
+            .fold(0.0, f64::max);
+        let plain = FormatOptions {
+            bar_length: 0,
+            ..self.options
+        };
+        for (index, prob) in self.probabilities.iter().enumerate() {
+            let markers: Vec<&str> = [(self.mean_index, "mean"), (self.median_index, "median")]
+                .iter()
+                .filter(|(marked, _)| *marked == Some(index))
+                .map(|&(_, marker)| marker)
+                .collect();
+            let row = format_row(&prob.value, prob.chance, &plain);
+            if markers.is_empty() {
+                write!(f, "{}", row)?;
+            } else {
+                write!(f, "{}{}{}", BOLD, row, RESET)?;
+            }
+            if self.options.bar_length > 0 {
+                let filled = ((prob.chance * self.options.bar_length as f64).floor() as usize)
+                    .min(self.options.bar_length);
+                let (red, green, blue) = gradient(if max_chance > 0.0 {
+                    prob.chance / max_chance
+                } else {
+                    0.0
+                });
+                write!(
+                    f,
+                    " : \x1b[38;2;{};{};{}m{}{}{}",
+                    red,
+                    green,
+                    blue,
+                    "#".repeat(filled),
+                    RESET,
+                    "-".repeat(self.options.bar_length - filled)
+                )?;
+            }
+            if !markers.is_empty() {
+                write!(f, " {}<- {}{}", BOLD, markers.join(", "), RESET)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Color going from blue at `0.0` over green to red at `1.0`.
+fn gradient(position: f64) -> (u8, u8, u8) {
+    let position = position.clamp(0.0, 1.0);
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    (
+        channel(2.0 * position - 1.0),
+        channel(1.0 - (2.0 * position - 1.0).abs()),
+        channel(1.0 - 2.0 * position),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Die, NormalInitializer, ProbabilityDistribution};
+
+    #[test]
+    fn coloring() {
+        assert_eq!(gradient(0.0), (0, 0, 255));
+        assert_eq!(gradient(0.5), (0, 255, 0));
+        assert_eq!(gradient(1.0), (255, 0, 0));
+
+        let options = FormatOptions {
+            number_width: 1,
+            decimals: 0,
+            bar_length: 2,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            Die::new(2).display_colored(options).to_string(),
+            "\x1b[1m1 : 50\x1b[0m : \x1b[38;2;255;0;0m#\x1b[0m- \x1b[1m<- mean, median\x1b[0m\n\
+             2 : 50 : \x1b[38;2;255;0;0m#\x1b[0m-\n"
+        );
+    }
+}
@@ -1,12 +1,95 @@
+use crate::format_options::FormatOptions;
 use crate::probability::Probability;
+use crate::probability_distribution::{ChiSquaredResult, DominanceResult, ProbabilityDistribution};
 use std::collections::HashMap;
 
-pub const NAME_FORMAT: usize = 20;
-pub const NUMBER_FORMAT: usize = 10;
-pub const DECIMAL_FORMAT: usize = 3;
-pub const BAR_LENGTH: usize = 50;
 pub const ALLOWED_ERROR: f64 = 1e-5;
 
+/// Asserts that both distributions have the same values with the same chances, since `==` only
+/// compares the values.
+#[cfg(test)]
+pub fn assert_same_chances<T>(actual: &[Probability<T>], expected: &[Probability<T>])
+where
+    T: PartialEq + std::fmt::Debug,
+{
+    assert_eq!(actual, expected);
+    for (actual, expected) in actual.iter().zip(expected) {
+        assert!(
+            (actual.chance - expected.chance).abs() < ALLOWED_ERROR,
+            "chance of {:?} is {} instead of {}",
+            actual.value,
+            actual.chance,
+            expected.chance
+        );
+    }
+}
+
+pub fn format_row<T>(value: &T, chance: f64, options: &FormatOptions) -> String
+where
+    T: std::fmt::Display,
+{
+    let row = format!(
+        "{:>width$} : {:>width$.decimals$}",
+        value,
+        chance * 100.0,
+        width = options.number_width,
+        decimals = options.decimals
+    );
+    if options.bar_length == 0 {
+        return row;
+    }
+    format!(
+        "{} : {:-<bar_length$}",
+        row,
+        "#".repeat((chance * options.bar_length as f64).floor() as usize),
+        bar_length = options.bar_length
+    )
+}
+
+pub fn detail_rows<T, D>(distribution: &D, options: &FormatOptions) -> Vec<(String, String)>
+where
+    D: ProbabilityDistribution<T> + ?Sized,
+    T: Copy + std::ops::Mul<T, Output = T> + std::fmt::Display,
+    Probability<T>: Ord,
+    f64: From<T>,
+{
+    vec![
+        ("Min".to_string(), distribution.get_min().to_string()),
+        ("Max".to_string(), distribution.get_max().to_string()),
+        (
+            "Mean".to_string(),
+            format!("{:.*}", options.decimals, distribution.get_mean()),
+        ),
+        (
+            "Variance".to_string(),
+            format!("{:.*}", options.decimals, distribution.get_variance()),
+        ),
+        (
+            "Standard Deviation".to_string(),
+            format!(
+                "{:.*}",
+                options.decimals,
+                distribution.get_standard_deviation()
+            ),
+        ),
+    ]
+}
+
+pub fn format_details(rows: &[(String, String)], options: &FormatOptions) -> String {
+    rows.iter()
+        .map(|(name, value)| {
+            format!(
+                "{:<name_width$}{:>number_width$}",
+                name,
+                value,
+                name_width = options.name_width,
+                number_width = options.number_width
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub fn values_to_probabilities<T>(values: &[T]) -> Vec<Probability<T>>
 where
     T: Copy,
@@ -50,7 +133,80 @@ where
     calc_variance(values).sqrt()
 }
 
-pub fn compress_additive<T>(values: &[Probability<T>]) -> Vec<Probability<T>>
+/// Smallest value whose cumulative chance reaches `alpha`, the values being sorted.
+pub fn calc_value_at_risk<T>(values: &[Probability<T>], alpha: f64) -> T
+where
+    T: Copy,
+{
+    assert!(
+        alpha > 0.0 && alpha <= 1.0,
+        "alpha has to be above 0 and at most 1"
+    );
+    let mut cumulative = 0.0;
+    values
+        .iter()
+        .find(|prob| {
+            cumulative += prob.chance;
+            cumulative >= alpha - ALLOWED_ERROR
+        })
+        .or(values.last())
+        .unwrap()
+        .value
+}
+
+/// Mean of the lowest `alpha` share of the chance, the values being sorted. The value where
+/// that share ends only counts with the part of its chance inside of it.
+pub fn calc_conditional_value_at_risk<T>(values: &[Probability<T>], alpha: f64) -> f64
+where
+    f64: From<T>,
+    T: Copy,
+{
+    assert!(
+        alpha > 0.0 && alpha <= 1.0,
+        "alpha has to be above 0 and at most 1"
+    );
+    let mut remaining = alpha;
+    let mut total = 0.0;
+    for prob in values {
+        let share = prob.chance.min(remaining);
+        total += share * f64::from(prob.value);
+        remaining -= share;
+        if remaining <= 0.0 {
+            break;
+        }
+    }
+    total / (alpha - remaining.max(0.0))
+}
+
+/// Same as [`compress_additive`], but also merges every outcome with a chance below `epsilon`
+/// into the next lower kept outcome, or the lowest kept outcome if there is none below. The
+/// total chance stays the same.
+///
+/// If every outcome falls below `epsilon`, only the most likely one is kept.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ compress_additive_with_epsilon, Probability };
+/// let compressed = compress_additive_with_epsilon(
+///     &[
+///         Probability { value: 1, chance: 0.5 },
+///         Probability { value: 2, chance: 0.0001 },
+///         Probability { value: 3, chance: 0.4999 },
+///     ],
+///     0.001,
+/// );
+/// let outcomes: Vec<(i32, f64)> =
+///     compressed.iter().map(|prob| (prob.value, prob.chance)).collect();
+/// assert_eq!(outcomes.len(), 2);
+/// assert_eq!(outcomes[0].0, 1);
+/// assert!((outcomes[0].1 - 0.5001).abs() < 1e-12);
+/// assert_eq!(outcomes[1].0, 3);
+/// assert!((outcomes[1].1 - 0.4999).abs() < 1e-12);
+/// ```
+pub fn compress_additive_with_epsilon<T>(
+    values: &[Probability<T>],
+    epsilon: f64,
+) -> Vec<Probability<T>>
 where
     Probability<T>: Ord,
     T: std::cmp::Eq + std::hash::Hash + Copy,
@@ -65,13 +221,332 @@ where
         }
     }
 
-    let mut result = Vec::new();
+    let mut compressed = Vec::new();
     for (key, value) in value_map {
-        result.push(Probability {
+        compressed.push(Probability {
             value: key,
             chance: value,
         });
     }
-    result.sort();
+    compressed.sort();
+    if !compressed.iter().any(|prob| prob.chance >= epsilon) {
+        let total = compressed.iter().fold(0.0, |acc, prob| acc + prob.chance);
+        return compressed
+            .into_iter()
+            .max_by(|a, b| a.chance.total_cmp(&b.chance))
+            .map(|prob| {
+                vec![Probability {
+                    value: prob.value,
+                    chance: total,
+                }]
+            })
+            .unwrap_or_default();
+    }
+
+    let mut result: Vec<Probability<T>> = Vec::with_capacity(compressed.len());
+    let mut carried = 0.0;
+    for prob in compressed {
+        if prob.chance >= epsilon {
+            result.push(Probability {
+                value: prob.value,
+                chance: prob.chance + carried,
+            });
+            carried = 0.0;
+        } else if let Some(previous) = result.last_mut() {
+            previous.chance += prob.chance;
+        } else {
+            carried += prob.chance;
+        }
+    }
     result
 }
+
+/// Merges the chances of equal values and sorts the result, keeping every outcome.
+pub fn compress_additive<T>(values: &[Probability<T>]) -> Vec<Probability<T>>
+where
+    Probability<T>: Ord,
+    T: std::cmp::Eq + std::hash::Hash + Copy,
+{
+    compress_additive_with_epsilon(values, 0.0)
+}
+
+pub fn calc_dominance<T>(values: &[Probability<T>], other: &[Probability<T>]) -> DominanceResult
+where
+    f64: From<T>,
+    T: Ord + Copy,
+{
+    let mut points: Vec<T> = values.iter().chain(other).map(|prob| prob.value).collect();
+    points.sort();
+    points.dedup();
+
+    let cumulative_at = |probabilities: &[Probability<T>], point: T| {
+        probabilities
+            .iter()
+            .filter(|prob| prob.value <= point)
+            .fold(0.0, |acc, prob| acc + prob.chance)
+    };
+
+    let mut first_order = true;
+    let mut second_order = true;
+    let mut strict = false;
+    let mut area = 0.0;
+    let mut other_area = 0.0;
+    for (index, point) in points.iter().enumerate() {
+        let cumulative = cumulative_at(values, *point);
+        let other_cumulative = cumulative_at(other, *point);
+        if cumulative > other_cumulative + ALLOWED_ERROR {
+            first_order = false;
+        } else if cumulative < other_cumulative - ALLOWED_ERROR {
+            strict = true;
+        }
+        if let Some(next) = points.get(index + 1) {
+            let width = f64::from(*next) - f64::from(*point);
+            area += cumulative * width;
+            other_area += other_cumulative * width;
+            if area > other_area + ALLOWED_ERROR {
+                second_order = false;
+            }
+        }
+    }
+
+    match (strict, first_order, second_order) {
+        (false, true, _) => DominanceResult::Equal,
+        (true, true, _) => DominanceResult::FirstOrder,
+        (_, false, true) => DominanceResult::SecondOrder,
+        (_, false, false) => DominanceResult::None,
+    }
+}
+
+pub fn calc_chi_squared<T>(values: &[Probability<T>], observed: &[(T, u64)]) -> ChiSquaredResult
+where
+    T: PartialEq + Copy,
+{
+    let total = observed.iter().fold(0, |acc, (_, count)| acc + count) as f64;
+    let count_of = |value: T| {
+        observed
+            .iter()
+            .filter(|(observed_value, _)| *observed_value == value)
+            .fold(0, |acc, (_, count)| acc + count) as f64
+    };
+    let unexpected = observed
+        .iter()
+        .any(|(value, count)| *count > 0 && !values.iter().any(|prob| prob.value == *value));
+
+    let degrees_of_freedom = values.len().saturating_sub(1);
+    let statistic = if unexpected {
+        f64::INFINITY
+    } else {
+        values.iter().fold(0.0, |acc, prob| {
+            let expected = prob.chance * total;
+            if expected > 0.0 {
+                let difference = count_of(prob.value) - expected;
+                acc + difference * difference / expected
+            } else {
+                acc
+            }
+        })
+    };
+    let p_value = if statistic.is_infinite() {
+        0.0
+    } else if degrees_of_freedom == 0 {
+        1.0
+    } else {
+        regularized_upper_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+    };
+
+    ChiSquaredResult {
+        statistic,
+        degrees_of_freedom,
+        p_value,
+    }
+}
+
+pub fn calc_normal_approximation_error<T>(values: &[Probability<T>]) -> f64
+where
+    f64: From<T>,
+    T: std::ops::Mul<Output = T> + Copy,
+{
+    let mean = calc_mean(values);
+    let standard_deviation = calc_standard_deviation(values);
+    let mut cumulative = 0.0;
+    values.iter().fold(0.0, |max_error: f64, prob| {
+        cumulative += prob.chance;
+        // continuity correction, the value covers everything up to the next integer
+        let x = f64::from(prob.value) + 0.5;
+        let approximated = if standard_deviation > 0.0 {
+            normal_cdf((x - mean) / standard_deviation)
+        } else if x >= mean {
+            1.0
+        } else {
+            0.0
+        };
+        max_error.max((cumulative - approximated).abs())
+    })
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * complementary_error_function(-z / std::f64::consts::SQRT_2)
+}
+
+/// Inverse of [`normal_cdf`], found via bisection.
+pub fn normal_quantile(p: f64) -> f64 {
+    const ITERATIONS: usize = 100;
+    let (mut low, mut high) = (-40.0, 40.0);
+    for _ in 0..ITERATIONS {
+        let middle = 0.5 * (low + high);
+        if normal_cdf(middle) < p {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Chebyshev approximation of `erfc(x)`, with a relative error below `1.2e-7`.
+fn complementary_error_function(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let result = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+/// Lanczos approximation of `ln(Γ(x))`.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000000000190015, |acc, (index, coefficient)| {
+            acc + coefficient / (x + 1.0 + index as f64)
+        });
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x)`.
+fn regularized_upper_gamma(a: f64, x: f64) -> f64 {
+    const ITERATIONS: usize = 200;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let log_prefix = -x + a * x.ln() - ln_gamma(a);
+    if x < a + 1.0 {
+        // series expansion of the lower function
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        1.0 - sum * log_prefix.exp()
+    } else {
+        // continued fraction via the modified Lentz method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for n in 1..ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        log_prefix.exp() * h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressing_with_epsilon() {
+        let values = [
+            Probability {
+                value: 1,
+                chance: 0.001,
+            },
+            Probability {
+                value: 2,
+                chance: 0.5,
+            },
+            Probability {
+                value: 3,
+                chance: 0.001,
+            },
+            Probability {
+                value: 2,
+                chance: 0.498,
+            },
+        ];
+        let certain = [Probability {
+            value: 2,
+            chance: 1.0,
+        }];
+        let exact = [(1, 0.001), (2, 0.998), (3, 0.001)]
+            .map(|(value, chance)| Probability { value, chance });
+        assert_same_chances(&compress_additive_with_epsilon(&values, 0.01), &certain);
+        assert_same_chances(&compress_additive_with_epsilon(&values, 0.0), &exact);
+        assert_same_chances(&compress_additive(&values), &exact);
+        assert_same_chances(&compress_additive_with_epsilon(&values, 0.999), &certain);
+
+        let spread = [
+            Probability {
+                value: 1,
+                chance: 0.1,
+            },
+            Probability {
+                value: 2,
+                chance: 0.2,
+            },
+        ];
+        let merged = [Probability {
+            value: 2,
+            chance: 0.3,
+        }];
+        assert_same_chances(&compress_additive_with_epsilon(&spread, 0.5), &merged);
+        assert_same_chances(&compress_additive_with_epsilon(&spread, 0.15), &merged);
+        assert!(compress_additive_with_epsilon::<i32>(&[], 0.5).is_empty());
+    }
+}
@@ -1,8 +1,25 @@
 use crate::common::*;
+use crate::drop_initializer::prep;
+use crate::normal_initializer::new_uniform;
+use crate::polynomial::SparsePolynomial;
 use crate::probability::Probability;
 use crate::probability_distribution::ProbabilityDistribution;
-use crate::NormalInitializer;
-use core::ops::Add;
+use crate::uniform_sum::uniform_sum;
+use crate::{
+    at_least, ExplodingCondition, ExplosionStats, FormatOptions, NormalInitializer, OverflowError,
+    OverflowPolicy,
+};
+use core::cmp::{Ordering, Reverse};
+use core::ops::{Add, Neg, Range, RangeInclusive, Rem};
+use core::str::FromStr;
+use smallvec::SmallVec;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::sync::OnceLock;
+
+/// Amount of probabilities a [`Die`] stores without allocating, enough for everything up to a
+/// d20.
+const INLINE_PROBABILITIES: usize = 20;
 
 /// A representation of a die, using the provided initializers.
 ///
@@ -52,10 +69,1695 @@ use core::ops::Add;
 /// ```
 #[derive(Debug, Clone)]
 pub struct Die {
-    probabilities: Vec<Probability<i32>>,
+    probabilities: SmallVec<[Probability<i32>; INLINE_PROBABILITIES]>,
+    approximate: bool,
+    truncated_mass: f64,
+    label: Option<Box<Label>>,
+    stats: OnceLock<Stats>,
+}
+
+/// Statistics of a [`Die`], calculated on first access since the probabilities never change
+/// after creation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Stats {
+    min: Option<i32>,
+    max: Option<i32>,
+    mean: f64,
+    variance: f64,
+}
+
+/// Name and metadata attached to a [`Die`], boxed to keep unlabeled dice small.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Label {
+    name: Option<String>,
+    metadata: BTreeMap<String, String>,
+    description: Option<String>,
+}
+
+impl Die {
+    /// Creates the empirical distribution of the given recorded rolls, using the observed
+    /// frequency of each value as its chance.
+    ///
+    /// When given an empty slice, creates an [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let recorded = Die::from_samples(&[1, 2, 2, 4]);
+    /// assert_eq!(
+    ///     recorded.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 1, chance: 0.25 },
+    ///         Probability { value: 2, chance: 0.5 },
+    ///         Probability { value: 4, chance: 0.25 },
+    ///     ]);
+    /// ```
+    pub fn from_samples(samples: &[i32]) -> Die {
+        Die::from_sample_iter(samples.iter().copied())
+    }
+
+    /// Same as [`Die::from_samples`], but consumes the recorded rolls one by one, so they never
+    /// need to be collected in memory.
+    pub fn from_sample_iter<I>(samples: I) -> Die
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        Die::from_sample_iter_smoothed(samples, 0.0)
+    }
+
+    /// Same as [`Die::from_samples`], but applies Laplace smoothing by adding `smoothing` to the
+    /// count of every value between the lowest and highest recorded roll.
+    ///
+    /// This gives values that happened to never be rolled a small chance instead of none.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let recorded = Die::from_samples_smoothed(&[1, 1, 3], 1.0);
+    /// assert_eq!(
+    ///     recorded.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 1, chance: 0.5 },
+    ///         Probability { value: 2, chance: 0.16666666666666666 },
+    ///         Probability { value: 3, chance: 0.3333333333333333 },
+    ///     ]);
+    /// ```
+    pub fn from_samples_smoothed(samples: &[i32], smoothing: f64) -> Die {
+        Die::from_sample_iter_smoothed(samples.iter().copied(), smoothing)
+    }
+
+    /// Streaming variant of [`Die::from_samples_smoothed`].
+    pub fn from_sample_iter_smoothed<I>(samples: I, smoothing: f64) -> Die
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        let mut counts = BTreeMap::new();
+        for sample in samples {
+            *counts.entry(sample).or_insert(0.0) += 1.0;
+        }
+        if let (Some(&min), Some(&max)) = (counts.keys().next(), counts.keys().next_back()) {
+            if smoothing > 0.0 {
+                for value in min..=max {
+                    *counts.entry(value).or_insert(0.0) += smoothing;
+                }
+            }
+        }
+        let total: f64 = counts.values().sum();
+        Die::from_probabilities(
+            counts
+                .into_iter()
+                .map(|(value, count)| Probability {
+                    value,
+                    chance: count / total,
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates a die from integer weights, normalizing them so every value gets a chance of its
+    /// weight divided by the total weight.
+    ///
+    /// Values listed multiple times have their weights added up and values with a weight of `0`
+    /// are left out. Creates an [empty die][`Die::empty()`] if the total weight is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, ProbabilityDistribution, NormalInitializer };
+    /// // a d6 loaded towards six
+    /// let loaded = Die::from_weights(&[(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 3)]);
+    /// let six = loaded.get_probabilities()[5];
+    /// assert_eq!(six.value, 6);
+    /// assert!((six.chance - 0.375).abs() < 1e-12);
+    /// ```
+    pub fn from_weights(weights: &[(i32, u64)]) -> Die {
+        Die::from_integer_weights(weights.iter().copied())
+    }
+
+    /// Creates a die from observed counts per value, like a frequency map from logs or a tally
+    /// sheet. Normalizes the same way as [`Die::from_weights`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, ProbabilityDistribution };
+    /// # use std::collections::HashMap;
+    /// let tally = HashMap::from([(1, 12), (2, 8), (3, 10), (4, 10)]);
+    /// let observed = Die::from_counts(tally);
+    /// let one = observed.get_probabilities()[0];
+    /// assert_eq!(one.value, 1);
+    /// assert!((one.chance - 0.3).abs() < 1e-9);
+    /// ```
+    pub fn from_counts<I>(counts: I) -> Die
+    where
+        I: IntoIterator<Item = (i32, u64)>,
+    {
+        Die::from_integer_weights(counts)
+    }
+
+    /// Creates a single trial succeeding with a chance of `p`, represented as `1` for a success
+    /// and `0` for a failure.
+    ///
+    /// # Panics
+    /// Panics if `p` is not between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let hit = Die::bernoulli(0.6);
+    /// assert_eq!(
+    ///     hit.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 0, chance: 0.4 },
+    ///         Probability { value: 1, chance: 0.6 },
+    ///     ]);
+    /// ```
+    pub fn bernoulli(p: f64) -> Die {
+        assert!((0.0..=1.0).contains(&p), "p has to be between 0 and 1");
+        Die::from_probabilities(
+            [
+                Probability {
+                    value: 0,
+                    chance: 1.0 - p,
+                },
+                Probability {
+                    value: 1,
+                    chance: p,
+                },
+            ]
+            .into_iter()
+            .filter(|prob| prob.chance > 0.0)
+            .collect(),
+        )
+    }
+
+    /// Creates a fair coin, meaning a [bernoulli trial][`Die::bernoulli`] with a chance of `0.5`.
+    pub fn coin() -> Die {
+        Die::bernoulli(0.5)
+    }
+
+    /// Creates the distribution of successes in `n` independent trials, each succeeding with a
+    /// chance of `p`.
+    ///
+    /// # Panics
+    /// Panics if `p` is not between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let hits = Die::binomial(2, 0.5);
+    /// assert_eq!(
+    ///     hits.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 0, chance: 0.25 },
+    ///         Probability { value: 1, chance: 0.5 },
+    ///         Probability { value: 2, chance: 0.25 },
+    ///     ]);
+    /// ```
+    pub fn binomial(n: u32, p: f64) -> Die {
+        assert!((0.0..=1.0).contains(&p), "p has to be between 0 and 1");
+        if p == 0.0 {
+            return Die::empty();
+        }
+        if p == 1.0 {
+            return Die::from(n as i32);
+        }
+        let mut ln_choose = 0.0;
+        Die::from_probabilities(
+            (0..=n)
+                .map(|k| {
+                    if k > 0 {
+                        ln_choose += f64::from(n - k + 1).ln() - f64::from(k).ln();
+                    }
+                    Probability {
+                        value: k as i32,
+                        chance: (ln_choose
+                            + f64::from(k) * p.ln()
+                            + f64::from(n - k) * (1.0 - p).ln())
+                        .exp(),
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates the distribution of the amount of trials needed for the first success, each
+    /// succeeding with a chance of `p`, capped at `max_trials`.
+    ///
+    /// Everything that would take longer than `max_trials` is counted as `max_trials`, so the
+    /// last value also holds the chance of never succeeding in time. When given `0` as
+    /// `max_trials`, creates an [empty die][`Die::empty()`].
+    ///
+    /// # Panics
+    /// Panics if `p` is not between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let rolls_needed = Die::geometric(0.5, 3);
+    /// assert_eq!(
+    ///     rolls_needed.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 1, chance: 0.5 },
+    ///         Probability { value: 2, chance: 0.25 },
+    ///         Probability { value: 3, chance: 0.25 },
+    ///     ]);
+    /// ```
+    pub fn geometric(p: f64, max_trials: u32) -> Die {
+        assert!((0.0..=1.0).contains(&p), "p has to be between 0 and 1");
+        if max_trials == 0 {
+            return Die::empty();
+        }
+        let mut remaining = 1.0;
+        Die::from_probabilities(
+            (1..=max_trials)
+                .map(|trial| {
+                    let chance = if trial == max_trials {
+                        remaining
+                    } else {
+                        remaining * p
+                    };
+                    remaining -= chance;
+                    Probability {
+                        value: trial as i32,
+                        chance,
+                    }
+                })
+                .filter(|prob| prob.chance > 0.0)
+                .collect(),
+        )
+    }
+
+    /// Creates a Poisson distribution with the mean `lambda`, truncated to the values from `0` up
+    /// to, and including, `max`.
+    ///
+    /// A Poisson distribution has no upper bound, so the chances of every value above `max` are
+    /// dropped and the remaining chances are scaled up to add up to `1.0` again. This slightly
+    /// increases the chance of every kept value, so `max` should be chosen well above `lambda`
+    /// to keep the result close to the real distribution.
+    ///
+    /// # Panics
+    /// Panics if `lambda` is negative or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let encounters = Die::poisson(1.5, 20);
+    /// assert_eq!(encounters.get_min(), 0);
+    /// assert_eq!(encounters.get_max(), 20);
+    /// assert!((encounters.get_mean() - 1.5).abs() < 1e-9);
+    /// ```
+    pub fn poisson(lambda: f64, max: u32) -> Die {
+        assert!(
+            lambda.is_finite() && lambda >= 0.0,
+            "lambda has to be a finite, non-negative number"
+        );
+        if lambda == 0.0 {
+            return Die::empty();
+        }
+        let mut ln_factorial = 0.0;
+        let weights: Vec<f64> = (0..=max)
+            .map(|k| {
+                if k > 0 {
+                    ln_factorial += f64::from(k).ln();
+                }
+                (f64::from(k) * lambda.ln() - lambda - ln_factorial).exp()
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        Die::from_probabilities(
+            weights
+                .into_iter()
+                .enumerate()
+                .map(|(k, weight)| Probability {
+                    value: k as i32,
+                    chance: weight / total,
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns a compact, parseable representation of this die, listing every value and its
+    /// chance as `value:chance`, separated by `, `.
+    ///
+    /// Parsing the result via [`str::parse`] recreates the exact same die.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer };
+    /// let d4 = Die::new(4);
+    /// assert_eq!(d4.to_canonical_string(), "1:0.25, 2:0.25, 3:0.25, 4:0.25");
+    /// assert_eq!(d4.to_canonical_string().parse::<Die>(), Ok(d4));
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        self.probabilities
+            .iter()
+            .map(|prob| format!("{}:{}", prob.value, prob.chance))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Parses a pasted table of values and chances in percent, like an
+    /// [AnyDice export][`Die::to_anydice`] or two spreadsheet columns.
+    ///
+    /// Columns may be separated by commas, semicolons or whitespace and chances may end with a
+    /// `%`. Lines not starting with a number, like headers, are skipped. Since exported tables
+    /// are usually rounded, the chances only have to add up to within one percent of `100` and
+    /// are normalized afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let pasted = "\"output 1\",2.00,0.82,1,3\n#,%\n1,33.33\n2,33.33\n3,33.33\n";
+    /// let d3 = Die::from_table(pasted).unwrap();
+    /// assert_eq!(d3, Die::new(3));
+    /// assert!((d3.get_chance(1) - 1.0 / 3.0).abs() < 1e-12);
+    /// assert_eq!(Die::from_table("Value\tChance\n1\t50%\n2\t50%"), Ok(Die::new(2)));
+    /// ```
+    pub fn from_table(table: &str) -> Result<Die, ParseDieError> {
+        let mut probabilities = Vec::new();
+        for line in table.lines() {
+            let fields: Vec<&str> = line
+                .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .collect();
+            let is_data = fields.first().is_some_and(|field| {
+                field
+                    .trim_start_matches('-')
+                    .starts_with(|c: char| c.is_ascii_digit())
+            });
+            if !is_data {
+                continue;
+            }
+            let [value, chance] = fields[..] else {
+                return Err(ParseDieError::InvalidEntry(line.trim().to_string()));
+            };
+            probabilities.push(Probability {
+                value: value
+                    .parse()
+                    .map_err(|_| ParseDieError::InvalidValue(value.to_string()))?,
+                chance: chance
+                    .trim_end_matches('%')
+                    .parse()
+                    .ok()
+                    .filter(|chance: &f64| chance.is_finite() && *chance >= 0.0)
+                    .ok_or_else(|| ParseDieError::InvalidChance(chance.to_string()))?
+                    / 100.0,
+            });
+        }
+        let sum = probabilities
+            .iter()
+            .fold(0.0, |acc, curr| acc + curr.chance);
+        if probabilities.is_empty() || (sum - 1.0).abs() > 0.01 {
+            return Err(ParseDieError::InvalidSum);
+        }
+        for prob in &mut probabilities {
+            prob.chance /= sum;
+        }
+        Ok(Die::from_probabilities(probabilities))
+    }
+
+    /// Sums up every die in the given slice.
+    ///
+    /// Always combines the two dice with the fewest outcomes next, which keeps the intermediate
+    /// results as small as possible. When given an empty slice, creates an
+    /// [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let three_d6 = Die::sum_all(&[Die::new(6), Die::new(6), Die::new(6)]);
+    /// assert_eq!(three_d6.get_min(), 3);
+    /// assert_eq!(three_d6.get_max(), 18);
+    /// ```
+    pub fn sum_all(dice: &[Die]) -> Die {
+        let mut heap: BinaryHeap<_> = dice
+            .iter()
+            .map(|die| Reverse(BySupport(die.clone())))
+            .collect();
+        while heap.len() > 1 {
+            let Reverse(BySupport(first)) = heap.pop().unwrap();
+            let Reverse(BySupport(second)) = heap.pop().unwrap();
+            heap.push(Reverse(BySupport(&first + &second)));
+        }
+        heap.pop()
+            .map_or_else(Die::empty, |Reverse(BySupport(die))| die)
+    }
+
+    /// Rolls this die `n` times and sums up the results.
+    ///
+    /// Doubles up the intermediate sums instead of adding one die at a time. Dice rolling
+    /// consecutive values with equal chance, like [`Die::new`], are summed via their closed form
+    /// instead, which stays exact and fast even for hundreds of dice. When given `0`, creates an
+    /// [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer };
+    /// assert_eq!(Die::new(6).repeat(3), Die::sum_all(&vec![Die::new(6); 3]));
+    /// ```
+    pub fn repeat(&self, n: usize) -> Die {
+        self.try_repeat(n, OverflowPolicy::Saturate)
+            .expect("saturating never overflows")
+    }
+
+    /// Same as [`add_flat`][`ProbabilityDistribution::add_flat`], but handles values leaving the
+    /// range of `i32` according to the given policy.
+    ///
+    /// The plain arithmetic of [`Die`] always saturates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, OverflowPolicy, ProbabilityDistribution };
+    /// let huge = Die::from(i32::MAX - 1);
+    /// assert!(huge.try_add_flat(2, OverflowPolicy::Error).is_err());
+    /// assert_eq!(
+    ///     huge.try_add_flat(2, OverflowPolicy::Saturate),
+    ///     Ok(Die::from(i32::MAX))
+    /// );
+    /// ```
+    pub fn try_add_flat(
+        &self,
+        flat_increase: i32,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Die, OverflowError> {
+        let die = Die::from_probabilities(
+            self.probabilities
+                .iter()
+                .map(|prob| {
+                    Ok(Probability {
+                        value: overflow_policy.add(prob.value, flat_increase)?,
+                        chance: prob.chance,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        )
+        .with_label_of(self)
+        .with_approximation_of(self, 1)
+        .with_described(
+            self.describe()
+                .map(|description| describe_flat(&description, flat_increase)),
+        );
+        let stats = self.stats();
+        Ok(
+            match (
+                stats.min.and_then(|min| min.checked_add(flat_increase)),
+                stats.max.and_then(|max| max.checked_add(flat_increase)),
+            ) {
+                (Some(min), Some(max)) => die.with_stats(Stats {
+                    min: Some(min),
+                    max: Some(max),
+                    mean: stats.mean + f64::from(flat_increase),
+                    variance: stats.variance,
+                }),
+                _ => die,
+            },
+        )
+    }
+
+    /// Multiplies every value by `factor`, like doubling the damage dice of a critical hit.
+    ///
+    /// Values leaving the range of `i32` saturate. The mean and variance of the result are
+    /// derived from the ones of this die instead of being calculated again.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let doubled = Die::new(6).scale(2);
+    /// assert_eq!(doubled, Die::from_values(&[2, 4, 6, 8, 10, 12]));
+    /// assert_eq!(doubled.get_mean(), 7.0);
+    /// assert_eq!((-Die::new(4)).get_max(), -1);
+    /// ```
+    pub fn scale(&self, factor: i32) -> Die {
+        let die = self
+            .map_values(|value| value.saturating_mul(factor))
+            .with_described(self.describe().map(|description| match factor {
+                -1 => format!("-{}", describe_grouped(&description)),
+                _ => format!("{} * {}", describe_grouped(&description), factor),
+            }));
+        let stats = self.stats();
+        let (low, high) = match (
+            stats.min.and_then(|min| min.checked_mul(factor)),
+            stats.max.and_then(|max| max.checked_mul(factor)),
+        ) {
+            (Some(low), Some(high)) => (low, high),
+            _ => return die,
+        };
+        die.with_stats(Stats {
+            min: Some(low.min(high)),
+            max: Some(low.max(high)),
+            mean: stats.mean * f64::from(factor),
+            variance: stats.variance * f64::from(factor) * f64::from(factor),
+        })
+    }
+
+    /// Same as [`add_independent`][`ProbabilityDistribution::add_independent`], but handles
+    /// values leaving the range of `i32` according to the given policy.
+    pub fn try_add_independent(
+        &self,
+        probability_distribution: &impl ProbabilityDistribution<i32>,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Die, OverflowError> {
+        Ok(Die::from_polynomial(
+            SparsePolynomial::from_probabilities(self.get_probabilities()).multiply(
+                &SparsePolynomial::from_probabilities(probability_distribution.get_probabilities()),
+                overflow_policy,
+            )?,
+        )
+        .with_approximation_of(self, 1))
+    }
+
+    /// Same as [`add_dependent`][`ProbabilityDistribution::add_dependent`], but handles values
+    /// leaving the range of `i32` according to the given policy.
+    ///
+    /// Exploding dice are built on top of dependent adding, so deep explosions can be checked
+    /// for overflows this way.
+    pub fn try_add_dependent<F, D>(
+        &self,
+        callback_fn: &F,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Die, OverflowError>
+    where
+        F: Fn(&i32) -> D,
+        D: ProbabilityDistribution<i32>,
+    {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for outer_prob in &self.probabilities {
+            for inner_prob in callback_fn(&outer_prob.value).get_probabilities() {
+                let value = overflow_policy.add(outer_prob.value, inner_prob.value)?;
+                *accumulated.entry(value).or_insert(0.0) += outer_prob.chance * inner_prob.chance;
+            }
+        }
+        Ok(Die::from_accumulated(accumulated).with_approximation_of(self, 1))
+    }
+
+    /// Same as [`Die::repeat`], but handles values leaving the range of `i32` according to the
+    /// given policy.
+    pub fn try_repeat(
+        &self,
+        n: usize,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Die, OverflowError> {
+        let sum = match self
+            .uniform_faces()
+            .and_then(|(start, faces)| (n > 1).then(|| uniform_sum(start, faces, n)).flatten())
+        {
+            Some(probabilities) => {
+                Die::from_polynomial(SparsePolynomial::from_probabilities(&probabilities))
+            }
+            None => Die::from_polynomial(
+                SparsePolynomial::from_probabilities(&self.probabilities)
+                    .power(n, overflow_policy)?,
+            ),
+        };
+        Ok(sum.with_approximation_of(self, n).with_described(
+            self.describe()
+                .map(|description| describe_repeat(&description, n)),
+        ))
+    }
+
+    /// Takes every value modulo `n`, merging the values that end up the same.
+    ///
+    /// Uses the euclidean remainder, so the result is always between `0` and `|n| - 1`, even for
+    /// negative values. For example `-1` modulo `10` results in `9`, matching a wrap-around
+    /// track.
+    ///
+    /// # Panics
+    /// Panics if `n` is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer };
+    /// let ones_digit = Die::new(100).rem_flat(10);
+    /// assert_eq!(ones_digit, Die::from_range(0, 9));
+    /// assert_eq!(Die::from_range(-2, -1) % 10, Die::from_values(&[8, 9]));
+    /// ```
+    pub fn rem_flat(&self, n: i32) -> Die {
+        assert!(
+            n != 0,
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        // only `i32::MIN` modulo `-1` overflows, its remainder is `0` all the same
+        self.map_values(|value| value.checked_rem_euclid(n).unwrap_or(0))
+            .with_described(
+                self.describe()
+                    .map(|description| format!("{} % {}", describe_grouped(&description), n)),
+            )
+    }
+
+    /// Takes the absolute of every value, merging the chances of `x` and `-x`.
+    ///
+    /// `i32::MIN` saturates to `i32::MAX`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let fudge = Die::from_range(-1, 1);
+    /// assert_eq!(
+    ///     fudge.abs().get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 0, chance: 1.0 / 3.0 },
+    ///         Probability { value: 1, chance: 2.0 / 3.0 },
+    ///     ]);
+    /// ```
+    pub fn abs(&self) -> Die {
+        self.map_values(i32::saturating_abs).with_described(
+            self.describe()
+                .map(|description| format!("|{}|", description)),
+        )
+    }
+
+    /// Turns every value into its degree of success against `target`, `-1` for falling short of
+    /// it, `0` for reaching it and one more for every full `step` beyond that, like raises in
+    /// Savage Worlds.
+    ///
+    /// # Panics
+    /// Panics if `step` is not positive.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let raises = Die::new(12).degrees_of_success(4, 4);
+    /// assert_eq!(raises, Die::from_values(&[-1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2]));
+    /// assert!((raises.get_chance(-1) - 0.25).abs() < 1e-12);
+    /// assert!((raises.get_chance(0) - 1.0 / 3.0).abs() < 1e-12);
+    /// assert!((raises.get_chance(1) - 1.0 / 3.0).abs() < 1e-12);
+    /// assert!((raises.get_chance(2) - 1.0 / 12.0).abs() < 1e-12);
+    /// assert_eq!(raises.describe().as_deref(), Some("d12 vs 4"));
+    /// ```
+    pub fn degrees_of_success(&self, target: i32, step: i32) -> Die {
+        assert!(step > 0, "step has to be positive");
+        self.map_values(|value| {
+            if value < target {
+                -1
+            } else {
+                ((i64::from(value) - i64::from(target)) / i64::from(step)) as i32
+            }
+        })
+        .with_described(
+            self.describe()
+                .map(|description| format!("{} vs {}", describe_grouped(&description), target)),
+        )
+    }
+
+    /// Splits this die up into the chance of falling short of `target` and the distribution of
+    /// how far it exceeds `target` when reaching it, like the margin of success of a check.
+    ///
+    /// Margins larger than `i32::MAX` saturate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let check = Die::new(20).margin_over(15);
+    /// assert!((check.failure - 0.7).abs() < 1e-12);
+    /// assert_eq!(check.margin, Some(Die::new(6).add_flat(-1)));
+    /// let margin = check.margin.unwrap();
+    /// assert!((margin.get_chance(0) - 1.0 / 6.0).abs() < 1e-12);
+    /// assert!((margin.get_chance(5) - 1.0 / 6.0).abs() < 1e-12);
+    /// assert_eq!(Die::new(20).margin_over(21).margin, None);
+    /// ```
+    pub fn margin_over(&self, target: i32) -> Margin {
+        let success: Vec<Probability<i32>> = self
+            .probabilities
+            .iter()
+            .filter(|prob| prob.value >= target)
+            .map(|prob| Probability {
+                value: (i64::from(prob.value) - i64::from(target)).min(i64::from(i32::MAX)) as i32,
+                chance: prob.chance,
+            })
+            .collect();
+        let success_chance = success.iter().fold(0.0, |acc, prob| acc + prob.chance);
+        Margin {
+            failure: self
+                .probabilities
+                .iter()
+                .filter(|prob| prob.value < target)
+                .fold(0.0, |acc, prob| acc + prob.chance),
+            margin: (success_chance > 0.0).then(|| {
+                Die::from_probabilities(
+                    success
+                        .into_iter()
+                        .map(|prob| prob * (1.0 / success_chance))
+                        .collect(),
+                )
+            }),
+        }
+    }
+
+    /// Splits this die up by `condition_fn` into the distribution given the condition holds, the
+    /// distribution given it doesn't, and the chance of it holding, like the damage on a hit and
+    /// on a miss.
+    ///
+    /// A side that can't happen at all is an [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let (hit, miss, hit_chance) = Die::new(20).partition(|&value| value >= 11);
+    /// assert_eq!(hit, Die::new(10).add_flat(10));
+    /// assert!((hit.get_chance(11) - 0.1).abs() < 1e-12);
+    /// assert_eq!(miss, Die::new(10));
+    /// assert!((miss.get_chance(10) - 0.1).abs() < 1e-12);
+    /// assert!((hit_chance - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn partition<F>(&self, condition_fn: F) -> (Die, Die, f64)
+    where
+        F: Fn(&i32) -> bool,
+    {
+        let (held, failed): (Vec<Probability<i32>>, Vec<Probability<i32>>) = self
+            .probabilities
+            .iter()
+            .partition(|prob| condition_fn(&prob.value));
+        let held_chance = held.iter().fold(0.0, |acc, prob| acc + prob.chance);
+        let conditioned = |probabilities: Vec<Probability<i32>>| {
+            let total = probabilities
+                .iter()
+                .fold(0.0, |acc, prob| acc + prob.chance);
+            if total > 0.0 {
+                Die::from_probabilities(
+                    probabilities
+                        .into_iter()
+                        .map(|prob| prob * (1.0 / total))
+                        .collect(),
+                )
+            } else {
+                Die::empty()
+            }
+        };
+        (conditioned(held), conditioned(failed), held_chance)
+    }
+
+    /// Returns the chance of rolling a value fulfilling `condition_fn` at least `occurrences`
+    /// times in `trials` independent rolls of this die.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer };
+    /// // at least one crit in 4 attacks
+    /// let any_crit = Die::new(20).at_least(|&value| value == 20, 1, 4);
+    /// assert!((any_crit - (1.0 - 0.95f64.powi(4))).abs() < 1e-12);
+    /// ```
+    pub fn at_least<F>(&self, condition_fn: F, occurrences: u32, trials: u32) -> f64
+    where
+        F: Fn(&i32) -> bool,
+    {
+        at_least(self.chance_of(condition_fn), occurrences, trials)
+    }
+
+    /// Creates the distribution of how many of `trials` independent rolls of this die result in
+    /// a value fulfilling `condition_fn`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// // hits of 3 attacks, each hitting on 11 or more
+    /// let hits = Die::new(20).count_over_trials(|&value| value >= 11, 3);
+    /// assert_eq!(hits, Die::binomial(3, 0.5));
+    /// assert!((hits.get_chance(0) - 0.125).abs() < 1e-12);
+    /// assert!((hits.get_chance(2) - 0.375).abs() < 1e-12);
+    /// ```
+    pub fn count_over_trials<F>(&self, condition_fn: F, trials: u32) -> Die
+    where
+        F: Fn(&i32) -> bool,
+    {
+        Die::binomial(trials, self.chance_of(condition_fn))
+    }
+
+    /// Returns the expected amount of rolls of this die until a value fulfilling `condition_fn`
+    /// comes up, including that roll. Returns [`f64::INFINITY`] if no value ever does.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer };
+    /// // a wandering monster shows up on a 1 on a d6
+    /// assert!((Die::new(6).expected_trials_until(|&value| value == 1) - 6.0).abs() < 1e-9);
+    /// ```
+    pub fn expected_trials_until<F>(&self, condition_fn: F) -> f64
+    where
+        F: Fn(&i32) -> bool,
+    {
+        1.0 / self.chance_of(condition_fn)
+    }
+
+    /// Creates the distribution of the amount of rolls of this die until a value fulfilling
+    /// `condition_fn` comes up, capped at `max_trials` like a [geometric
+    /// distribution][`Die::geometric`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let checks = Die::new(6).trials_until(|&value| value == 1, 3);
+    /// assert!((checks.get_survival(2) - 25.0 / 36.0).abs() < 1e-12);
+    /// ```
+    pub fn trials_until<F>(&self, condition_fn: F, max_trials: u32) -> Die
+    where
+        F: Fn(&i32) -> bool,
+    {
+        Die::geometric(self.chance_of(condition_fn), max_trials)
+    }
+
+    /// Creates the distribution of the amount of rolls of this die needed for their sum to reach
+    /// `target`, like filling a clock or finishing a race.
+    ///
+    /// Everything that would take longer than `max_rolls` is counted as `max_rolls`, which only
+    /// matters for dice able to roll `0` or less. Reaching a `target` of `0` or less doesn't
+    /// take any rolls at all.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let rolls = Die::new(6).rolls_to_reach(7, 10);
+    /// assert_eq!(rolls.get_min(), 2);
+    /// assert_eq!(rolls.get_max(), 7);
+    /// assert!((rolls.get_survival(1) - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn rolls_to_reach(&self, target: i32, max_rolls: u32) -> Die {
+        if target <= 0 || max_rolls == 0 {
+            return Die::empty();
+        }
+        let target = i64::from(target);
+        let mut sums: HashMap<i64, f64> = HashMap::from([(0, 1.0)]);
+        let mut rolls = Vec::new();
+        for roll in 1..=max_rolls {
+            let mut next = HashMap::with_capacity(sums.len());
+            let mut reached = 0.0;
+            for (&sum, &chance) in &sums {
+                for prob in &self.probabilities {
+                    let sum = sum + i64::from(prob.value);
+                    if sum >= target {
+                        reached += chance * prob.chance;
+                    } else {
+                        *next.entry(sum).or_insert(0.0) += chance * prob.chance;
+                    }
+                }
+            }
+            sums = next;
+            if roll == max_rolls {
+                reached += sums.values().sum::<f64>();
+            }
+            if reached > 0.0 {
+                rolls.push(Probability {
+                    value: roll as i32,
+                    chance: reached,
+                });
+            }
+            if sums.is_empty() {
+                break;
+            }
+        }
+        Die::from_probabilities(rolls)
+    }
+
+    /// Chance of rolling a value fulfilling `condition_fn`.
+    fn chance_of<F>(&self, condition_fn: F) -> f64
+    where
+        F: Fn(&i32) -> bool,
+    {
+        self.probabilities
+            .iter()
+            .filter(|prob| condition_fn(&prob.value))
+            .fold(0.0, |acc, prob| acc + prob.chance)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Creates the distribution of `combine_fn` applied to the rolls of this die and an
+    /// independent one, merging the results that end up the same.
+    ///
+    /// Covers any mechanic taking some function of two rolls, like the higher of two dice or
+    /// the product of a damage roll and a multiplier.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let d6 = Die::new(6);
+    /// assert_eq!(d6.combine_with(&d6, |a, b| a + b), d6.add_independent(&d6));
+    /// let advantage = Die::new(20).combine_with(&Die::new(20), i32::max);
+    /// assert!((advantage.get_mean() - 13.825).abs() < 1e-9);
+    /// ```
+    pub fn combine_with<F>(
+        &self,
+        probability_distribution: &impl ProbabilityDistribution<i32>,
+        combine_fn: F,
+    ) -> Die
+    where
+        F: Fn(i32, i32) -> i32,
+    {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for prob in &self.probabilities {
+            for other_prob in probability_distribution.get_probabilities() {
+                *accumulated
+                    .entry(combine_fn(prob.value, other_prob.value))
+                    .or_insert(0.0) += prob.chance * other_prob.chance;
+            }
+        }
+        Die::from_accumulated(accumulated)
+    }
+
+    /// Creates the distribution of `combine_fn` applied to the rolls of every die in the given
+    /// slice, merging the results that end up the same. The rolls are handed over in the order of
+    /// the slice.
+    ///
+    /// Every combination of rolls is evaluated one after another without being collected up
+    /// front, but their amount still grows exponentially with the amount of dice. When given an
+    /// empty slice, creates an [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// // the middle of three d20
+    /// let middle = Die::combine_all(&vec![Die::new(20); 3], |rolls| {
+    ///     let mut rolls = rolls.to_vec();
+    ///     rolls.sort_unstable();
+    ///     rolls[1]
+    /// });
+    /// assert!((middle.get_mean() - 10.5).abs() < 1e-9);
+    /// assert!(middle.get_chance(10) > Die::new(20).get_chance(10));
+    /// ```
+    pub fn combine_all<F>(dice: &[Die], combine_fn: F) -> Die
+    where
+        F: Fn(&[i32]) -> i32,
+    {
+        let mut accumulated = HashMap::new();
+        for (values, chance) in prep(dice) {
+            *accumulated.entry(combine_fn(&values)).or_insert(0.0) += chance;
+        }
+        Die::from_accumulated(accumulated)
+    }
+
+    /// Creates the distribution of the absolute difference between this die and an independent
+    /// one, like the margin of an opposed check.
+    ///
+    /// Differences larger than `i32::MAX` saturate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let margin = Die::new(2).abs_diff(&Die::new(2));
+    /// assert_eq!(
+    ///     margin.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 0, chance: 0.5 },
+    ///         Probability { value: 1, chance: 0.5 },
+    ///     ]);
+    /// ```
+    pub fn abs_diff(&self, probability_distribution: &impl ProbabilityDistribution<i32>) -> Die {
+        self.combine_with(probability_distribution, |value, other_value| {
+            i32::try_from(value.abs_diff(other_value)).unwrap_or(i32::MAX)
+        })
+    }
+
+    /// Compares this die with an independent one, resulting in `-1` if this die rolls lower, `0`
+    /// on a tie and `1` if this die rolls higher.
+    ///
+    /// The result can be branched on via
+    /// [`conditional_chain`][`ProbabilityDistribution::conditional_chain`] to model contests
+    /// with explicit tie handling.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let contest = Die::new(2).compare(&Die::new(2));
+    /// assert_eq!(
+    ///     contest.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: -1, chance: 0.25 },
+    ///         Probability { value: 0, chance: 0.5 },
+    ///         Probability { value: 1, chance: 0.25 },
+    ///     ]);
+    /// ```
+    pub fn compare(&self, probability_distribution: &impl ProbabilityDistribution<i32>) -> Die {
+        self.combine_with(probability_distribution, |value, other_value| {
+            value.cmp(&other_value) as i32
+        })
+    }
+
+    /// Same as [`Die::sum_all`], but switches to a normal approximation if the sum could have
+    /// more than `max_support` different outcomes.
+    ///
+    /// The approximation uses the exact mean and variance of the sum, which are cheap to
+    /// calculate, and spreads the chances over the integers via the normal distribution. The
+    /// result is marked as [approximate][`Die::is_approximate`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let thousand_d6 = Die::sum_all_approximate(&vec![Die::new(6); 1000], 1000);
+    /// assert!(thousand_d6.is_approximate());
+    /// assert!((thousand_d6.get_mean() - 3500.0).abs() < 1e-6);
+    /// ```
+    pub fn sum_all_approximate(dice: &[Die], max_support: usize) -> Die {
+        let (min, max, mean, variance) =
+            dice.iter()
+                .fold((0i64, 0i64, 0.0, 0.0), |(min, max, mean, variance), die| {
+                    (
+                        min + i64::from(die.get_min()),
+                        max + i64::from(die.get_max()),
+                        mean + die.get_mean(),
+                        variance + die.get_variance(),
+                    )
+                });
+        let outcomes = max - min + 1;
+        if outcomes <= i64::try_from(max_support).unwrap_or(i64::MAX) {
+            Die::sum_all(dice)
+        } else {
+            dice.iter().fold(
+                Die::discretized_normal(mean, variance.sqrt(), min, max),
+                |sum, die| sum.with_approximation_of(die, 1),
+            )
+        }
+    }
+
+    /// Same as [`Die::repeat`], but switches to a normal approximation if the sum could have more
+    /// than `max_support` different outcomes, like [`Die::sum_all_approximate`].
+    pub fn repeat_approximate(&self, n: usize, max_support: usize) -> Die {
+        let times = i64::try_from(n).unwrap_or(i64::MAX);
+        let outcomes = (i64::from(self.get_max()) - i64::from(self.get_min()))
+            .saturating_mul(times)
+            .saturating_add(1);
+        if outcomes <= i64::try_from(max_support).unwrap_or(i64::MAX) {
+            self.repeat(n)
+        } else {
+            Die::discretized_normal(
+                self.get_mean() * n as f64,
+                (self.get_variance() * n as f64).sqrt(),
+                i64::from(self.get_min()).saturating_mul(times),
+                i64::from(self.get_max()).saturating_mul(times),
+            )
+            .with_approximation_of(self, n)
+        }
+    }
+
+    /// Removes every outcome with a chance below `threshold` and scales up the remaining chances
+    /// to make up for the removed ones.
+    ///
+    /// If anything was removed, the result is marked as [approximate][`Die::is_approximate`].
+    /// The removed chance can be checked beforehand via [`Die::pruned_mass`]. If every outcome
+    /// falls below `threshold`, only the most likely one is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let long_tail = Die::geometric(0.9, 10);
+    /// assert!(long_tail.pruned_mass(1e-4) < 1e-4);
+    /// let pruned = long_tail.prune(1e-4);
+    /// assert_eq!(pruned.get_max(), 4);
+    /// assert!(pruned.is_approximate());
+    /// ```
+    pub fn prune(&self, threshold: f64) -> Die {
+        let kept: Vec<Probability<i32>> = self
+            .probabilities
+            .iter()
+            .filter(|prob| prob.chance >= threshold)
+            .copied()
+            .collect();
+        if kept.len() == self.probabilities.len() {
+            return self.clone();
+        }
+        let kept = if kept.is_empty() {
+            self.probabilities
+                .iter()
+                .max_by(|a, b| a.chance.total_cmp(&b.chance))
+                .map(|prob| vec![*prob])
+                .unwrap_or_default()
+        } else {
+            kept
+        };
+        let total = kept.iter().fold(0.0, |acc, prob| acc + prob.chance);
+        let mut die =
+            Die::from_probabilities(kept.into_iter().map(|prob| prob * (1.0 / total)).collect())
+                .with_label_of(self)
+                .with_approximation_of(self, 1);
+        die.approximate = true;
+        die
+    }
+
+    /// Merges every outcome with a chance below `epsilon` into the next lower remaining one,
+    /// unlike [`Die::prune`], which scales up the remaining chances instead. The total chance
+    /// stays the same.
+    ///
+    /// If anything was merged, the result is marked as [approximate][`Die::is_approximate`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let long_tail = Die::geometric(0.9, 10);
+    /// let compressed = long_tail.compress(1e-4);
+    /// assert_eq!(compressed.get_max(), 4);
+    /// assert!((compressed.get_chance(4) - long_tail.get_survival(3)).abs() < 1e-12);
+    /// assert!(compressed.is_approximate());
+    /// ```
+    pub fn compress(&self, epsilon: f64) -> Die {
+        let compressed = compress_additive_with_epsilon(&self.probabilities, epsilon);
+        if compressed.len() == self.probabilities.len() {
+            return self.clone();
+        }
+        let mut die = Die::from_probabilities(compressed)
+            .with_label_of(self)
+            .with_approximation_of(self, 1);
+        die.approximate = true;
+        die
+    }
+
+    /// Explodes this die on the given condition without limiting how often it explodes in a row,
+    /// like "d6 explodes on 6 forever".
+    ///
+    /// The result is the sum of the geometric series over the chance to explode, which gets cut
+    /// off once the chance to explode yet another time drops below `tolerance`. If anything was
+    /// cut off, the remaining chances get scaled up to make up for it and the cut off chance is
+    /// kept as [truncated mass][`Die::truncated_mass`].
+    ///
+    /// # Panics
+    /// Panics if `tolerance` is not positive or if the die explodes on every value, since it
+    /// would never stop exploding.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, ExplodingCondition, NormalInitializer, ProbabilityDistribution };
+    /// let exploding = Die::new(6).explode_unbounded(ExplodingCondition::Equal, 6, 1e-12);
+    /// assert!((exploding.get_mean() - 4.2).abs() < 1e-9);
+    /// assert!((exploding.get_survival(17) - 1.0 / 216.0).abs() < 1e-9);
+    /// assert!(exploding.truncated_mass() < 1e-12);
+    /// ```
+    pub fn explode_unbounded(
+        &self,
+        exploding_condition: ExplodingCondition,
+        exploding_range: i32,
+        tolerance: f64,
+    ) -> Die {
+        self.explode_unbounded_with_stats(exploding_condition, exploding_range, tolerance)
+            .0
+    }
+
+    /// Same as [`Die::explode_unbounded`], but also returns how long the chains of explosions
+    /// get.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, ExplodingCondition, NormalInitializer, ProbabilityDistribution };
+    /// let (_, stats) =
+    ///     Die::new(6).explode_unbounded_with_stats(ExplodingCondition::Equal, 6, 1e-12);
+    /// assert!((stats.expected_explosions - 0.2).abs() < 1e-9);
+    /// assert!((stats.chain_length.get_survival(1) - 1.0 / 36.0).abs() < 1e-9);
+    /// ```
+    pub fn explode_unbounded_with_stats(
+        &self,
+        exploding_condition: ExplodingCondition,
+        exploding_range: i32,
+        tolerance: f64,
+    ) -> (Die, ExplosionStats) {
+        assert!(tolerance > 0.0, "tolerance has to be positive");
+        let (exploding, stopping): (Vec<Probability<i32>>, Vec<Probability<i32>>) = self
+            .probabilities
+            .iter()
+            .partition(|prob| exploding_condition.matches(prob.value, exploding_range));
+        if exploding.is_empty() {
+            return (self.clone(), ExplosionStats::new(Die::empty()));
+        }
+        assert!(!stopping.is_empty(), "die explodes on every value");
+        let stopping_chance = stopping.iter().fold(0.0, |acc, prob| acc + prob.chance);
+
+        // sum of the exploded values so far -> chance to explode exactly that way
+        let mut exploded: HashMap<i32, f64> = HashMap::from([(0, 1.0)]);
+        let mut accumulated = HashMap::new();
+        let mut chain_lengths = HashMap::new();
+        let truncated = loop {
+            let mut next = HashMap::with_capacity(exploded.len() + exploding.len());
+            for (&sum, &chance) in &exploded {
+                for prob in &stopping {
+                    *accumulated
+                        .entry(sum.saturating_add(prob.value))
+                        .or_insert(0.0) += chance * prob.chance;
+                }
+                for prob in &exploding {
+                    *next.entry(sum.saturating_add(prob.value)).or_insert(0.0) +=
+                        chance * prob.chance;
+                }
+            }
+            chain_lengths.insert(
+                chain_lengths.len() as i32,
+                exploded.values().sum::<f64>() * stopping_chance,
+            );
+            let remaining = next.values().sum::<f64>();
+            if remaining < tolerance {
+                break remaining;
+            }
+            exploded = next;
+        };
+        let mut die = Die::from_accumulated(accumulated);
+        let mut chain_length = Die::from_accumulated(chain_lengths);
+        if truncated > 0.0 {
+            for prob in die
+                .probabilities
+                .iter_mut()
+                .chain(chain_length.probabilities.iter_mut())
+            {
+                prob.chance /= 1.0 - truncated;
+            }
+        }
+        (
+            die.with_truncated_mass(truncated)
+                .with_approximation_of(self, 1)
+                .with_label_of(self),
+            ExplosionStats::new(chain_length.with_truncated_mass(truncated)),
+        )
+    }
+
+    /// Returns the total chance of every outcome below `threshold`, meaning the chance
+    /// [`Die::prune`] would remove.
+    pub fn pruned_mass(&self, threshold: f64) -> f64 {
+        self.probabilities
+            .iter()
+            .filter(|prob| prob.chance < threshold)
+            .fold(0.0, |acc, prob| acc + prob.chance)
+    }
+
+    /// Whether this die was created by an approximation instead of exact calculations.
+    ///
+    /// Dice calculated from an approximate one, like its sum with another die, are approximate
+    /// as well.
+    pub fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+
+    /// Returns the chance cut off by the tolerance of a recursive mechanic, like
+    /// [`Die::explode_unbounded`], before the remaining chances got scaled up. Exact results
+    /// have a truncated mass of `0.0`.
+    ///
+    /// Arithmetic on the resulting die keeps the truncated mass, combining the ones of
+    /// independent dice into the chance of any of them being cut off.
+    pub fn truncated_mass(&self) -> f64 {
+        self.truncated_mass
+    }
+
+    /// Attaches a name to this die, like "Greatsword damage", replacing any previous one.
+    ///
+    /// The name and [metadata][`Die::with_metadata`] are shown by
+    /// [`get_details`][`ProbabilityDistribution::get_details`] and reports comparing several
+    /// dice. They are kept by transformations of a single die, like
+    /// [`add_flat`][`ProbabilityDistribution::add_flat`] or [`Die::prune`], but not when
+    /// combining dice.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let greatsword = Die::new(6)
+    ///     .repeat(2)
+    ///     .with_name("Greatsword damage")
+    ///     .with_metadata("source", "PHB");
+    /// let enchanted = greatsword.add_flat(1);
+    /// assert_eq!(enchanted.name(), Some("Greatsword damage"));
+    /// assert_eq!(enchanted.metadata("source"), Some("PHB"));
+    /// assert!(enchanted.get_details().starts_with("Name"));
+    /// assert_eq!((&enchanted + &Die::new(4)).name(), None);
+    /// ```
+    pub fn with_name(mut self, name: &str) -> Die {
+        self.label.get_or_insert_with(Box::default).name = Some(name.to_string());
+        self
+    }
+
+    /// Attaches the given metadata entry to this die, replacing any previous value of the key.
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Die {
+        self.label
+            .get_or_insert_with(Box::default)
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Returns the name of this die, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.label.as_ref()?.name.as_deref()
+    }
+
+    /// Returns the value of the given metadata key, if any.
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.label.as_ref()?.metadata.get(key).map(String::as_str)
+    }
+
+    /// Returns every metadata entry of this die, ordered by key.
+    pub fn metadata_entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.label
+            .iter()
+            .flat_map(|label| label.metadata.iter())
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Records how this die was built, like `4d6 drop lowest 1 + 2`, replacing any previous
+    /// description.
+    ///
+    /// Dice created via [`new`][`NormalInitializer::new`] describe themselves like `d6`, and
+    /// flat modifiers, [`Die::repeat`], [`Die::rem_flat`], adding two described dice via `+` and
+    /// the [`PoolBuilder`][`crate::PoolBuilder`] extend the description of their input. Every
+    /// other way of creating a die leaves it undescribed, unless it always rolls the same value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, DropType, NormalInitializer, PoolBuilder };
+    /// let stats = PoolBuilder::new().add(4, Die::new(6)).plus(2).drop(1, DropType::Low);
+    /// assert_eq!(stats.describe().as_deref(), Some("4d6 drop lowest 1 + 2"));
+    /// assert_eq!((&Die::new(8) + &Die::new(4)).describe().as_deref(), Some("d8 + d4"));
+    /// let custom = Die::from_values(&[1, 1, 2]).with_description("loaded coin");
+    /// assert_eq!(custom.repeat(2).describe().as_deref(), Some("2x(loaded coin)"));
+    /// ```
+    pub fn with_description(mut self, description: &str) -> Die {
+        self.label.get_or_insert_with(Box::default).description = Some(description.to_string());
+        self
+    }
+
+    /// Returns how this die was built, if recorded, see [`Die::with_description`].
+    ///
+    /// Dice always rolling the same value describe themselves by that value, like `3`.
+    pub fn describe(&self) -> Option<Cow<'_, str>> {
+        match self
+            .label
+            .as_ref()
+            .and_then(|label| label.description.as_deref())
+        {
+            Some(description) => Some(Cow::Borrowed(description)),
+            // described only on request, so creating constants stays free of allocations
+            None => match self.probabilities.as_slice() {
+                [constant] => Some(Cow::Owned(constant.value.to_string())),
+                _ => None,
+            },
+        }
+    }
+
+    /// Replaces the description with the given one, removing it if `None`.
+    pub(crate) fn with_described(self, description: Option<String>) -> Die {
+        match description {
+            Some(description) => self.with_description(&description),
+            None => match self.label {
+                Some(mut label) => {
+                    label.description = None;
+                    Die {
+                        label: Some(label),
+                        ..self
+                    }
+                }
+                None => self,
+            },
+        }
+    }
+
+    /// Copies the name and metadata of `other` onto this die.
+    fn with_label_of(mut self, other: &Die) -> Die {
+        self.label = other.label.as_ref().map(|label| {
+            Box::new(Label {
+                name: label.name.clone(),
+                metadata: label.metadata.clone(),
+                description: None,
+            })
+        });
+        self
+    }
+
+    /// Returns the statistics of this die, calculating them on first access.
+    fn stats(&self) -> &Stats {
+        self.stats.get_or_init(|| {
+            let mean = calc_mean(&self.probabilities);
+            // squared in f64, since squaring large values overflows i32
+            let mean_of_squares = self.probabilities.iter().fold(0.0, |acc, prob| {
+                acc + prob.chance * (f64::from(prob.value) * f64::from(prob.value))
+            });
+            Stats {
+                min: self.probabilities.iter().min().map(|prob| prob.value),
+                max: self.probabilities.iter().max().map(|prob| prob.value),
+                mean,
+                variance: mean_of_squares - mean * mean,
+            }
+        })
+    }
+
+    /// Sets the statistics of this die, if they weren't calculated yet.
+    fn with_stats(self, stats: Stats) -> Die {
+        let _ = self.stats.set(stats);
+        self
+    }
+
+    /// Records the chance cut off while creating this die, marking it as approximate if anything
+    /// was cut off.
+    pub(crate) fn with_truncated_mass(mut self, truncated_mass: f64) -> Die {
+        if truncated_mass > 0.0 {
+            self.approximate = true;
+        }
+        self.truncated_mass = truncated_mass;
+        self
+    }
+
+    /// Carries the approximation of `other` over to this die, as calculated from `times`
+    /// independent rolls of it.
+    fn with_approximation_of(mut self, other: &Die, times: usize) -> Die {
+        if times > 0 && other.approximate {
+            self.approximate = true;
+            self.truncated_mass =
+                1.0 - (1.0 - self.truncated_mass) * (1.0 - other.truncated_mass).powf(times as f64);
+        }
+        self
+    }
+
+    /// Creates a die from an already sorted and compressed polynomial, skipping the checks of
+    /// [`from_probabilities`][`NormalInitializer::from_probabilities`].
+    fn from_polynomial(polynomial: SparsePolynomial) -> Die {
+        let probabilities = polynomial.into_probabilities();
+        if probabilities.is_empty() {
+            return Die::empty();
+        }
+        Die {
+            probabilities: probabilities.into_iter().collect(),
+            approximate: false,
+            truncated_mass: 0.0,
+            label: None,
+            stats: OnceLock::new(),
+        }
+    }
+
+    /// Returns the first value and the amount of faces if this die rolls consecutive values with
+    /// equal chance, like [`Die::new`].
+    fn uniform_faces(&self) -> Option<(i32, u32)> {
+        let first = self.probabilities.first()?;
+        let faces = u32::try_from(self.probabilities.len()).ok()?;
+        let consecutive = self
+            .probabilities
+            .windows(2)
+            .all(|pair| pair[0].value.checked_add(1) == Some(pair[1].value));
+        let equal = self
+            .probabilities
+            .iter()
+            .all(|prob| (prob.chance - first.chance).abs() < 1e-12);
+        (consecutive && equal).then_some((first.value, faces))
+    }
+
+    /// Creates a die by applying `mapping_fn` to every value, merging the values that end up the
+    /// same.
+    fn map_values<F>(&self, mapping_fn: F) -> Die
+    where
+        F: Fn(i32) -> i32,
+    {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for prob in &self.probabilities {
+            *accumulated.entry(mapping_fn(prob.value)).or_insert(0.0) += prob.chance;
+        }
+        Die::from_accumulated(accumulated)
+            .with_label_of(self)
+            .with_approximation_of(self, 1)
+    }
+
+    /// Creates a die from chances accumulated per value, skipping the checks of
+    /// [`from_probabilities`][`NormalInitializer::from_probabilities`].
+    fn from_accumulated(accumulated: HashMap<i32, f64>) -> Die {
+        if accumulated.is_empty() {
+            return Die::empty();
+        }
+        let mut probabilities: SmallVec<[Probability<i32>; INLINE_PROBABILITIES]> = accumulated
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
+            .collect();
+        probabilities.sort_unstable();
+        Die {
+            probabilities,
+            approximate: false,
+            truncated_mass: 0.0,
+            label: None,
+            stats: OnceLock::new(),
+        }
+    }
+
+    /// Normalizes integer weights, summing them up exactly before converting to chances.
+    fn from_integer_weights<I>(weights: I) -> Die
+    where
+        I: IntoIterator<Item = (i32, u64)>,
+    {
+        let mut merged = BTreeMap::new();
+        for (value, weight) in weights {
+            if weight > 0 {
+                *merged.entry(value).or_insert(0u128) += u128::from(weight);
+            }
+        }
+        let total: u128 = merged.values().sum();
+        if total == 0 {
+            return Die::empty();
+        }
+        Die {
+            probabilities: merged
+                .into_iter()
+                .map(|(value, weight)| Probability {
+                    value,
+                    chance: weight as f64 / total as f64,
+                })
+                .collect(),
+            approximate: false,
+            truncated_mass: 0.0,
+            label: None,
+            stats: OnceLock::new(),
+        }
+    }
+
+    fn discretized_normal(mean: f64, standard_deviation: f64, min: i64, max: i64) -> Die {
+        // everything further away than this has a chance far below the allowed error
+        const REACH: f64 = 8.0;
+        let lowest = min.max((mean - REACH * standard_deviation).floor() as i64);
+        let highest = max.min((mean + REACH * standard_deviation).ceil() as i64);
+        let weights: Vec<(i32, f64)> = (lowest..=highest)
+            .map(|value| {
+                let x = value as f64;
+                let weight = normal_cdf((x + 0.5 - mean) / standard_deviation)
+                    - normal_cdf((x - 0.5 - mean) / standard_deviation);
+                // values beyond the range of `i32` saturate, like the plain arithmetic does
+                let value = value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+                (value, weight)
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        let total = weights.iter().fold(0.0, |acc, (_, weight)| acc + weight);
+        let mut die = Die::from_probabilities(
+            weights
+                .into_iter()
+                .map(|(value, weight)| Probability {
+                    value,
+                    chance: weight / total,
+                })
+                .collect(),
+        );
+        die.approximate = true;
+        die
+    }
+
+    /// Rolls this die `n` times and keeps the highest result, like advantage (`n = 2`) or elven
+    /// accuracy (`n = 3`).
+    ///
+    /// Uses the cumulative distribution `F(x)^n` instead of enumerating every combination, so
+    /// large `n` stays cheap. When given `0`, creates an [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let advantage = Die::new(2).best_of_n(2);
+    /// assert_eq!(
+    ///     advantage.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 1, chance: 0.25 },
+    ///         Probability { value: 2, chance: 0.75 },
+    ///     ]);
+    /// ```
+    pub fn best_of_n(&self, n: usize) -> Die {
+        if n == 0 {
+            return Die::empty();
+        }
+        let mut cumulative = 0.0;
+        let mut previous = 0.0;
+        Die::from_probabilities(
+            self.get_probabilities()
+                .iter()
+                .map(|prob| {
+                    cumulative += prob.chance;
+                    let current = cumulative.powi(n as i32);
+                    let chance = current - previous;
+                    previous = current;
+                    Probability {
+                        value: prob.value,
+                        chance,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Rolls this die `n` times and keeps the lowest result, like disadvantage (`n = 2`).
+    ///
+    /// Uses the survival function `S(x)^n` instead of enumerating every combination, so large
+    /// `n` stays cheap. When given `0`, creates an [empty die][`Die::empty()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let disadvantage = Die::new(2).worst_of_n(2);
+    /// assert_eq!(
+    ///     disadvantage.get_probabilities(),
+    ///     &vec![
+    ///         Probability { value: 1, chance: 0.75 },
+    ///         Probability { value: 2, chance: 0.25 },
+    ///     ]);
+    /// ```
+    pub fn worst_of_n(&self, n: usize) -> Die {
+        if n == 0 {
+            return Die::empty();
+        }
+        let mut survival = 0.0;
+        let mut previous = 0.0;
+        let mut probabilities: Vec<Probability<i32>> = self
+            .get_probabilities()
+            .iter()
+            .rev()
+            .map(|prob| {
+                survival += prob.chance;
+                let current = survival.powi(n as i32);
+                let chance = current - previous;
+                previous = current;
+                Probability {
+                    value: prob.value,
+                    chance,
+                }
+            })
+            .collect();
+        probabilities.reverse();
+        Die::from_probabilities(probabilities)
+    }
+}
+
+/// Appends a flat modifier to a description, like `d6 + 2`.
+fn describe_flat(description: &str, flat_increase: i32) -> String {
+    match flat_increase.cmp(&0) {
+        Ordering::Less => format!("{} - {}", description, flat_increase.unsigned_abs()),
+        Ordering::Equal => description.to_string(),
+        Ordering::Greater => format!("{} + {}", description, flat_increase),
+    }
+}
+
+/// Describes `n` copies of a described die, like `3d6` or `3x(d6 + 1)`.
+pub(crate) fn describe_repeat(description: &str, n: usize) -> String {
+    let is_plain_die = description
+        .strip_prefix('d')
+        .is_some_and(|sides| !sides.is_empty() && sides.chars().all(|c| c.is_ascii_digit()));
+    if is_plain_die {
+        format!("{}{}", n, description)
+    } else {
+        format!("{}x({})", n, description)
+    }
+}
+
+/// Wraps a description in parentheses if it consists of several terms.
+pub(crate) fn describe_grouped(description: &str) -> String {
+    if description.contains(' ') {
+        format!("({})", description)
+    } else {
+        description.to_string()
+    }
 }
 
 impl NormalInitializer<i32, Die> for Die {
+    /// Same as the default, but [describes][`Die::describe`] the die like `d6`.
+    fn new(size: i32) -> Die {
+        new_uniform::<i32, Die>(size).with_description(&format!("d{}", size))
+    }
+
     /// Creates a new die with the given [probabilities][`Probability<i32>`].
     ///
     /// When given `0`, creates an [empty die][`Die::empty()`].
@@ -86,16 +1788,55 @@ impl NormalInitializer<i32, Die> for Die {
             return Die::empty();
         }
         Die {
-            probabilities: compress_additive(&probabilities),
+            probabilities: compress_additive(&probabilities).into_iter().collect(),
+            approximate: false,
+            truncated_mass: 0.0,
+            label: None,
+            stats: OnceLock::new(),
         }
     }
 }
 
 impl ProbabilityDistribution<i32> for Die {
-    fn get_probabilities(&self) -> &Vec<Probability<i32>> {
+    fn get_probabilities(&self) -> &[Probability<i32>] {
         &self.probabilities
     }
 
+    fn get_max(&self) -> i32 {
+        self.stats().max.unwrap()
+    }
+
+    fn get_mean(&self) -> f64 {
+        self.stats().mean
+    }
+
+    fn get_min(&self) -> i32 {
+        self.stats().min.unwrap()
+    }
+
+    fn get_standard_deviation(&self) -> f64 {
+        self.get_variance().sqrt()
+    }
+
+    fn get_variance(&self) -> f64 {
+        self.stats().variance
+    }
+
+    /// Same as the default, but starts with the name and metadata of this die, if any.
+    fn get_details_with(&self, options: &FormatOptions) -> String {
+        let mut rows: Vec<(String, String)> = self
+            .name()
+            .map(|name| ("Name".to_string(), name.to_string()))
+            .into_iter()
+            .chain(
+                self.metadata_entries()
+                    .map(|(key, value)| (key.to_string(), value.to_string())),
+            )
+            .collect();
+        rows.extend(detail_rows(self, options));
+        format_details(&rows, options)
+    }
+
     /// Add an independent die to this one.
     ///
     /// Creates and returns a new die as a result.
@@ -110,17 +1851,8 @@ impl ProbabilityDistribution<i32> for Die {
     /// );
     /// ```
     fn add_independent(&self, probability_distribution: &impl ProbabilityDistribution<i32>) -> Die {
-        Die::from_probabilities(
-            probability_distribution
-                .get_probabilities()
-                .iter()
-                .flat_map(|outer_prob| {
-                    self.get_probabilities()
-                        .iter()
-                        .map(|inner_prob| *outer_prob + *inner_prob)
-                })
-                .collect(),
-        )
+        self.try_add_independent(probability_distribution, OverflowPolicy::Saturate)
+            .expect("saturating never overflows")
     }
 
     /// Add a dependent die to this one.
@@ -150,23 +1882,13 @@ impl ProbabilityDistribution<i32> for Die {
     ///     ]
     /// );
     /// ```
-    fn add_dependent<F>(&self, callback_fn: &F) -> Die
+    fn add_dependent<F, D>(&self, callback_fn: &F) -> Die
     where
-        F: Fn(&i32) -> Die,
+        F: Fn(&i32) -> D,
+        D: ProbabilityDistribution<i32>,
     {
-        Die::from_probabilities(
-            self.get_probabilities()
-                .iter()
-                .flat_map(|outer_prob| {
-                    callback_fn(&outer_prob.value)
-                        .get_probabilities()
-                        .iter()
-                        .map(|inner_prob| *outer_prob + *inner_prob)
-                        // dislike the collect here...
-                        .collect::<Vec<Probability<i32>>>()
-                })
-                .collect(),
-        )
+        self.try_add_dependent(callback_fn, OverflowPolicy::Saturate)
+            .expect("saturating never overflows")
     }
 
     /// Add an independent die to this one.
@@ -174,7 +1896,7 @@ impl ProbabilityDistribution<i32> for Die {
     /// # Examples
     /// ```
     /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
-    /// let hit_or_miss = Die::new(20).conditional_chain(&|&val| {
+    /// let hit_or_miss = Die::new(20).conditional_chain(&mut |&val| {
     ///     if val >= 16 {
     ///         Die::new(1)
     ///     } else {
@@ -188,22 +1910,19 @@ impl ProbabilityDistribution<i32> for Die {
     ///         Probability { value: 1, chance: 0.25 },
     ///     ]);
     /// ```
-    fn conditional_chain<F>(&self, callback_fn: &mut F) -> Die
+    fn conditional_chain<F, D>(&self, callback_fn: &mut F) -> Die
     where
-        F: FnMut(&i32) -> Die,
+        F: FnMut(&i32) -> D,
+        D: ProbabilityDistribution<i32>,
     {
-        Die::from_probabilities(
-            self.get_probabilities()
-                .iter()
-                .flat_map(|outer_prob| {
-                    callback_fn(&outer_prob.value)
-                        .get_probabilities()
-                        .iter()
-                        .map(|inner_prob| *inner_prob * outer_prob.chance)
-                        .collect::<Vec<Probability<i32>>>()
-                })
-                .collect::<Vec<Probability<i32>>>(),
-        )
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for outer_prob in &self.probabilities {
+            for inner_prob in callback_fn(&outer_prob.value).get_probabilities() {
+                *accumulated.entry(inner_prob.value).or_insert(0.0) +=
+                    inner_prob.chance * outer_prob.chance;
+            }
+        }
+        Die::from_accumulated(accumulated).with_approximation_of(self, 1)
     }
 
     /// Adds a flat amount to a die.
@@ -223,15 +1942,8 @@ impl ProbabilityDistribution<i32> for Die {
     /// );
     /// ```
     fn add_flat(&self, flat_increase: i32) -> Die {
-        Die::from_probabilities(
-            self.get_probabilities()
-                .iter()
-                .map(|prob| Probability {
-                    value: prob.value + flat_increase,
-                    chance: prob.chance,
-                })
-                .collect(),
-        )
+        self.try_add_flat(flat_increase, OverflowPolicy::Saturate)
+            .expect("saturating never overflows")
     }
 }
 
@@ -246,73 +1958,269 @@ impl<'a> Add<&'a Die> for &'a Die {
 
     fn add(self, rhs: &'a Die) -> Self::Output {
         self.add_independent(rhs)
+            .with_approximation_of(rhs, 1)
+            .with_described(
+                self.describe()
+                    .zip(rhs.describe())
+                    .map(|(left, right)| format!("{} + {}", left, right)),
+            )
+    }
+}
+
+impl Neg for &Die {
+    type Output = Die;
+
+    fn neg(self) -> Self::Output {
+        self.scale(-1)
+    }
+}
+
+impl Neg for Die {
+    type Output = Die;
+
+    fn neg(self) -> Self::Output {
+        self.scale(-1)
+    }
+}
+
+impl Add<Die> for Die {
+    type Output = Die;
+
+    fn add(self, rhs: Die) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Add<i32> for Die {
+    type Output = Die;
+
+    fn add(self, rhs: i32) -> Self::Output {
+        self.add_flat(rhs)
+    }
+}
+
+impl Add<i32> for &Die {
+    type Output = Die;
+
+    fn add(self, rhs: i32) -> Self::Output {
+        self.add_flat(rhs)
+    }
+}
+
+impl Rem<i32> for Die {
+    type Output = Die;
+
+    fn rem(self, rhs: i32) -> Self::Output {
+        self.rem_flat(rhs)
+    }
+}
+
+impl Rem<i32> for &Die {
+    type Output = Die;
+
+    fn rem(self, rhs: i32) -> Self::Output {
+        self.rem_flat(rhs)
+    }
+}
+
+impl<'a, F> Add<&'a F> for &'a Die
+where
+    F: Fn(&i32) -> Die,
+{
+    type Output = Die;
+
+    fn add(self, rhs: &'a F) -> Self::Output {
+        self.add_dependent(rhs)
+    }
+}
+
+impl<F> Add<F> for Die
+where
+    F: Fn(&i32) -> Die,
+{
+    type Output = Die;
+
+    fn add(self, rhs: F) -> Self::Output {
+        self.add_dependent(&rhs)
+    }
+}
+
+impl PartialEq for Die {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_probabilities() == other.get_probabilities()
+    }
+}
+
+impl Eq for Die {}
+
+/// Creates a die always rolling the given value, described by the value itself, so flat
+/// numbers can be mixed with dice.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+/// let damage = Die::sum_all(&[Die::new(6), 3.into()]);
+/// assert_eq!(damage, Die::new(6).add_flat(3));
+/// assert_eq!(damage.describe().as_deref(), Some("3 + d6"));
+/// ```
+impl From<i32> for Die {
+    fn from(value: i32) -> Self {
+        Die::from_values(&[value])
+    }
+}
+
+/// Creates a die rolling every value of the range with equal chance, like
+/// [`from_range`][`NormalInitializer::from_range`]. An empty range creates an
+/// [empty die][`Die::empty()`].
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer };
+/// assert_eq!(Die::from(1..=6), Die::new(6));
+/// assert_eq!(Die::from(6..=1), Die::empty());
+/// ```
+impl From<RangeInclusive<i32>> for Die {
+    fn from(range: RangeInclusive<i32>) -> Self {
+        if range.is_empty() {
+            return Die::empty();
+        }
+        Die::from_range(*range.start(), *range.end())
+    }
+}
+
+/// Creates a die rolling every value of the range, excluding its end, with equal chance. An
+/// empty range creates an [empty die][`Die::empty()`].
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+/// assert_eq!(Die::from(0..4), Die::new(4).add_flat(-1));
+/// ```
+impl From<Range<i32>> for Die {
+    fn from(range: Range<i32>) -> Self {
+        if range.is_empty() {
+            return Die::empty();
+        }
+        Die::from_range(range.start, range.end - 1)
     }
 }
 
-impl Add<Die> for Die {
-    type Output = Die;
-
-    fn add(self, rhs: Die) -> Self::Output {
-        self.add_independent(&rhs)
+/// Collects values into a die like [`from_values`][`NormalInitializer::from_values`], every
+/// occurrence of a value adding to its chance.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer };
+/// let d6: Die = (1..=6).collect();
+/// assert_eq!(d6, Die::new(6));
+/// let evens: Die = (1..=6).filter(|value| value % 2 == 0).collect();
+/// assert_eq!(evens, Die::from_values(&[2, 4, 6]));
+/// ```
+impl FromIterator<i32> for Die {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        Die::from_values(&iter.into_iter().collect::<Vec<_>>())
     }
 }
 
-impl Add<i32> for Die {
-    type Output = Die;
-
-    fn add(self, rhs: i32) -> Self::Output {
-        self.add_flat(rhs)
-    }
+/// Result of [`Die::margin_over`], telling failures and the margin of successes apart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Margin {
+    /// Chance of falling short of the target.
+    pub failure: f64,
+    /// Distribution of how far the target was exceeded, given it was reached at all. `None` if
+    /// the target can't be reached.
+    pub margin: Option<Die>,
 }
 
-impl<'a> Add<i32> for &'a Die {
-    type Output = Die;
+/// Error returned when parsing a [`Die`] from its
+/// [canonical representation][`Die::to_canonical_string`] or a [table][`Die::from_table`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDieError {
+    /// An entry is not of the form `value:chance`, or a table row has more than two columns.
+    InvalidEntry(String),
+    /// A value could not be parsed.
+    InvalidValue(String),
+    /// A chance could not be parsed or is negative.
+    InvalidChance(String),
+    /// The chances do not add up to `1.0`.
+    InvalidSum,
+}
 
-    fn add(self, rhs: i32) -> Self::Output {
-        self.add_flat(rhs)
+impl std::fmt::Display for ParseDieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseDieError::InvalidEntry(entry) => write!(f, "invalid entry `{entry}`"),
+            ParseDieError::InvalidValue(value) => write!(f, "invalid value `{value}`"),
+            ParseDieError::InvalidChance(chance) => write!(f, "invalid chance `{chance}`"),
+            ParseDieError::InvalidSum => write!(f, "chances do not add up to 1"),
+        }
     }
 }
 
-impl<'a, F> Add<&'a F> for &'a Die
-where
-    F: Fn(&i32) -> Die,
-{
-    type Output = Die;
+impl std::error::Error for ParseDieError {}
 
-    fn add(self, rhs: &'a F) -> Self::Output {
-        self.add_dependent(rhs)
+impl FromStr for Die {
+    type Err = ParseDieError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let probabilities = s
+            .split(',')
+            .map(|entry| {
+                let entry = entry.trim();
+                let (value, chance) = entry
+                    .split_once(':')
+                    .ok_or_else(|| ParseDieError::InvalidEntry(entry.to_string()))?;
+                let value = value.trim();
+                let chance = chance.trim();
+                Ok(Probability {
+                    value: value
+                        .parse()
+                        .map_err(|_| ParseDieError::InvalidValue(value.to_string()))?,
+                    chance: chance
+                        .parse()
+                        .ok()
+                        .filter(|chance: &f64| *chance >= 0.0)
+                        .ok_or_else(|| ParseDieError::InvalidChance(chance.to_string()))?,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let sum = probabilities
+            .iter()
+            .fold(0.0, |acc, curr| acc + curr.chance);
+        if sum >= 1.0 + ALLOWED_ERROR || sum <= 1.0 - ALLOWED_ERROR {
+            return Err(ParseDieError::InvalidSum);
+        }
+        Ok(Die::from_probabilities(probabilities))
     }
 }
 
-impl<F> Add<F> for Die
-where
-    F: Fn(&i32) -> Die,
-{
-    type Output = Die;
+/// Orders dice by the amount of outcomes, used to pick the cheapest pair in [`Die::sum_all`].
+struct BySupport(Die);
 
-    fn add(self, rhs: F) -> Self::Output {
-        self.add_dependent(&rhs)
+impl PartialEq for BySupport {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl PartialEq for Die {
-    fn eq(&self, other: &Self) -> bool {
-        self.get_probabilities() == other.get_probabilities()
+impl Eq for BySupport {}
+
+impl PartialOrd for BySupport {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Eq for Die {}
-
-impl From<i32> for Die {
-    fn from(value: i32) -> Self {
-        Die::from_values(&[value])
+impl Ord for BySupport {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.probabilities.len().cmp(&other.0.probabilities.len())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::NormalInitializer;
+    use crate::{ExplodingCondition, ExplodingInitializer, NormalInitializer};
 
     #[test]
     fn initializers() {
@@ -331,8 +2239,15 @@ mod tests {
         assert_eq!(expected_die.get_probabilities(), &expected_probabilities);
         // other initializers
         assert_eq!(Die::new(2), expected_die);
-        assert_eq!(Die::from_values(&vec![1, 2]), expected_die);
+        assert_eq!(Die::from_values(&[1, 2]), expected_die);
         assert_eq!(Die::from_range(1, 2), expected_die);
+        assert_eq!(Die::from(1..=2), expected_die);
+        assert_eq!(Die::from(1..3), expected_die);
+        assert_eq!(Die::from(3..3), Die::empty());
+        assert_eq!([1, 2].into_iter().collect::<Die>(), expected_die);
+        let weighted: Die = [1, 1, 2].into_iter().collect();
+        assert!((weighted.get_probabilities()[0].chance - 2.0 / 3.0).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::from(i32::MAX - 1..i32::MAX), Die::from(i32::MAX - 1));
         assert_eq!(
             Die::empty(),
             Die::from_probabilities(vec![Probability {
@@ -359,18 +2274,12 @@ mod tests {
 
     #[test]
     fn min() {
-        assert_eq!(
-            (Die::new(2) + Die::from_values(&vec![3, 4, 5])).get_min(),
-            4
-        )
+        assert_eq!((Die::new(2) + Die::from_values(&[3, 4, 5])).get_min(), 4)
     }
 
     #[test]
     fn max() {
-        assert_eq!(
-            (Die::new(2) + Die::from_values(&vec![3, 4, 5])).get_max(),
-            7
-        )
+        assert_eq!((Die::new(2) + Die::from_values(&[3, 4, 5])).get_max(), 7)
     }
 
     #[test]
@@ -397,7 +2306,7 @@ mod tests {
     #[test]
     fn adding_dependent() {
         assert_eq!(
-            *(Die::new(2) + &|&prob: &_| if prob == 2 { Die::new(2) } else { Die::new(0) })
+            *(Die::new(2) + |&prob: &_| if prob == 2 { Die::new(2) } else { Die::new(0) })
                 .get_probabilities(),
             vec![
                 Probability {
@@ -419,7 +2328,7 @@ mod tests {
     #[test]
     fn chaining_dice() {
         assert_eq!(
-            *(Die::new(2).conditional_chain(&|&prob| if prob == 1 {
+            *(Die::new(2).conditional_chain(&mut |&prob| if prob == 1 {
                 Die::new(2)
             } else {
                 Die::new(3)
@@ -442,6 +2351,134 @@ mod tests {
         )
     }
 
+    #[test]
+    fn dependent_chances() {
+        let chained = Die::new(2).conditional_chain(&mut |&prob| {
+            if prob == 1 {
+                Die::new(2)
+            } else {
+                Die::new(3)
+            }
+        });
+        let expected = [5.0 / 12.0, 5.0 / 12.0, 1.0 / 6.0];
+        for (prob, chance) in chained.get_probabilities().iter().zip(expected) {
+            assert!((prob.chance - chance).abs() < ALLOWED_ERROR);
+        }
+        let added = Die::new(2).add_dependent(&|&prob| Die::new(prob));
+        let expected = [0.5, 0.25, 0.25];
+        assert_eq!(added, Die::from_values(&[2, 3, 4]));
+        for (prob, chance) in added.get_probabilities().iter().zip(expected) {
+            assert!((prob.chance - chance).abs() < ALLOWED_ERROR);
+        }
+    }
+
+    #[test]
+    fn overflowing() {
+        let huge = Die::from_values(&[1, i32::MAX]);
+        assert_eq!(huge.add_flat(1), Die::from_values(&[2, i32::MAX]));
+        assert_eq!(&huge + &huge, Die::from_values(&[2, i32::MAX]));
+        assert!(huge.try_add_flat(1, OverflowPolicy::Error).is_err());
+        assert!(huge
+            .try_add_independent(&huge, OverflowPolicy::Error)
+            .is_err());
+        assert!(huge
+            .try_add_dependent(&|_| Die::from(1), OverflowPolicy::Error)
+            .is_err());
+        assert!(huge.try_repeat(2, OverflowPolicy::Error).is_err());
+        assert_eq!(huge.try_repeat(1, OverflowPolicy::Error), Ok(huge.clone()));
+        assert_eq!(
+            Die::new_exploding(2, 2, ExplodingCondition::Equal, huge.clone()).get_max(),
+            i32::MAX
+        );
+    }
+
+    #[test]
+    fn remainder() {
+        let tens = Die::new(20).rem_flat(10);
+        assert_eq!(tens, Die::from_range(0, 9));
+        assert!((tens.get_probabilities()[0].chance - 0.1).abs() < ALLOWED_ERROR);
+        assert_eq!(&Die::from_range(-3, 3) % 3, Die::from_range(0, 2));
+        assert_eq!(Die::from_values(&[-1, 5]) % -4, Die::from_values(&[1, 3]));
+        assert_eq!(Die::from(i32::MIN).rem_flat(-1), Die::from(0));
+        assert_eq!(Die::from(i32::MIN).rem_flat(1), Die::from(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn remainder_by_zero() {
+        let _ = Die::new(6) % 0;
+    }
+
+    #[test]
+    fn absolute() {
+        let margin = Die::from_range(-2, 1).abs();
+        assert_eq!(margin, Die::from_range(0, 2));
+        assert!((margin.get_probabilities()[1].chance - 0.5).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::new(6).abs(), Die::new(6));
+        assert_eq!(Die::from(i32::MIN).abs(), Die::from(i32::MAX));
+    }
+
+    #[test]
+    fn absolute_difference() {
+        let margin = Die::new(6).abs_diff(&Die::new(6));
+        assert_eq!(margin, Die::from_range(0, 5));
+        assert!((margin.get_probabilities()[0].chance - 6.0 / 36.0).abs() < ALLOWED_ERROR);
+        assert!((margin.get_probabilities()[5].chance - 2.0 / 36.0).abs() < ALLOWED_ERROR);
+        assert!((margin.get_mean() - 70.0 / 36.0).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            Die::from(i32::MIN).abs_diff(&Die::from(i32::MAX)),
+            Die::from(i32::MAX)
+        );
+    }
+
+    #[test]
+    fn comparing() {
+        let contest = Die::new(20).compare(&Die::new(20).add_flat(2));
+        assert_eq!(contest, Die::from_range(-1, 1));
+        let chances = contest.to_map();
+        assert!((chances[&-1] - 0.5725).abs() < ALLOWED_ERROR);
+        assert!((chances[&0] - 0.045).abs() < ALLOWED_ERROR);
+        assert!((chances[&1] - 0.3825).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::new(4).compare(&Die::from(0)), Die::from(1));
+    }
+
+    #[test]
+    fn combining() {
+        let d4 = Die::new(4);
+        assert_eq!(
+            d4.combine_with(&d4, |a, b| a - b),
+            d4.add_independent(&-d4.clone())
+        );
+        let product = d4.combine_with(&Die::from_values(&[0, 2]), |a, b| a * b);
+        assert_eq!(product, Die::from_values(&[0, 2, 4, 6, 8]));
+        assert!((product.get_chance(0) - 0.5).abs() < ALLOWED_ERROR);
+        assert!((product.get_mean() - 2.5).abs() < ALLOWED_ERROR);
+        let lower = d4.combine_with(&d4, i32::min);
+        assert!((lower.get_chance(1) - 7.0 / 16.0).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            d4.combine_with(&Die::from(3), i32::max),
+            Die::from_values(&[3, 4])
+        );
+    }
+
+    #[test]
+    fn combining_all() {
+        let pool = [Die::new(4), Die::new(6), Die::new(2)];
+        let sum = Die::combine_all(&pool, |values| values.iter().sum());
+        assert_eq!(sum, Die::sum_all(&pool));
+        assert!((sum.get_mean() - Die::sum_all(&pool).get_mean()).abs() < ALLOWED_ERROR);
+        let first = Die::combine_all(&pool, |values| values[0]);
+        assert!((first.get_chance(1) - 0.25).abs() < ALLOWED_ERROR);
+        let pairs = Die::combine_all(&[Die::new(6), Die::new(6)], |values| {
+            i32::from(values[0] == values[1])
+        });
+        assert!((pairs.get_chance(1) - 1.0 / 6.0).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            Die::combine_all(&[], |values| values.len() as i32),
+            Die::empty()
+        );
+    }
+
     #[test]
     fn adding_flat() {
         assert_eq!(
@@ -477,8 +2514,528 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_fn() {
+        let bell = Die::from_fn(1..=4, |&value| if value == 1 { 0.0 } else { 2.0 });
+        assert_eq!(bell, Die::from_values(&[2, 3, 4]));
+        assert!((bell.get_probabilities()[0].chance - 1.0 / 3.0).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::from_fn(1..=6, |_| 1.0), Die::new(6));
+        assert_eq!(Die::from_fn(1..=6, |_| 0.0), Die::empty());
+    }
+
+    #[test]
+    fn bernoulli() {
+        let save = Die::bernoulli(0.35);
+        assert_eq!(save, Die::from_values(&[0, 1]));
+        assert!((save.get_mean() - 0.35).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::bernoulli(0.0), Die::from(0));
+        assert_eq!(Die::bernoulli(1.0), Die::from(1));
+        assert_eq!(Die::coin(), Die::binomial(1, 0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bernoulli_invalid_chance() {
+        Die::bernoulli(-0.1);
+    }
+
+    #[test]
+    fn binomial() {
+        let hits = Die::binomial(10, 0.3);
+        assert_eq!(hits, Die::from_range(0, 10));
+        assert!((hits.get_mean() - 3.0).abs() < ALLOWED_ERROR);
+        assert!((hits.get_variance() - 2.1).abs() < ALLOWED_ERROR);
+        assert!((hits.get_probabilities()[3].chance - 0.266827932).abs() < ALLOWED_ERROR);
+        assert!((Die::binomial(10_000, 0.5).get_mean() - 5000.0).abs() < 1e-6);
+        assert_eq!(Die::binomial(5, 0.0), Die::empty());
+        assert_eq!(Die::binomial(5, 1.0), Die::from(5));
+        assert_eq!(Die::binomial(0, 0.5), Die::empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn binomial_invalid_chance() {
+        Die::binomial(5, 1.5);
+    }
+
+    #[test]
+    fn repeated_trials() {
+        let d6 = Die::new(6);
+        assert!((d6.at_least(|&value| value == 6, 2, 2) - 1.0 / 36.0).abs() < ALLOWED_ERROR);
+        assert!((d6.at_least(|&value| value >= 4, 1, 3) - 0.875).abs() < ALLOWED_ERROR);
+        assert_eq!(d6.at_least(|&value| value > 6, 1, 10), 0.0);
+        assert_eq!(d6.at_least(|&value| value > 6, 0, 10), 1.0);
+        assert_same_chances(
+            d6.count_over_trials(|&value| value == 6, 4)
+                .get_probabilities(),
+            Die::binomial(4, 1.0 / 6.0).get_probabilities(),
+        );
+        assert_same_chances(
+            d6.count_over_trials(|&value| value > 6, 4)
+                .get_probabilities(),
+            Die::from(0).get_probabilities(),
+        );
+        assert!((d6.expected_trials_until(|&value| value >= 5) - 3.0).abs() < ALLOWED_ERROR);
+        assert_eq!(d6.expected_trials_until(|&value| value > 6), f64::INFINITY);
+        assert_eq!(d6.trials_until(|&value| value > 6, 4), Die::from(4));
+        assert_same_chances(
+            d6.trials_until(|&value| value >= 4, 5).get_probabilities(),
+            Die::geometric(0.5, 5).get_probabilities(),
+        );
+    }
+
+    #[test]
+    fn margins() {
+        let d6 = Die::new(6);
+        let margin = d6.margin_over(5);
+        assert!((margin.failure - 4.0 / 6.0).abs() < ALLOWED_ERROR);
+        assert_same_chances(
+            margin.margin.unwrap().get_probabilities(),
+            Die::from_values(&[0, 1]).get_probabilities(),
+        );
+        assert_eq!(d6.margin_over(1).failure, 0.0);
+        assert_same_chances(
+            d6.margin_over(1).margin.unwrap().get_probabilities(),
+            Die::new(6).add_flat(-1).get_probabilities(),
+        );
+        assert_eq!(
+            Die::from(i32::MAX).margin_over(-1).margin,
+            Some(Die::from(i32::MAX))
+        );
+        assert_eq!(d6.margin_over(7).margin, None);
+    }
+
+    #[test]
+    fn partitioning() {
+        let d6 = Die::new(6);
+        let (high, low, high_chance) = d6.partition(|&value| value > 4);
+        assert_same_chances(
+            high.get_probabilities(),
+            Die::from_values(&[5, 6]).get_probabilities(),
+        );
+        assert_same_chances(low.get_probabilities(), Die::new(4).get_probabilities());
+        assert!((high_chance - 1.0 / 3.0).abs() < ALLOWED_ERROR);
+        let (all, none, all_chance) = d6.partition(|_| true);
+        assert_same_chances(all.get_probabilities(), d6.get_probabilities());
+        assert_eq!(none, Die::empty());
+        assert!((all_chance - 1.0).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    fn rolling_to_reach() {
+        let d6 = Die::new(6);
+        assert_eq!(d6.rolls_to_reach(1, 5), Die::from(1));
+        assert_eq!(d6.rolls_to_reach(0, 5), Die::empty());
+        let two = d6.rolls_to_reach(2, 5);
+        assert!((two.get_probabilities()[0].chance - 5.0 / 6.0).abs() < ALLOWED_ERROR);
+        // filling a clock of 4 segments, never progressing half of the time
+        let clock = Die::from_values(&[0, 0, 1, 2]).rolls_to_reach(4, 8);
+        assert_eq!(clock.get_min(), 2);
+        assert_eq!(clock.get_max(), 8);
+        assert!(clock.get_probabilities().last().unwrap().chance > 0.01);
+    }
+
+    #[test]
+    fn geometric() {
+        let rolls_needed = Die::geometric(1.0 / 6.0, 10);
+        assert_eq!(rolls_needed, Die::new(10));
+        assert!((rolls_needed.get_probabilities()[1].chance - 5.0 / 36.0).abs() < ALLOWED_ERROR);
+        assert!(
+            (rolls_needed.get_probabilities()[9].chance - (5.0f64 / 6.0).powi(9)).abs()
+                < ALLOWED_ERROR
+        );
+        assert_eq!(Die::geometric(1.0, 5), Die::from(1));
+        assert_eq!(Die::geometric(0.0, 5), Die::from(5));
+        assert_eq!(Die::geometric(0.5, 0), Die::empty());
+    }
+
+    #[test]
+    fn poisson() {
+        let truncated = Die::poisson(2.0, 2);
+        let weights = [1.0, 2.0, 2.0];
+        for (prob, weight) in truncated.get_probabilities().iter().zip(weights) {
+            assert!((prob.chance - weight / 5.0).abs() < ALLOWED_ERROR);
+        }
+        let wide = Die::poisson(4.0, 40);
+        assert!((wide.get_mean() - 4.0).abs() < ALLOWED_ERROR);
+        assert!((wide.get_variance() - 4.0).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::poisson(0.0, 10), Die::empty());
+    }
+
+    #[test]
+    fn from_samples() {
+        let recorded = Die::from_samples(&[3, 1, 3, 3]);
+        assert_eq!(recorded, Die::from_values(&[1, 3]));
+        assert!((recorded.get_probabilities()[1].chance - 0.75).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::from_sample_iter(vec![3, 1, 3, 3]), recorded);
+        assert_eq!(Die::from_samples(&[]), Die::empty());
+
+        let smoothed = Die::from_samples_smoothed(&[1, 4], 0.5);
+        assert_eq!(smoothed, Die::new(4));
+        assert!((smoothed.get_probabilities()[0].chance - 0.375).abs() < ALLOWED_ERROR);
+        assert!((smoothed.get_probabilities()[1].chance - 0.125).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    fn canonical_string() {
+        let die = &(&Die::new(6) + &Die::new(6)) + &Die::new(10);
+        let parsed: Die = die.to_canonical_string().parse().unwrap();
+        assert_eq!(parsed, die);
+        assert_eq!(
+            parsed
+                .get_probabilities()
+                .iter()
+                .map(|prob| prob.chance)
+                .collect::<Vec<_>>(),
+            die.get_probabilities()
+                .iter()
+                .map(|prob| prob.chance)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(" 2 : 0.5 ,1:0.5".parse::<Die>(), Ok(Die::new(2)));
+        assert_eq!(
+            "1;0.5".parse::<Die>(),
+            Err(ParseDieError::InvalidEntry("1;0.5".to_string()))
+        );
+        assert_eq!(
+            "a:1".parse::<Die>(),
+            Err(ParseDieError::InvalidValue("a".to_string()))
+        );
+        assert_eq!(
+            "1:-1, 2:2".parse::<Die>(),
+            Err(ParseDieError::InvalidChance("-1".to_string()))
+        );
+        assert_eq!("1:0.5".parse::<Die>(), Err(ParseDieError::InvalidSum));
+    }
+
+    #[test]
+    fn weights() {
+        let loot = Die::from_weights(&[(3, 2), (1, 1), (3, 1), (2, 0)]);
+        assert_same_chances(
+            loot.get_probabilities(),
+            Die::from_values(&[1, 3, 3, 3]).get_probabilities(),
+        );
+        assert_same_chances(
+            Die::from_weights(&[(1, 0)]).get_probabilities(),
+            Die::empty().get_probabilities(),
+        );
+        assert_same_chances(
+            Die::from_weights(&[(1, u64::MAX), (2, u64::MAX)]).get_probabilities(),
+            Die::new(2).get_probabilities(),
+        );
+        let counted = Die::from_counts(BTreeMap::from([(2, 30), (4, 10)]));
+        assert_same_chances(
+            counted.get_probabilities(),
+            Die::from_values(&[2, 2, 2, 4]).get_probabilities(),
+        );
+        assert_same_chances(
+            Die::from_counts(Vec::new()).get_probabilities(),
+            Die::empty().get_probabilities(),
+        );
+    }
+
+    #[test]
+    fn table() {
+        let die = Die::from_weights(&[(-1, 1), (0, 2), (2, 5)]);
+        let parsed = Die::from_table(&die.to_anydice("x", crate::AnyDiceView::Normal)).unwrap();
+        assert_same_chances(parsed.get_probabilities(), die.get_probabilities());
+        let parsed = Die::from_table("1; 30\n2; 70.5").unwrap();
+        assert!((parsed.get_probabilities()[1].chance - 70.5 / 100.5).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            Die::from_table("1 50 3"),
+            Err(ParseDieError::InvalidEntry("1 50 3".to_string()))
+        );
+        assert_eq!(
+            Die::from_table("1.5 100"),
+            Err(ParseDieError::InvalidValue("1.5".to_string()))
+        );
+        assert_eq!(
+            Die::from_table("1 x"),
+            Err(ParseDieError::InvalidChance("x".to_string()))
+        );
+        assert_eq!(Die::from_table("1 90"), Err(ParseDieError::InvalidSum));
+        assert_eq!(Die::from_table("#,%"), Err(ParseDieError::InvalidSum));
+    }
+
+    #[test]
+    fn sum_all() {
+        assert_eq!(
+            Die::sum_all(&[Die::new(20), Die::new(2), Die::new(4)]),
+            &(&Die::new(20) + &Die::new(2)) + &Die::new(4)
+        );
+        assert!((Die::sum_all(&vec![Die::new(6); 10]).get_mean() - 35.0).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::sum_all(&[Die::new(8)]), Die::new(8));
+        assert_eq!(Die::sum_all(&[]), Die::empty());
+    }
+
+    #[test]
+    fn repeat() {
+        assert_eq!(Die::new(4).repeat(5), Die::sum_all(&vec![Die::new(4); 5]));
+        assert!((Die::new(4).repeat(5).get_mean() - 12.5).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::new(4).repeat(1), Die::new(4));
+        assert_eq!(Die::new(4).repeat(0), Die::empty());
+
+        let huge = Die::new(6).repeat(200);
+        let probabilities = huge.get_probabilities();
+        assert_eq!((huge.get_min(), huge.get_max()), (200, 1200));
+        assert_eq!(probabilities[0].chance, 6f64.powi(-200));
+        assert_eq!(probabilities[1].chance, 200.0 * 6f64.powi(-200));
+        assert!((huge.get_mean() - 700.0).abs() < ALLOWED_ERROR);
+        assert!((probabilities.iter().map(|prob| prob.chance).sum::<f64>() - 1.0).abs() < 1e-12);
+        let repeated = Die::from_values(&[1, 2, 2]).repeat(3);
+        assert_eq!(repeated.get_max(), 6);
+        assert!((repeated.get_probabilities()[0].chance - 1.0 / 27.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn approximation() {
+        let exact = Die::new(6).repeat_approximate(10, 100);
+        assert!(!exact.is_approximate());
+        assert_eq!(exact, Die::new(6).repeat(10));
+
+        let approximated = Die::new(6).repeat_approximate(100, 100);
+        assert!(approximated.is_approximate());
+        assert!((approximated.get_mean() - 350.0).abs() < 1e-6);
+        assert!((approximated.get_variance() - 291.6666666).abs() < 0.1);
+        let exact = Die::new(6).repeat(100).to_map();
+        let difference = approximated
+            .get_probabilities()
+            .iter()
+            .fold(0.0f64, |acc, prob| {
+                acc.max((prob.chance - exact[&prob.value]).abs())
+            });
+        assert!(difference < 1e-3);
+
+        let mixed = Die::sum_all_approximate(&[Die::new(20), Die::new(4)], 10);
+        assert!(mixed.is_approximate());
+        assert!(mixed.get_min() >= 2);
+        assert!(mixed.get_max() <= 24);
+        assert!(!Die::new(6).is_approximate());
+
+        // exactly `max_support` outcomes are still calculated exactly
+        assert!(!Die::new(6).repeat_approximate(2, 11).is_approximate());
+        assert!(Die::new(6).repeat_approximate(2, 10).is_approximate());
+        assert!(!Die::sum_all_approximate(&[Die::new(6), Die::new(6)], 11).is_approximate());
+        assert!(Die::sum_all_approximate(&[Die::new(6), Die::new(6)], 10).is_approximate());
+
+        let saturated = Die::from(i32::MAX).repeat_approximate(2, 1);
+        assert_eq!(saturated.get_max(), i32::MAX);
+        assert!((saturated.get_probabilities()[0].chance - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn approximation_is_carried_through() {
+        let approximated = Die::new(6).repeat_approximate(100, 100);
+        assert!((&approximated + 3).is_approximate());
+        assert!((&Die::new(4) + &approximated).is_approximate());
+        assert!((&approximated + &Die::new(4)).is_approximate());
+        assert!(approximated.scale(2).is_approximate());
+        assert!(approximated.repeat(2).is_approximate());
+        assert!(approximated
+            .add_dependent(&|_| Die::new(4))
+            .is_approximate());
+        assert!(approximated.prune(0.0).is_approximate());
+        assert!(!(Die::new(6) + Die::new(4)).is_approximate());
+
+        let exploding = Die::new(6).explode_unbounded(ExplodingCondition::Equal, 6, 1e-3);
+        let truncated = exploding.truncated_mass();
+        assert!(truncated > 0.0);
+        assert!(((&exploding + 1).truncated_mass() - truncated).abs() < 1e-12);
+        let twice = 1.0 - (1.0 - truncated) * (1.0 - truncated);
+        assert!(((&exploding + &exploding).truncated_mass() - twice).abs() < 1e-12);
+        assert!((exploding.repeat(2).truncated_mass() - twice).abs() < 1e-12);
+        assert!((exploding.prune(1e-9).truncated_mass() - truncated).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pruning() {
+        let die = Die::from_probabilities(vec![
+            Probability {
+                value: 1,
+                chance: 0.6,
+            },
+            Probability {
+                value: 2,
+                chance: 0.38,
+            },
+            Probability {
+                value: 3,
+                chance: 0.02,
+            },
+        ]);
+        assert!((die.pruned_mass(0.05) - 0.02).abs() < ALLOWED_ERROR);
+        let pruned = die.prune(0.05);
+        assert_eq!(pruned, Die::new(2));
+        assert!(pruned.is_approximate());
+        assert!((pruned.get_probabilities()[0].chance - 0.6 / 0.98).abs() < ALLOWED_ERROR);
+
+        assert!(!die.prune(0.01).is_approximate());
+        assert_eq!(die.prune(0.9), Die::from(1));
+        assert_eq!(die.pruned_mass(0.01), 0.0);
+    }
+
+    #[test]
+    fn compressing() {
+        let die = Die::from_probabilities(vec![
+            Probability {
+                value: 1,
+                chance: 0.6,
+            },
+            Probability {
+                value: 2,
+                chance: 0.38,
+            },
+            Probability {
+                value: 3,
+                chance: 0.02,
+            },
+        ]);
+        let compressed = die.compress(0.05);
+        assert_eq!(compressed.get_probabilities().len(), 2);
+        assert!((compressed.get_chance(1) - 0.6).abs() < ALLOWED_ERROR);
+        assert!((compressed.get_chance(2) - 0.4).abs() < ALLOWED_ERROR);
+        assert!(compressed.is_approximate());
+
+        let untouched = die.compress(0.01);
+        assert!(!untouched.is_approximate());
+        assert!((untouched.get_chance(3) - 0.02).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    fn exploding_unbounded() {
+        let d4 = Die::new(4);
+        let unbounded = d4.explode_unbounded(ExplodingCondition::Equal, 4, 0.1);
+        let values: Vec<_> = unbounded.as_pairs().map(|(value, _)| value).collect();
+        assert_eq!(values, vec![1, 2, 3, 5, 6, 7]);
+        assert!((unbounded.get_probabilities()[0].chance - 4.0 / 15.0).abs() < 1e-12);
+        assert!((unbounded.get_probabilities()[5].chance - 1.0 / 15.0).abs() < 1e-12);
+        assert!(unbounded.is_approximate());
+        assert_eq!(unbounded.truncated_mass(), 1.0 / 16.0);
+        let (_, stats) = d4.explode_unbounded_with_stats(ExplodingCondition::Equal, 4, 0.1);
+        assert_eq!(stats.chain_length, Die::from_values(&[0, 1]));
+        assert!((stats.expected_explosions - 0.2).abs() < 1e-12);
+
+        let precise = d4.explode_unbounded(ExplodingCondition::Equal, 4, 1e-12);
+        assert!((precise.get_mean() - 2.5 * 4.0 / 3.0).abs() < 1e-9);
+
+        let never = d4.explode_unbounded(ExplodingCondition::Greater, 4, 0.1);
+        assert_eq!(never, d4);
+        assert!(!never.is_approximate());
+        assert_eq!(never.truncated_mass(), 0.0);
+        let (_, stats) = d4.explode_unbounded_with_stats(ExplodingCondition::Greater, 4, 0.1);
+        assert_eq!(stats.expected_explosions, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn exploding_unbounded_forever() {
+        Die::new(4).explode_unbounded(ExplodingCondition::GreaterOrEqual, 1, 1e-3);
+    }
+
+    #[test]
+    fn describing() {
+        let d6 = Die::new(6);
+        assert_eq!(d6.describe().as_deref(), Some("d6"));
+        assert_eq!(d6.add_flat(-1).describe().as_deref(), Some("d6 - 1"));
+        assert_eq!(d6.add_flat(0).describe().as_deref(), Some("d6"));
+        assert_eq!(
+            d6.add_flat(1).repeat(3).describe().as_deref(),
+            Some("3x(d6 + 1)")
+        );
+        assert_eq!(
+            d6.add_flat(1).rem_flat(4).describe().as_deref(),
+            Some("(d6 + 1) % 4")
+        );
+        assert_eq!(Die::new(-2).abs().describe().as_deref(), Some("|d-2|"));
+        assert_eq!(Die::from_values(&[1, 2]).describe().as_deref(), None);
+        assert_eq!(
+            (&d6 + &Die::from_values(&[1, 2])).describe().as_deref(),
+            None
+        );
+        assert_eq!((&d6 + &Die::from(2)).describe().as_deref(), Some("d6 + 2"));
+        assert_eq!(Die::from(-3).describe().as_deref(), Some("-3"));
+        assert!(Die::from(-3).label.is_none());
+        assert_eq!(Die::from_values(&[4, 4]).describe().as_deref(), Some("4"));
+        assert_eq!(
+            d6.with_name("damage").with_described(None).name(),
+            Some("damage")
+        );
+        let exploded =
+            Die::new(6)
+                .with_name("damage")
+                .explode_unbounded(ExplodingCondition::Equal, 6, 1e-6);
+        assert_eq!(exploded.describe().as_deref(), None);
+        assert_eq!(exploded.name(), Some("damage"));
+        let three_d6 = Die::new(6).repeat(3);
+        assert!(three_d6.describe().is_some());
+        assert_eq!(three_d6.prune(0.01).describe().as_deref(), None);
+    }
+
+    #[test]
+    fn cached_stats() {
+        fn assert_thread_safe<T: Send + Sync>() {}
+        assert_thread_safe::<Die>();
+
+        let die = Die::from_values(&[1, 2, 2, 7]);
+        assert_eq!(die.get_mean(), calc_mean(die.get_probabilities()));
+        assert_eq!(die.get_variance(), calc_variance(die.get_probabilities()));
+        assert_eq!(die.clone().get_variance(), die.get_variance());
+        assert_eq!((die.get_min(), die.get_max()), (1, 7));
+        assert_eq!(die.get_standard_deviation(), die.get_variance().sqrt());
+        assert!(Die::from(i32::MAX).get_variance().abs() < 1e-9);
+    }
+
+    #[test]
+    fn derived_stats() {
+        let die = Die::from_values(&[1, 2, 2, 7]);
+        for (derived, expected) in [
+            (die.add_flat(3), Die::from_values(&[4, 5, 5, 10])),
+            (die.scale(3), Die::from_values(&[3, 6, 6, 21])),
+            (die.scale(-2), Die::from_values(&[-2, -4, -4, -14])),
+            (-&die, Die::from_values(&[-1, -2, -2, -7])),
+        ] {
+            assert!(derived.stats.get().is_some());
+            assert_eq!(derived, expected);
+            assert!((derived.get_mean() - calc_mean(expected.get_probabilities())).abs() < 1e-9);
+            assert!(
+                (derived.get_variance() - calc_variance(expected.get_probabilities())).abs() < 1e-9
+            );
+            assert_eq!(
+                (derived.get_min(), derived.get_max()),
+                (expected.get_min(), expected.get_max())
+            );
+        }
+        assert_eq!(die.scale(0), Die::from(0));
+        assert_eq!(die.scale(-1).describe().as_deref(), None);
+        assert_eq!((-Die::new(6)).describe().as_deref(), Some("-d6"));
+        assert_eq!(Die::new(6).scale(2).describe().as_deref(), Some("d6 * 2"));
+
+        let saturated = Die::from_values(&[1, i32::MAX]).add_flat(1);
+        assert!(saturated.stats.get().is_none());
+        assert_eq!(saturated.get_max(), i32::MAX);
+        assert_eq!((-Die::from(i32::MIN)).get_min(), i32::MAX);
+    }
+
+    #[test]
+    fn best_of_n() {
+        let elven_accuracy = Die::new(20).best_of_n(3);
+        assert_eq!(elven_accuracy.get_min(), 1);
+        assert_eq!(elven_accuracy.get_max(), 20);
+        assert!((elven_accuracy.get_probabilities()[19].chance - 0.142625).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::new(20).best_of_n(1), Die::new(20));
+        assert_eq!(Die::new(20).best_of_n(0), Die::empty());
+    }
+
+    #[test]
+    fn worst_of_n() {
+        let disadvantage = Die::new(20).worst_of_n(2);
+        assert!((disadvantage.get_probabilities()[0].chance - 0.0975).abs() < ALLOWED_ERROR);
+        assert!((disadvantage.get_mean() - 7.175).abs() < ALLOWED_ERROR);
+        assert_eq!(Die::new(20).worst_of_n(0), Die::empty());
+    }
+
     #[test]
     fn from_i32() {
-        assert_eq!(Die::from_values(&vec![8]), 8.into())
+        assert_eq!(Die::from_values(&[8]), 8.into())
     }
 }
@@ -1,6 +1,10 @@
-use crate::{NormalInitializer, Probability, ProbabilityDistribution};
+use crate::{
+    DropDice, KeepDice, Mechanic, NormalInitializer, Probability, ProbabilityDistribution, Step,
+};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Used to determine what to drop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DropType {
     /// Used to start dropping from the highest.
     High,
@@ -10,6 +14,8 @@ pub enum DropType {
 
 /// Initializers for dropping `n` results from the evaluated pool of [probability
 /// distributions][`ProbabilityDistribution`].
+///
+/// Every initializer applies the [`DropDice`] or [`KeepDice`] mechanic to the pool.
 pub trait DropInitializer<V, P> {
     /// Initializes a new `P` from given [probabilities][`Probability`] and drops `roll_amount`
     /// from the specified end.
@@ -23,14 +29,41 @@ pub trait DropInitializer<V, P> {
     ) -> P
     where
         P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
-        V: Copy + Ord + From<i32> + std::iter::Sum,
-        i32: From<V>,
+        V: Copy + Ord + From<i32> + Step + std::iter::Sum,
     {
-        drop_by_condition(
-            &vec![P::from_probabilities(probabilities); times],
-            drop_condition,
-            drop_amount,
-        )
+        DropDice::new(drop_amount, drop_condition)
+            .apply(&vec![P::from_probabilities(probabilities); times])
+    }
+
+    /// Evaluates a pool of possibly different `P` and drops `drop_amount` from the specified end.
+    ///
+    /// Unlike the other initializers, every member of the pool may be a different distribution.
+    fn drop_from_pool(pool: &[P], drop_amount: usize, drop_condition: DropType) -> P
+    where
+        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+        V: Copy + Ord + From<i32> + Step + std::iter::Sum,
+    {
+        DropDice::new(drop_amount, drop_condition).apply(pool)
+    }
+
+    /// Evaluates a pool of possibly different `P` and keeps `keep_amount` from the specified end,
+    /// dropping the rest.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, DropInitializer, DropType, NormalInitializer, ProbabilityDistribution };
+    /// // roll 1d20, 1d12 and 1d8, keep the highest two
+    /// let pool = [Die::new(20), Die::new(12), Die::new(8)];
+    /// let highest_two = Die::keep_from_pool(&pool, 2, DropType::High);
+    /// assert_eq!(highest_two.get_min(), 2);
+    /// assert_eq!(highest_two.get_max(), 32);
+    /// ```
+    fn keep_from_pool(pool: &[P], keep_amount: usize, keep_condition: DropType) -> P
+    where
+        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+        V: Copy + Ord + From<i32> + Step + std::iter::Sum,
+    {
+        KeepDice::new(keep_amount, keep_condition).apply(pool)
     }
 
     /// Initializes a new `P` from a given range and drops `roll_amount` from the specified end.
@@ -45,14 +78,9 @@ pub trait DropInitializer<V, P> {
     ) -> P
     where
         P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
-        V: Copy + Ord + From<i32> + std::iter::Sum,
-        i32: From<V>,
+        V: Copy + Ord + From<i32> + Step + std::iter::Sum,
     {
-        drop_by_condition(
-            &vec![P::from_range(start, end); times],
-            drop_condition,
-            drop_amount,
-        )
+        DropDice::new(drop_amount, drop_condition).apply(&vec![P::from_range(start, end); times])
     }
 
     /// Initializes a new `P` from given values and drops `roll_amount` from the specified end.
@@ -66,14 +94,9 @@ pub trait DropInitializer<V, P> {
     ) -> P
     where
         P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
-        V: Copy + Ord + From<i32> + std::iter::Sum,
-        i32: From<V>,
+        V: Copy + Ord + From<i32> + Step + std::iter::Sum,
     {
-        drop_by_condition(
-            &vec![P::from_values(values); times],
-            drop_condition,
-            drop_amount,
-        )
+        DropDice::new(drop_amount, drop_condition).apply(&vec![P::from_values(values); times])
     }
 
     /// Initializes a new `P` the specified amount of times and drops `drop_amount` from the specified end.
@@ -82,67 +105,84 @@ pub trait DropInitializer<V, P> {
     fn new_drop(amount: V, times: usize, drop_amount: usize, drop_condition: DropType) -> P
     where
         P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
-        V: Copy + Ord + From<i32> + std::iter::Sum,
-        i32: From<V>,
+        V: Copy + Ord + From<i32> + Step + std::iter::Sum,
     {
-        drop_by_condition(&vec![P::new(amount); times], drop_condition, drop_amount)
+        DropDice::new(drop_amount, drop_condition).apply(&vec![P::new(amount); times])
     }
 }
 
 impl<V, P> DropInitializer<V, P> for P
 where
     P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
-    V: Copy + Ord + From<i32> + std::iter::Sum,
-    i32: From<V>,
+    V: Copy + Ord + From<i32> + Step + std::iter::Sum,
 {
 }
 
-fn prep<T, I>(probability_structs: &[T]) -> Vec<(Vec<I>, f64)>
+/// Lazily yields every combination of values of a pool together with its chance, advancing the
+/// last member first like an odometer.
+#[derive(Debug, Clone)]
+pub(crate) struct Combinations<'a, I> {
+    pool: Vec<&'a [Probability<I>]>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<I> Iterator for Combinations<'_, I>
+where
+    I: Copy,
+{
+    type Item = (Vec<I>, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let combination = self.pool.iter().zip(&self.indices).fold(
+            (Vec::with_capacity(self.pool.len()), 1.0),
+            |(mut values, chance), (probabilities, &index)| {
+                values.push(probabilities[index].value);
+                (values, chance * probabilities[index].chance)
+            },
+        );
+        self.done = true;
+        for (probabilities, index) in self.pool.iter().zip(self.indices.iter_mut()).rev() {
+            *index += 1;
+            if *index < probabilities.len() {
+                self.done = false;
+                break;
+            }
+            *index = 0;
+        }
+        Some(combination)
+    }
+}
+
+/// Lazily enumerates every combination of values of the pool together with its chance, in the
+/// order of the pool.
+pub(crate) fn prep<T, I>(probability_structs: &[T]) -> Combinations<'_, I>
 where
     T: ProbabilityDistribution<I>,
     I: Copy,
 {
-    if let Some(first) = probability_structs.first() {
-        let first: Vec<Vec<Probability<I>>> = first
-            .get_probabilities()
-            .iter()
-            .map(|val| vec![*val])
-            .collect();
-        probability_structs[1..]
-            .iter()
-            .fold(first, |acc, curr| {
-                acc.iter()
-                    .flat_map(|prev_val| {
-                        curr.get_probabilities()
-                            .iter()
-                            .map(|val_to_add| {
-                                let mut new_v1 = prev_val.clone();
-                                new_v1.push(*val_to_add);
-                                new_v1
-                            })
-                            .collect::<Vec<_>>()
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .iter()
-            .map(|combination| {
-                let (value, chance) =
-                    combination
-                        .iter()
-                        .fold((vec![], 1.0), |(mut values, chance), curr| {
-                            values.push(curr.value);
-                            let chance = chance * curr.chance;
-                            (values, chance)
-                        });
-                (value, chance)
-            })
-            .collect()
-    } else {
-        Vec::new()
+    let pool: Vec<&[Probability<I>]> = probability_structs
+        .iter()
+        .map(|probability_struct| probability_struct.get_probabilities())
+        .collect();
+    Combinations {
+        indices: vec![0; pool.len()],
+        done: pool.is_empty() || pool.iter().any(|probabilities| probabilities.is_empty()),
+        pool,
     }
 }
 
-fn drop_by_condition<T, P>(
+/// Sums up the kept dice of the pool without enumerating every combination.
+///
+/// Every outcome has exactly one threshold, the worst kept value. For each candidate threshold
+/// `t`, the dice get folded into states of how many rolled better than `t`, how many rolled
+/// exactly `t` and the sum of the better ones. The threshold is the worst kept value if fewer
+/// than the kept amount rolled better, but enough rolled at least `t`, with the missing kept
+/// dice all showing `t`. This stays polynomial in the pool size, values and kept amount.
+pub(crate) fn drop_by_condition<T, P>(
     probability_structs: &[P],
     drop_condition: DropType,
     drop_amount: usize,
@@ -151,27 +191,65 @@ where
     P: ProbabilityDistribution<T> + NormalInitializer<T, P>,
     T: Copy + Ord + std::iter::Sum,
 {
-    P::from_probabilities(
-        prep(probability_structs)
-            .iter()
-            .map(|(values, chance)| {
-                let mut new_values = values.clone();
-                new_values.sort();
-
-                match drop_condition {
-                    DropType::High => (),
-                    DropType::Low => new_values.reverse(),
-                }
-
-                for _ in 0..drop_amount {
-                    new_values.pop();
-                }
+    if probability_structs.is_empty() {
+        return P::from_probabilities(Vec::new());
+    }
+    let keep_amount = probability_structs.len().saturating_sub(drop_amount);
+    if keep_amount == 0 {
+        return P::from_probabilities(vec![Probability {
+            value: std::iter::empty().sum(),
+            chance: 1.0,
+        }]);
+    }
+    let is_better = |a: &T, b: &T| match drop_condition {
+        DropType::High => a < b,
+        DropType::Low => a > b,
+    };
+    let add = |a: T, b: T| [a, b].into_iter().sum::<T>();
+    let thresholds: BTreeSet<T> = probability_structs
+        .iter()
+        .flat_map(|probability_struct| probability_struct.get_probabilities())
+        .map(|prob| prob.value)
+        .collect();
 
-                Probability {
-                    value: new_values.into_iter().sum(),
-                    chance: *chance,
+    let mut accumulated: BTreeMap<T, f64> = BTreeMap::new();
+    for &threshold in &thresholds {
+        // (better, equal, sum of better) -> chance
+        let mut states: BTreeMap<(usize, usize, T), f64> = BTreeMap::new();
+        states.insert((0, 0, std::iter::empty().sum()), 1.0);
+        for probability_struct in probability_structs {
+            let mut next_states = BTreeMap::new();
+            for (&(better, equal, sum), &chance) in &states {
+                for prob in probability_struct.get_probabilities() {
+                    let key = if prob.value == threshold {
+                        (better, (equal + 1).min(keep_amount), sum)
+                    } else if is_better(&prob.value, &threshold) {
+                        if better + 1 == keep_amount {
+                            continue;
+                        }
+                        (better + 1, equal, add(sum, prob.value))
+                    } else {
+                        (better, equal, sum)
+                    };
+                    *next_states.entry(key).or_insert(0.0) += chance * prob.chance;
                 }
-            })
+            }
+            states = next_states;
+        }
+        for ((better, equal, sum), chance) in states {
+            if better + equal >= keep_amount {
+                let value = add(
+                    sum,
+                    std::iter::repeat_n(threshold, keep_amount - better).sum(),
+                );
+                *accumulated.entry(value).or_insert(0.0) += chance;
+            }
+        }
+    }
+    P::from_probabilities(
+        accumulated
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
             .collect(),
     )
 }
@@ -184,7 +262,7 @@ mod tests {
     #[test]
     fn prep_dice_same() {
         let input = vec![Die::new(2), Die::new(2), Die::new(2)];
-        let fn_result = prep(&input);
+        let fn_result: Vec<_> = prep(&input).collect();
         assert_eq!(
             fn_result,
             vec![
@@ -203,7 +281,7 @@ mod tests {
     #[test]
     fn prep_dice_difference() {
         let input = vec![Die::new(2), Die::new(3), Die::new(1)];
-        let fn_result = prep(&input);
+        let fn_result: Vec<_> = prep(&input).collect();
         assert_eq!(
             fn_result,
             vec![
@@ -217,15 +295,22 @@ mod tests {
         )
     }
 
+    #[test]
+    fn prep_lazily() {
+        assert_eq!(prep::<Die, i32>(&[]).next(), None);
+        let pool = vec![Die::new(6); 20];
+        let mut combinations = prep(&pool);
+        assert_eq!(combinations.next().unwrap().0, vec![1; 20]);
+        let mut second = vec![1; 20];
+        second[19] = 2;
+        assert_eq!(combinations.next().unwrap().0, second);
+    }
+
     #[test]
     fn drop_by_condition_low() {
         assert_eq!(
-            drop_by_condition(
-                &vec![Die::new(2), Die::new(2), Die::new(2)],
-                DropType::Low,
-                1
-            )
-            .get_probabilities(),
+            drop_by_condition(&[Die::new(2), Die::new(2), Die::new(2)], DropType::Low, 1)
+                .get_probabilities(),
             &vec![
                 Probability {
                     value: 2,
@@ -246,12 +331,8 @@ mod tests {
     #[test]
     fn drop_by_condition_high() {
         assert_eq!(
-            drop_by_condition(
-                &vec![Die::new(2), Die::new(2), Die::new(2)],
-                DropType::Low,
-                1
-            )
-            .get_probabilities(),
+            drop_by_condition(&[Die::new(2), Die::new(2), Die::new(2)], DropType::Low, 1)
+                .get_probabilities(),
             &vec![
                 Probability {
                     value: 2,
@@ -269,6 +350,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn drop_by_condition_matches_enumeration() {
+        let pool = [Die::new(4), Die::new(2), Die::from_values(&[-1, 3, 3, 6])];
+        for drop_condition in [DropType::High, DropType::Low] {
+            for drop_amount in 0..=4 {
+                let mut expected = BTreeMap::new();
+                for (mut values, chance) in prep(&pool) {
+                    values.sort();
+                    if drop_condition == DropType::Low {
+                        values.reverse();
+                    }
+                    values.truncate(values.len().saturating_sub(drop_amount));
+                    *expected
+                        .entry(values.into_iter().sum::<i32>())
+                        .or_insert(0.0) += chance;
+                }
+                let result = drop_by_condition(&pool, drop_condition, drop_amount);
+                assert_eq!(result.get_probabilities().len(), expected.len());
+                for (prob, (value, chance)) in result.iter().zip(expected) {
+                    assert_eq!(prob.value, value);
+                    assert!((prob.chance - chance).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn drop_by_condition_large_pool() {
+        let dropped = Die::new_drop(6, 8, 2, DropType::Low);
+        assert_eq!(dropped.get_min(), 6);
+        assert_eq!(dropped.get_max(), 36);
+        let total: f64 = dropped.iter().map(|prob| prob.chance).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(Die::new_drop(6, 40, 30, DropType::High).get_max(), 60);
+    }
+
     #[test]
     fn drop_initializers() {
         let expected_output = Die::from_probabilities(vec![
@@ -299,7 +416,7 @@ mod tests {
             expected_output
         );
         assert_eq!(
-            Die::drop_from_values(&vec![1, 2, 3], 4, 2, DropType::Low),
+            Die::drop_from_values(&[1, 2, 3], 4, 2, DropType::Low),
             expected_output
         );
         assert_eq!(
@@ -325,4 +442,18 @@ mod tests {
             expected_output
         )
     }
+
+    #[test]
+    fn pool_initializers() {
+        let pool = [Die::new(4), Die::new(2), Die::new(6)];
+        let keep_highest = Die::keep_from_pool(&pool, 2, DropType::High);
+        assert_eq!(keep_highest, Die::drop_from_pool(&pool, 1, DropType::Low));
+        assert_eq!(keep_highest.get_min(), 2);
+        assert_eq!(keep_highest.get_max(), 10);
+        assert!((keep_highest.get_mean() - 6.1875).abs() < 1e-9);
+        assert_eq!(
+            Die::keep_from_pool(&pool, 3, DropType::Low),
+            &(&Die::new(4) + &Die::new(2)) + &Die::new(6)
+        );
+    }
 }
@@ -0,0 +1,137 @@
+use crate::common::*;
+use crate::{Die, DominanceResult, NormalInitializer, Probability, ProbabilityDistribution};
+
+/// Object safe facade over [`ProbabilityDistribution<i32>`], so different distribution types can
+/// be stored together, like in a `Vec<Box<dyn DynDistribution>>`.
+///
+/// Implemented for every [`ProbabilityDistribution<i32>`]. Combining distributions goes through
+/// [`Die`], since the concrete types are unknown.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, DynDistribution, NormalInitializer, ProbabilityDistribution };
+/// let pool: Vec<Box<dyn DynDistribution>> = vec![Box::new(Die::new(6)), Box::new(Die::coin())];
+/// let sum = Die::sum_dyn(pool.iter().map(|distribution| distribution.as_ref()));
+/// assert_eq!(sum.get_max(), 7);
+/// assert!((pool[0].mean() - 3.5).abs() < 1e-9);
+/// assert_eq!(pool[1].compare_to(pool[0].as_ref()).get_max(), 0);
+/// ```
+pub trait DynDistribution {
+    /// Returns the probabilities of this distribution.
+    fn probability_slice(&self) -> &[Probability<i32>];
+
+    /// Converts this distribution into a [`Die`].
+    fn to_die(&self) -> Die {
+        Die::from_probabilities(self.probability_slice().to_vec())
+    }
+
+    /// Returns the lowest possible value.
+    fn min_value(&self) -> i32 {
+        self.probability_slice()
+            .iter()
+            .map(|prob| prob.value)
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Returns the highest possible value.
+    fn max_value(&self) -> i32 {
+        self.probability_slice()
+            .iter()
+            .map(|prob| prob.value)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the mean, see [`ProbabilityDistribution::get_mean`].
+    fn mean(&self) -> f64 {
+        calc_mean(self.probability_slice())
+    }
+
+    /// Returns the variance, see [`ProbabilityDistribution::get_variance`].
+    fn variance(&self) -> f64 {
+        calc_variance(self.probability_slice())
+    }
+
+    /// Returns the standard deviation, see [`ProbabilityDistribution::get_standard_deviation`].
+    fn standard_deviation(&self) -> f64 {
+        calc_standard_deviation(self.probability_slice())
+    }
+
+    /// Checks whether this distribution stochastically dominates the given one, see
+    /// [`ProbabilityDistribution::dominates`].
+    fn dominance(&self, other: &dyn DynDistribution) -> DominanceResult {
+        calc_dominance(self.probability_slice(), other.probability_slice())
+    }
+
+    /// Compares this distribution to the given one, see [`Die::compare`].
+    fn compare_to(&self, other: &dyn DynDistribution) -> Die {
+        self.to_die().compare(&other.to_die())
+    }
+}
+
+impl<P> DynDistribution for P
+where
+    P: ProbabilityDistribution<i32>,
+{
+    fn probability_slice(&self) -> &[Probability<i32>] {
+        self.get_probabilities()
+    }
+}
+
+impl Die {
+    /// Sums up distributions of possibly different types, see [`Die::sum_all`].
+    pub fn sum_dyn<'a, I>(distributions: I) -> Die
+    where
+        I: IntoIterator<Item = &'a dyn DynDistribution>,
+    {
+        Die::sum_all(
+            &distributions
+                .into_iter()
+                .map(|distribution| distribution.to_die())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HasProbabilities;
+
+    struct Constant(Vec<Probability<i32>>);
+
+    impl HasProbabilities<i32> for Constant {
+        fn probabilities(&self) -> &[Probability<i32>] {
+            &self.0
+        }
+
+        fn from_sorted_probabilities(probabilities: Vec<Probability<i32>>) -> Self {
+            Constant(probabilities)
+        }
+    }
+
+    #[test]
+    fn heterogeneous() {
+        let pool: Vec<Box<dyn DynDistribution>> = vec![
+            Box::new(Die::new(4)),
+            Box::new(Constant(vec![Probability {
+                value: 10,
+                chance: 1.0,
+            }])),
+        ];
+        assert_eq!(pool[1].min_value(), 10);
+        assert_eq!(pool[0].max_value(), 4);
+        assert_eq!(pool[1].variance(), 0.0);
+        assert_eq!(pool[1].to_die(), Die::from(10));
+        assert_eq!(
+            pool[1].dominance(pool[0].as_ref()),
+            DominanceResult::FirstOrder
+        );
+        assert_eq!(
+            Die::sum_dyn(pool.iter().map(|distribution| distribution.as_ref())),
+            Die::new(4).add_flat(10)
+        );
+        assert_eq!(Die::sum_dyn([]), Die::empty());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::common::*;
+use crate::{Die, NormalInitializer, Probability};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Estimated chance of a single face of a [`DieEstimate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FaceEstimate {
+    /// The face.
+    pub value: i32,
+    /// How often the face was observed.
+    pub count: u64,
+    /// Estimated chance, including smoothing.
+    pub chance: f64,
+    /// Lower bound of the confidence interval.
+    pub lower: f64,
+    /// Upper bound of the confidence interval.
+    pub upper: f64,
+}
+
+/// Estimate of a possibly loaded die from observed rolls.
+///
+/// The chances are the maximum likelihood estimate, meaning the observed frequencies, with an
+/// optional additive smoothing applied to the count of every face. The confidence intervals are
+/// Wilson score intervals of the raw counts, so they stay within `0` and `1` and remain useful
+/// for faces that were rarely or never rolled.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ DieEstimate, ProbabilityDistribution };
+/// let rolls = [1, 2, 3, 4, 5, 6, 6, 6, 6, 6, 6, 6];
+/// let estimate = DieEstimate::from_samples(&rolls, 1..=6, 0.0, 0.95);
+/// let six = estimate.faces()[5];
+/// assert_eq!(six.count, 7);
+/// assert!(six.lower < six.chance && six.chance < six.upper);
+/// // even from so few rolls, a fair six is unlikely
+/// assert!(six.lower > 1.0 / 6.0);
+/// assert!((estimate.die().get_mean() - 4.75).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DieEstimate {
+    die: Die,
+    faces: Vec<FaceEstimate>,
+    confidence: f64,
+}
+
+impl DieEstimate {
+    /// Estimates the die from the given rolls.
+    ///
+    /// Every value of `faces` is part of the estimate, even if it was never rolled, as well as
+    /// every rolled value outside of it. `smoothing` gets added to the count of every face and
+    /// `confidence` is the chance of the intervals to cover the real chance, like `0.95`.
+    ///
+    /// When given no rolls and no faces, the die is [empty][`Die::empty()`].
+    ///
+    /// # Panics
+    /// Panics if `confidence` is not between `0.0` and `1.0` or `smoothing` is negative.
+    pub fn from_samples(
+        samples: &[i32],
+        faces: RangeInclusive<i32>,
+        smoothing: f64,
+        confidence: f64,
+    ) -> Self {
+        assert!(
+            (0.0..1.0).contains(&confidence),
+            "confidence has to be between 0 and 1"
+        );
+        assert!(smoothing >= 0.0, "smoothing can't be negative");
+        let mut counts: BTreeMap<i32, u64> = faces.map(|face| (face, 0)).collect();
+        for &sample in samples {
+            *counts.entry(sample).or_insert(0) += 1;
+        }
+        let n = samples.len() as f64;
+        let total = n + smoothing * counts.len() as f64;
+        let z = normal_quantile(0.5 + confidence / 2.0);
+        let faces: Vec<FaceEstimate> = counts
+            .into_iter()
+            .map(|(value, count)| {
+                let (lower, upper) = wilson_interval(count as f64, n, z);
+                FaceEstimate {
+                    value,
+                    count,
+                    chance: if total > 0.0 {
+                        (count as f64 + smoothing) / total
+                    } else {
+                        0.0
+                    },
+                    lower,
+                    upper,
+                }
+            })
+            .collect();
+        let die = if total > 0.0 {
+            Die::from_probabilities(
+                faces
+                    .iter()
+                    .filter(|face| face.chance > 0.0)
+                    .map(|face| Probability {
+                        value: face.value,
+                        chance: face.chance,
+                    })
+                    .collect(),
+            )
+        } else {
+            Die::empty()
+        };
+        DieEstimate {
+            die,
+            faces,
+            confidence,
+        }
+    }
+
+    /// Returns the estimated die.
+    pub fn die(&self) -> &Die {
+        &self.die
+    }
+
+    /// Returns the estimate of every face, ordered by value.
+    pub fn faces(&self) -> &[FaceEstimate] {
+        &self.faces
+    }
+
+    /// Returns the confidence level of the intervals.
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+}
+
+/// Wilson score interval of `successes` out of `n` trials.
+fn wilson_interval(successes: f64, n: f64, z: f64) -> (f64, f64) {
+    if n == 0.0 {
+        return (0.0, 1.0);
+    }
+    let p = successes / n;
+    let z_squared = z * z;
+    let denominator = 1.0 + z_squared / n;
+    let center = (p + z_squared / (2.0 * n)) / denominator;
+    let half_width = z / denominator * (p * (1.0 - p) / n + z_squared / (4.0 * n * n)).sqrt();
+    (
+        (center - half_width).max(0.0),
+        (center + half_width).min(1.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimating() {
+        let estimate = DieEstimate::from_samples(&[1, 1, 2, 7], 1..=3, 1.0, 0.95);
+        let values: Vec<_> = estimate.faces().iter().map(|face| face.value).collect();
+        assert_eq!(values, vec![1, 2, 3, 7]);
+        let chances: Vec<_> = estimate.faces().iter().map(|face| face.chance).collect();
+        assert_eq!(chances, vec![0.375, 0.25, 0.125, 0.25]);
+        assert_eq!(estimate.die(), &Die::from_values(&[1, 2, 3, 7]));
+        assert_eq!(estimate.confidence(), 0.95);
+
+        let unseen = estimate.faces()[2];
+        assert_eq!(unseen.count, 0);
+        assert_eq!(unseen.lower, 0.0);
+        assert!(unseen.upper > 0.0);
+
+        let empty = DieEstimate::from_samples(&[], RangeInclusive::new(1, 0), 0.0, 0.5);
+        assert_eq!(empty.die(), &Die::empty());
+    }
+
+    #[test]
+    fn wilson() {
+        // 95% interval of 5 out of 10, as listed in common references
+        let (lower, upper) = wilson_interval(5.0, 10.0, normal_quantile(0.975));
+        assert!((lower - 0.2366).abs() < 1e-4);
+        assert!((upper - 0.7634).abs() < 1e-4);
+        assert_eq!(wilson_interval(0.0, 0.0, 1.96), (0.0, 1.0));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::common::ALLOWED_ERROR;
+use crate::{Die, ProbabilityDistribution};
+use core::ops::Not;
+
+/// The chance of a named condition, like "hit" or "target fails save".
+///
+/// Events can be combined, but every combination states its assumption explicitly: either the
+/// events are independent, mutually exclusive, or the chance of the second event is known given
+/// the first one.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, Event, NormalInitializer };
+/// let hit = Event::from_distribution("hit", &Die::new(20), |&value| value >= 11);
+/// let failed_save = Event::from_distribution("failed save", &Die::new(20), |&value| value < 13);
+/// let both = hit.and_independent(&failed_save);
+/// assert_eq!(both.name(), "(hit and failed save)");
+/// assert!((both.chance() - 0.3).abs() < 1e-9);
+/// assert!(((!&both).chance() - 0.7).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    name: String,
+    chance: f64,
+}
+
+impl Event {
+    /// Creates a new event with the given name and chance.
+    ///
+    /// # Panics
+    /// Panics if `chance` is not between `0.0` and `1.0`.
+    pub fn new(name: &str, chance: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&chance),
+            "chance has to be between 0 and 1"
+        );
+        Event {
+            name: name.to_string(),
+            chance,
+        }
+    }
+
+    /// Creates a new event from the chance of the given distribution to roll a value fulfilling
+    /// `condition_fn`.
+    pub fn from_distribution<T, F>(
+        name: &str,
+        probability_distribution: &impl ProbabilityDistribution<T>,
+        condition_fn: F,
+    ) -> Self
+    where
+        F: Fn(&T) -> bool,
+    {
+        let chance = probability_distribution
+            .iter()
+            .filter(|prob| condition_fn(&prob.value))
+            .fold(0.0, |acc, prob| acc + prob.chance);
+        Event::new(name, chance.clamp(0.0, 1.0))
+    }
+
+    /// Returns the name of this event.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the chance of this event happening.
+    pub fn chance(&self) -> f64 {
+        self.chance
+    }
+
+    /// Both events happen, assuming they are independent of each other.
+    pub fn and_independent(&self, other: &Event) -> Event {
+        Event::new(
+            &format!("({} and {})", self.name, other.name),
+            self.chance * other.chance,
+        )
+    }
+
+    /// At least one of the events happens, assuming they are independent of each other.
+    pub fn or_independent(&self, other: &Event) -> Event {
+        Event::new(
+            &format!("({} or {})", self.name, other.name),
+            self.chance + other.chance - self.chance * other.chance,
+        )
+    }
+
+    /// One of the events happens, assuming they can't happen at the same time.
+    ///
+    /// # Panics
+    /// Panics if both chances add up to more than `1.0`, since such events can't be mutually
+    /// exclusive.
+    pub fn or_exclusive(&self, other: &Event) -> Event {
+        let chance = self.chance + other.chance;
+        assert!(
+            chance <= 1.0 + ALLOWED_ERROR,
+            "mutually exclusive chances can't add up to more than 1"
+        );
+        Event::new(
+            &format!("({} or {})", self.name, other.name),
+            chance.min(1.0),
+        )
+    }
+
+    /// Both events happen, where `conditional` holds the chance of the second event given that
+    /// this one already happened.
+    pub fn and_given(&self, conditional: &Event) -> Event {
+        Event::new(
+            &format!("({} and then {})", self.name, conditional.name),
+            self.chance * conditional.chance,
+        )
+    }
+
+    /// The event happens at least once in `trials` independent repeats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Event, NormalInitializer };
+    /// let crit = Event::from_distribution("crit", &Die::new(20), |&value| value == 20);
+    /// let any_crit = crit.at_least_one(4);
+    /// assert_eq!(any_crit.name(), "(crit at least once in 4)");
+    /// assert!((any_crit.chance() - (1.0 - 0.95f64.powi(4))).abs() < 1e-12);
+    /// ```
+    pub fn at_least_one(&self, trials: u32) -> Event {
+        Event::new(
+            &format!("({} at least once in {})", self.name, trials),
+            at_least_one(self.chance, trials),
+        )
+    }
+
+    /// The event happens at least `occurrences` times in `trials` independent repeats.
+    pub fn at_least(&self, occurrences: u32, trials: u32) -> Event {
+        Event::new(
+            &format!(
+                "({} at least {} times in {})",
+                self.name, occurrences, trials
+            ),
+            at_least(self.chance, occurrences, trials),
+        )
+    }
+
+    /// Creates the distribution of how many of `trials` independent repeats this event happens
+    /// in.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Event, NormalInitializer, ProbabilityDistribution };
+    /// let hit = Event::new("hit", 0.6);
+    /// let hits = hit.count_over_trials(5);
+    /// assert!((hits.get_mean() - 3.0).abs() < 1e-9);
+    /// assert_eq!(hit.count_over_trials(1), hit.to_die());
+    /// assert!((hit.count_over_trials(1).get_chance(1) - 0.6).abs() < 1e-9);
+    /// ```
+    pub fn count_over_trials(&self, trials: u32) -> Die {
+        Die::binomial(trials, self.chance)
+    }
+
+    /// Converts this event into a [`Die`] rolling `1` if it happens and `0` otherwise.
+    pub fn to_die(&self) -> Die {
+        Die::bernoulli(self.chance)
+    }
+}
+
+impl Not for &Event {
+    type Output = Event;
+
+    /// The event does not happen.
+    fn not(self) -> Self::Output {
+        Event::new(&format!("not {}", self.name), 1.0 - self.chance)
+    }
+}
+
+impl Not for Event {
+    type Output = Event;
+
+    fn not(self) -> Self::Output {
+        !&self
+    }
+}
+
+/// Returns the chance of an event with the given chance happening at least once in `trials`
+/// independent repeats.
+///
+/// # Panics
+/// Panics if `chance` is not between `0.0` and `1.0`.
+///
+/// # Examples
+/// ```
+/// # use die_stats::at_least_one;
+/// assert!((at_least_one(0.5, 2) - 0.75).abs() < 1e-12);
+/// ```
+pub fn at_least_one(chance: f64, trials: u32) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&chance),
+        "chance has to be between 0 and 1"
+    );
+    1.0 - (1.0 - chance).powf(f64::from(trials))
+}
+
+/// Returns the chance of an event with the given chance happening at least `occurrences` times
+/// in `trials` independent repeats.
+///
+/// # Panics
+/// Panics if `chance` is not between `0.0` and `1.0`.
+pub fn at_least(chance: f64, occurrences: u32, trials: u32) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&chance),
+        "chance has to be between 0 and 1"
+    );
+    match occurrences {
+        0 => 1.0,
+        1 => at_least_one(chance, trials),
+        _ if occurrences > trials => 0.0,
+        _ => Die::binomial(trials, chance)
+            .get_survival(occurrences as i32 - 1)
+            .clamp(0.0, 1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn combining() {
+        let crit = Event::from_distribution("crit", &Die::new(20), |&value| value == 20);
+        let fumble = Event::from_distribution("fumble", &Die::new(20), |&value| value == 1);
+        assert!((crit.chance() - 0.05).abs() < ALLOWED_ERROR);
+
+        let either = crit.or_exclusive(&fumble);
+        assert_eq!(either.name(), "(crit or fumble)");
+        assert!((either.chance() - 0.1).abs() < ALLOWED_ERROR);
+
+        let either_independent = crit.or_independent(&fumble);
+        assert!((either_independent.chance() - 0.0975).abs() < ALLOWED_ERROR);
+
+        let confirmed = crit.and_given(&Event::new("confirm", 0.5));
+        assert_eq!(confirmed.name(), "(crit and then confirm)");
+        assert!((confirmed.chance() - 0.025).abs() < ALLOWED_ERROR);
+
+        let twice = crit.at_least(2, 3);
+        assert_eq!(twice.name(), "(crit at least 2 times in 3)");
+        assert!((twice.chance() - (3.0 * 0.05 * 0.05 * 0.95 + 0.05f64.powi(3))).abs() < 1e-12);
+        assert_eq!(crit.at_least(0, 3).chance(), 1.0);
+        assert_eq!(crit.at_least(4, 3).chance(), 0.0);
+        assert_eq!(at_least_one(0.3, 0), 0.0);
+        assert!((crit.at_least(1, 4).chance() - crit.at_least_one(4).chance()).abs() < 1e-12);
+
+        let no_crit = !crit;
+        assert_eq!(no_crit.name(), "not crit");
+        assert!((no_crit.chance() - 0.95).abs() < ALLOWED_ERROR);
+        assert_eq!(no_crit.to_die(), Die::from_values(&[0, 1]));
+        assert!((no_crit.to_die().get_chance(1) - 0.95).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_exclusive() {
+        Event::new("a", 0.6).or_exclusive(&Event::new("b", 0.6));
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{NormalInitializer, Probability, ProbabilityDistribution};
+use crate::{
+    Die, ExplodeInto, Mechanic, NormalInitializer, Probability, ProbabilityDistribution, Step,
+};
 
 /// Used to determine the fuse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExplodingCondition {
     /// Explodes on everything lower than given value.
     Lower,
@@ -14,7 +17,29 @@ pub enum ExplodingCondition {
     Greater,
 }
 
+/// How often a die explodes in a row, as returned by [`Die::explode_unbounded_with_stats`] and
+/// [`ExplodeDice::stats`][`crate::ExplodeDice::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplosionStats {
+    /// Mean amount of explosions in a row.
+    pub expected_explosions: f64,
+    /// Distribution of the amount of explosions in a row, `0` meaning the die didn't explode.
+    pub chain_length: Die,
+}
+
+impl ExplosionStats {
+    /// Collects the stats of the given distribution of chain lengths.
+    pub fn new(chain_length: Die) -> Self {
+        ExplosionStats {
+            expected_explosions: chain_length.get_mean(),
+            chain_length,
+        }
+    }
+}
+
 /// Initializers for "exploding" a [probability distribution][`crate::ProbabilityDistribution`] on a given condition.
+///
+/// Every initializer applies the [`ExplodeInto`] mechanic to the initialized distribution.
 pub trait ExplodingInitializer<V, P> {
     /// Initializes a new `P` from given [probabilities][`Probability`] and explodes on given condition.
     ///
@@ -26,15 +51,11 @@ pub trait ExplodingInitializer<V, P> {
         exploding: P,
     ) -> P
     where
-        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V> + 'static,
-        V: Copy + Ord + From<i32> + 'static,
-        i32: From<V>,
+        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+        V: Copy + Ord + From<i32> + Step,
     {
-        P::from_probabilities(probabilities).add_dependent(&exploding_helper(
-            exploding_range,
-            exploding_condition,
-            exploding,
-        ))
+        ExplodeInto::new(exploding_condition, exploding_range, exploding)
+            .apply(&[P::from_probabilities(probabilities)])
     }
 
     /// Initializes a new `P` from given range and explodes on given condition.
@@ -48,15 +69,11 @@ pub trait ExplodingInitializer<V, P> {
         exploding: P,
     ) -> P
     where
-        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V> + 'static,
-        V: Copy + Ord + From<i32> + 'static,
-        i32: From<V>,
+        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+        V: Copy + Ord + From<i32> + Step,
     {
-        P::from_range(start, end).add_dependent(&exploding_helper(
-            exploding_range,
-            exploding_condition,
-            exploding,
-        ))
+        ExplodeInto::new(exploding_condition, exploding_range, exploding)
+            .apply(&[P::from_range(start, end)])
     }
 
     /// Initializes a new `P` from given values and explodes on given condition.
@@ -69,15 +86,11 @@ pub trait ExplodingInitializer<V, P> {
         exploding: P,
     ) -> P
     where
-        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V> + 'static,
-        V: Copy + Ord + From<i32> + 'static,
-        i32: From<V>,
+        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+        V: Copy + Ord + From<i32> + Step,
     {
-        P::from_values(values).add_dependent(&exploding_helper(
-            exploding_range,
-            exploding_condition,
-            exploding,
-        ))
+        ExplodeInto::new(exploding_condition, exploding_range, exploding)
+            .apply(&[P::from_values(values)])
     }
 
     /// Initializes a new `P` and explodes on given condition.
@@ -90,54 +103,56 @@ pub trait ExplodingInitializer<V, P> {
         exploding: P,
     ) -> P
     where
-        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V> + 'static,
-        V: Copy + Ord + From<i32> + 'static,
-        i32: From<V>,
+        P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+        V: Copy + Ord + From<i32> + Step,
     {
-        P::new(amount).add_dependent(&exploding_helper(
-            exploding_range,
-            exploding_condition,
-            exploding,
-        ))
+        ExplodeInto::new(exploding_condition, exploding_range, exploding).apply(&[P::new(amount)])
     }
 }
 
 impl<V, P> ExplodingInitializer<V, P> for P
 where
-    P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V> + 'static,
-    V: Copy + Ord + From<i32> + 'static,
-    i32: From<V>,
+    P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+    V: Copy + Ord + From<i32> + Step,
 {
 }
 
-fn exploding_helper<V, P>(
+pub(crate) fn exploding_helper<V, P>(
     exploding_range: V,
     exploding_condition: ExplodingCondition,
     exploding: P,
-) -> Box<dyn Fn(&V) -> P>
+) -> impl Fn(&V) -> P
 where
-    P: Clone + NormalInitializer<V, P> + 'static,
-    V: Copy + Ord + From<i32> + 'static,
+    P: Clone + NormalInitializer<V, P>,
+    V: Copy + Ord + From<i32>,
 {
-    Box::new(move |&prob: &_| {
-        if match exploding_condition {
-            ExplodingCondition::Lower => prob < exploding_range,
-            ExplodingCondition::LowerOrEqual => prob <= exploding_range,
-            ExplodingCondition::Equal => prob == exploding_range,
-            ExplodingCondition::GreaterOrEqual => prob >= exploding_range,
-            ExplodingCondition::Greater => prob > exploding_range,
-        } {
+    move |&prob: &V| {
+        if exploding_condition.matches(prob, exploding_range) {
             exploding.clone()
         } else {
             P::empty()
         }
-    })
+    }
+}
+
+impl ExplodingCondition {
+    /// Checks whether `value` lights the fuse for the given `exploding_range`.
+    pub(crate) fn matches<V: Ord>(&self, value: V, exploding_range: V) -> bool {
+        match self {
+            ExplodingCondition::Lower => value < exploding_range,
+            ExplodingCondition::LowerOrEqual => value <= exploding_range,
+            ExplodingCondition::Equal => value == exploding_range,
+            ExplodingCondition::GreaterOrEqual => value >= exploding_range,
+            ExplodingCondition::Greater => value > exploding_range,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Die;
+    use crate::HasProbabilities;
+    use std::borrow::Cow;
 
     #[test]
     fn exploding_condition_equality() {
@@ -183,12 +198,7 @@ mod tests {
             expected_probabilities
         );
         assert_eq!(
-            Die::exploding_from_values(
-                &vec![1, 2],
-                1,
-                ExplodingCondition::LowerOrEqual,
-                Die::new(2)
-            ),
+            Die::exploding_from_values(&[1, 2], 1, ExplodingCondition::LowerOrEqual, Die::new(2)),
             expected_probabilities
         );
         assert_eq!(
@@ -214,4 +224,36 @@ mod tests {
             expected_probabilities
         );
     }
+
+    #[derive(Debug, Clone)]
+    struct Borrowed<'a>(Cow<'a, [Probability<i32>]>);
+
+    impl HasProbabilities<i32> for Borrowed<'_> {
+        fn probabilities(&self) -> &[Probability<i32>] {
+            &self.0
+        }
+
+        fn from_sorted_probabilities(probabilities: Vec<Probability<i32>>) -> Self {
+            Borrowed(Cow::Owned(probabilities))
+        }
+    }
+
+    impl NormalInitializer<i32, Self> for Borrowed<'_> {
+        fn from_probabilities(probabilities: Vec<Probability<i32>>) -> Self {
+            Borrowed(Cow::Owned(
+                Die::from_probabilities(probabilities)
+                    .get_probabilities()
+                    .to_vec(),
+            ))
+        }
+    }
+
+    #[test]
+    fn exploding_borrowed() {
+        let d2 = Die::new(2);
+        let exploding = Borrowed(Cow::Borrowed(d2.get_probabilities()));
+        let exploded = Borrowed::new_exploding(2, 2, ExplodingCondition::Equal, exploding);
+        assert_eq!(exploded.get_max(), 4);
+        assert_eq!(exploded.get_min(), 1);
+    }
 }
@@ -0,0 +1,1047 @@
+use crate::{
+    CountSuccesses, Die, DropDice, DropType, ExplodeDice, ExplodingCondition, KeepDice, Mechanic,
+    NormalInitializer, ProbabilityDistribution, RerollDice,
+};
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::sync::Arc;
+
+/// Errors that can occur while evaluating an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpressionError {
+    /// A character that is not part of the expression syntax.
+    UnexpectedCharacter(char),
+    /// A token that is not allowed at its position.
+    UnexpectedToken(String),
+    /// The expression ended while more input was expected.
+    UnexpectedEnd,
+    /// A variable was used before it was bound.
+    UnknownVariable(String),
+    /// A number could not be represented.
+    InvalidNumber(String),
+    /// A dice modifier that applies to every side of its die, like exploding a `d1` without a
+    /// limit or rerolling a `d6` until it shows more than `6`.
+    ImpossibleModifier(String),
+    /// More dice in a single dice term than an evaluator rolls.
+    TooManyDice(usize),
+    /// More sides on a single die than an evaluator rolls.
+    TooManySides(i32),
+}
+
+impl std::fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpressionError::UnexpectedCharacter(character) => {
+                write!(f, "unexpected character `{character}`")
+            }
+            ExpressionError::UnexpectedToken(token) => write!(f, "unexpected token `{token}`"),
+            ExpressionError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ExpressionError::UnknownVariable(name) => write!(f, "unknown variable `{name}`"),
+            ExpressionError::InvalidNumber(number) => write!(f, "invalid number `{number}`"),
+            ExpressionError::ImpossibleModifier(modifier) => {
+                write!(f, "modifier `{modifier}` applies to every side of the die")
+            }
+            ExpressionError::TooManyDice(amount) => {
+                write!(
+                    f,
+                    "too many dice `{amount}`, at most {MAX_DICE} are allowed"
+                )
+            }
+            ExpressionError::TooManySides(sides) => {
+                write!(
+                    f,
+                    "too many sides `{sides}`, at most {MAX_SIDES} are allowed"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+/// Flavour of the dice modifiers understood by an [`Evaluator`], following the virtual tabletops
+/// they originate from.
+///
+/// Both dialects support keeping and dropping via `kh`, `kl`, `dh` and `dl`, exploding via `!`
+/// and compounding via `!!`, but differ in the details:
+///
+/// | Modifier   | Roll20                          | Foundry                        |
+/// |------------|---------------------------------|--------------------------------|
+/// | `r<2`      | rerolls until the condition fails | rerolls once                 |
+/// | `ro<2`     | rerolls once                    | -                              |
+/// | `rr<2`     | -                               | rerolls until the condition fails |
+/// | `x`, `xo`  | -                               | explodes, explodes once        |
+/// | `cs>19`    | ignored, only marks criticals   | counts successes               |
+/// | `cf<2`     | ignored, only marks fumbles     | -                              |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// Modifiers as used by Roll20.
+    #[default]
+    Roll20,
+    /// Modifiers as used by Foundry VTT.
+    Foundry,
+}
+
+/// Evaluates dice expressions like `2d6 + d8 - 1` into a [`Die`].
+///
+/// Supports the dice notation `NdM` and `dM`, flat numbers, `+`, `-`, parentheses and the
+/// comparisons `>=`, `<=`, `>`, `<`, `==` and `!=`, which result in `1` if the comparison holds
+/// and `0` otherwise.
+///
+/// Dice can be followed by the modifiers of the selected [`Dialect`], like `4d6kh3` or
+/// `2d20kl1`. Rerolls and explosions are applied to every die first, then keeping, dropping or
+/// counting successes is applied to the whole pool. Exploded dice count as part of the die that
+/// exploded, meaning `!` behaves like `!!` when combined with keeping or dropping, and
+/// explosions stop after a depth of ten, unless a [tolerance][`Evaluator::set_tolerance`] is set.
+///
+/// A single dice term rolls at most `1000` dice.
+///
+/// Additional suffixes can be registered via [`register_suffix`][`Evaluator::register_suffix`].
+///
+/// Statements of the form `let NAME = expression;` bind the result to a variable, which can be
+/// used by every following expression. Variable names have to start with an uppercase letter or
+/// an underscore. Bindings are kept by the evaluator across calls to
+/// [`evaluate`][`Evaluator::evaluate`], but only if the whole input evaluated successfully.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, Evaluator, NormalInitializer, ProbabilityDistribution };
+/// let mut evaluator = Evaluator::new();
+/// let hit = evaluator.evaluate("let ATTACK = d20 + 5; ATTACK >= 16").unwrap();
+/// assert!((hit.get_mean() - 0.5).abs() < 1e-9);
+///
+/// let damage = evaluator.evaluate("(ATTACK >= 16) + 2d6").unwrap();
+/// assert_eq!(damage.get_max(), 13);
+/// ```
+#[derive(Clone, Default)]
+pub struct Evaluator {
+    variables: HashMap<String, Die>,
+    dialect: Dialect,
+    suffixes: HashMap<String, SuffixFn>,
+    tolerance: Option<f64>,
+}
+
+/// Function evaluating a custom dice suffix, see [`Evaluator::register_suffix`].
+type SuffixFn = Arc<dyn Fn(&[Die], Option<i32>) -> Die + Send + Sync>;
+
+impl std::fmt::Debug for Evaluator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Evaluator")
+            .field("variables", &self.variables)
+            .field("dialect", &self.dialect)
+            .field("suffixes", &self.suffixes.keys().collect::<Vec<_>>())
+            .field("tolerance", &self.tolerance)
+            .finish()
+    }
+}
+
+impl Evaluator {
+    /// Creates a new evaluator without any bound variables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new evaluator without any bound variables, using the given [`Dialect`] for dice
+    /// modifiers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Dialect, Evaluator, ProbabilityDistribution };
+    /// let mut roll20 = Evaluator::with_dialect(Dialect::Roll20);
+    /// let mut foundry = Evaluator::with_dialect(Dialect::Foundry);
+    /// assert_eq!(roll20.evaluate("d20cs>19").unwrap().get_max(), 20);
+    /// assert_eq!(foundry.evaluate("d20cs>19").unwrap().get_max(), 1);
+    /// ```
+    pub fn with_dialect(dialect: Dialect) -> Self {
+        Evaluator {
+            dialect,
+            ..Self::default()
+        }
+    }
+
+    /// Registers a custom suffix for dice terms, like `h` in `3d6h` or `3d6h2`.
+    ///
+    /// Whenever a dice term ends with the suffix, `suffix_fn` is called with the pool of dice,
+    /// after rerolls and explosions were applied, and the number following the suffix, if any.
+    /// Custom suffixes take precedence over the built-in modifiers and replace keeping, dropping
+    /// and counting successes, so only one of them can be used per dice term.
+    ///
+    /// # Panics
+    /// Panics if `suffix` is empty or contains anything but ASCII letters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, DropInitializer, DropType, Evaluator, NormalInitializer };
+    /// let mut evaluator = Evaluator::new();
+    /// // keeps the highest die, or the given amount of highest dice
+    /// evaluator.register_suffix("best", |pool, amount| {
+    ///     Die::keep_from_pool(pool, amount.unwrap_or(1) as usize, DropType::High)
+    /// });
+    /// assert_eq!(evaluator.evaluate("2d20best"), evaluator.evaluate("2d20kh1"));
+    /// assert_eq!(evaluator.evaluate("4d6best3"), evaluator.evaluate("4d6kh3"));
+    /// ```
+    pub fn register_suffix<F>(&mut self, suffix: &str, suffix_fn: F)
+    where
+        F: Fn(&[Die], Option<i32>) -> Die + Send + Sync + 'static,
+    {
+        assert!(
+            !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_alphabetic()),
+            "suffix has to consist of ASCII letters"
+        );
+        self.suffixes
+            .insert(suffix.to_string(), Arc::new(suffix_fn));
+    }
+
+    /// Lets explosions continue until the chance to explode again drops below `tolerance`,
+    /// instead of stopping after a fixed depth, see [`ExplodeDice::unbounded`]. Applies to both
+    /// exploding (`!`, `x`) and compounding (`!!`) dice.
+    ///
+    /// Evaluating a die that explodes on every side, like `d1!`, fails with
+    /// [`ExpressionError::ImpossibleModifier`] once a tolerance is set.
+    ///
+    /// # Panics
+    /// Panics if `tolerance` is not a positive, finite number.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Evaluator, ProbabilityDistribution };
+    /// let mut evaluator = Evaluator::new();
+    /// evaluator.set_tolerance(1e-9);
+    /// let exploding = evaluator.evaluate("d6!").unwrap();
+    /// assert!((exploding.get_mean() - 4.2).abs() < 1e-6);
+    /// assert!(exploding.truncated_mass() < 1e-9);
+    /// ```
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        assert!(
+            tolerance > 0.0 && tolerance.is_finite(),
+            "tolerance has to be positive and finite"
+        );
+        self.tolerance = Some(tolerance);
+    }
+
+    /// Binds the given die to a variable, replacing any previous binding.
+    pub fn set_variable(&mut self, name: &str, die: Die) {
+        self.variables.insert(name.to_string(), die);
+    }
+
+    /// Returns the die bound to the given variable, if any.
+    pub fn get_variable(&self, name: &str) -> Option<&Die> {
+        self.variables.get(name)
+    }
+
+    /// Evaluates the given input, binding all `let` statements and returning the result of the
+    /// final expression. If evaluating fails, none of the `let` statements are bound.
+    pub fn evaluate(&mut self, input: &str) -> Result<Die, ExpressionError> {
+        let tokens = tokenize(input, self)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            evaluator: self,
+            bindings: HashMap::new(),
+        };
+        let result = parser.program()?;
+        let bindings = parser.bindings;
+        self.variables.extend(bindings);
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i32),
+    Dice,
+    Identifier(String),
+    Let,
+    Plus,
+    Minus,
+    Assign,
+    Semicolon,
+    OpenParen,
+    CloseParen,
+    Comparison(Comparison),
+    Modifier(DiceModifier),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Dice => write!(f, "d"),
+            Token::Identifier(name) => write!(f, "{name}"),
+            Token::Let => write!(f, "let"),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Assign => write!(f, "="),
+            Token::Semicolon => write!(f, ";"),
+            Token::OpenParen => write!(f, "("),
+            Token::CloseParen => write!(f, ")"),
+            Token::Comparison(comparison) => write!(f, "{}", comparison.symbol()),
+            Token::Modifier(modifier) => write!(f, "{modifier}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Lower,
+    LowerOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Lower => "<",
+            Comparison::LowerOrEqual => "<=",
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+        }
+    }
+
+    fn holds(&self, difference: i32) -> bool {
+        match self {
+            Comparison::Greater => difference > 0,
+            Comparison::GreaterOrEqual => difference >= 0,
+            Comparison::Lower => difference < 0,
+            Comparison::LowerOrEqual => difference <= 0,
+            Comparison::Equal => difference == 0,
+            Comparison::NotEqual => difference != 0,
+        }
+    }
+}
+
+/// Modifier directly following a dice term.
+#[derive(Debug, Clone, PartialEq)]
+enum DiceModifier {
+    Keep {
+        highest: bool,
+        amount: usize,
+    },
+    Drop {
+        highest: bool,
+        amount: usize,
+    },
+    Reroll {
+        comparison: Comparison,
+        target: i32,
+        once: bool,
+    },
+    Explode {
+        once: bool,
+    },
+    CountSuccesses {
+        comparison: Comparison,
+        target: i32,
+    },
+    Ignored,
+    Custom {
+        suffix: String,
+        argument: Option<i32>,
+    },
+}
+
+impl DiceModifier {
+    fn is_pool_modifier(&self) -> bool {
+        matches!(
+            self,
+            DiceModifier::Keep { .. }
+                | DiceModifier::Drop { .. }
+                | DiceModifier::CountSuccesses { .. }
+                | DiceModifier::Custom { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for DiceModifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let side = |highest: bool| if highest { "h" } else { "l" };
+        match self {
+            DiceModifier::Keep { highest, amount } => write!(f, "k{}{amount}", side(*highest)),
+            DiceModifier::Drop { highest, amount } => write!(f, "d{}{amount}", side(*highest)),
+            DiceModifier::Reroll {
+                comparison,
+                target,
+                once,
+            } => write!(
+                f,
+                "r{}{}{target}",
+                if *once { "o" } else { "r" },
+                comparison.symbol()
+            ),
+            DiceModifier::Explode { once } => write!(f, "x{}", if *once { "o" } else { "" }),
+            DiceModifier::CountSuccesses { comparison, target } => {
+                write!(f, "cs{}{target}", comparison.symbol())
+            }
+            DiceModifier::Ignored => Ok(()),
+            DiceModifier::Custom {
+                suffix,
+                argument: Some(argument),
+            } => write!(f, "{suffix}{argument}"),
+            DiceModifier::Custom {
+                suffix,
+                argument: None,
+            } => write!(f, "{suffix}"),
+        }
+    }
+}
+
+/// Maximum amount of times a die explodes in a row.
+const EXPLOSION_DEPTH: usize = 10;
+
+/// Maximum amount of dice rolled by a single dice term.
+const MAX_DICE: usize = 1000;
+
+/// Maximum amount of sides of a single die.
+const MAX_SIDES: i32 = 10_000;
+
+fn tokenize(input: &str, evaluator: &Evaluator) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&character) = chars.peek() {
+        match character {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            c if c.is_ascii_digit() => tokens.push(number(&mut chars)?),
+            'd' if second_is_digit(&chars) => {
+                chars.next();
+                tokens.push(Token::Dice);
+                tokens.push(number(&mut chars)?);
+                while let Some(modifier) = dice_modifier(&mut chars, evaluator)? {
+                    tokens.push(Token::Modifier(modifier));
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        word.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                match word.as_str() {
+                    "let" => tokens.push(Token::Let),
+                    "d" => tokens.push(Token::Dice),
+                    _ if word.starts_with(|c: char| c.is_uppercase() || c == '_') => {
+                        tokens.push(Token::Identifier(word))
+                    }
+                    _ => return Err(ExpressionError::UnexpectedToken(word)),
+                }
+            }
+            _ => {
+                chars.next();
+                let followed_by_equals = chars.peek() == Some(&'=');
+                let token = match (character, followed_by_equals) {
+                    ('+', _) => Token::Plus,
+                    ('-', _) => Token::Minus,
+                    (';', _) => Token::Semicolon,
+                    ('(', _) => Token::OpenParen,
+                    (')', _) => Token::CloseParen,
+                    ('>', true) => Token::Comparison(Comparison::GreaterOrEqual),
+                    ('>', false) => Token::Comparison(Comparison::Greater),
+                    ('<', true) => Token::Comparison(Comparison::LowerOrEqual),
+                    ('<', false) => Token::Comparison(Comparison::Lower),
+                    ('=', true) => Token::Comparison(Comparison::Equal),
+                    ('=', false) => Token::Assign,
+                    ('!', true) => Token::Comparison(Comparison::NotEqual),
+                    _ => return Err(ExpressionError::UnexpectedCharacter(character)),
+                };
+                if followed_by_equals && matches!(token, Token::Comparison(_)) {
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn second_is_digit(chars: &Peekable<Chars>) -> bool {
+    let mut lookahead = chars.clone();
+    lookahead.next();
+    lookahead.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Parses the next modifier attached to a dice term, if there is one.
+fn dice_modifier(
+    chars: &mut Peekable<Chars>,
+    evaluator: &Evaluator,
+) -> Result<Option<DiceModifier>, ExpressionError> {
+    let Some(&first) = chars.peek() else {
+        return Ok(None);
+    };
+    if let Some(suffix) = evaluator
+        .suffixes
+        .keys()
+        .filter(|suffix| {
+            let mut lookahead = chars.clone();
+            suffix.chars().all(|c| lookahead.next() == Some(c))
+        })
+        .max_by_key(|suffix| suffix.len())
+    {
+        for _ in 0..suffix.len() {
+            chars.next();
+        }
+        let argument = if chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+            match number(chars)? {
+                Token::Number(argument) => Some(argument),
+                token => return Err(ExpressionError::UnexpectedToken(token.to_string())),
+            }
+        } else {
+            None
+        };
+        return Ok(Some(DiceModifier::Custom {
+            suffix: suffix.clone(),
+            argument,
+        }));
+    }
+    let dialect = evaluator.dialect;
+    let mut lookahead = chars.clone();
+    lookahead.next();
+    let second = lookahead.next();
+    let modifier = match (first, second, dialect) {
+        ('k', Some(side @ ('h' | 'l')), _) | ('d', Some(side @ ('h' | 'l')), _) => {
+            chars.next();
+            chars.next();
+            let amount = optional_amount(chars)?;
+            if first == 'k' {
+                DiceModifier::Keep {
+                    highest: side == 'h',
+                    amount,
+                }
+            } else {
+                DiceModifier::Drop {
+                    highest: side == 'h',
+                    amount,
+                }
+            }
+        }
+        ('k', _, _) => {
+            chars.next();
+            DiceModifier::Keep {
+                highest: true,
+                amount: optional_amount(chars)?,
+            }
+        }
+        ('r', _, _) => {
+            chars.next();
+            let once = match (chars.peek(), dialect) {
+                (Some('o'), Dialect::Roll20) => {
+                    chars.next();
+                    true
+                }
+                (Some('r'), Dialect::Foundry) => {
+                    chars.next();
+                    false
+                }
+                (_, Dialect::Roll20) => false,
+                (_, Dialect::Foundry) => true,
+            };
+            let (comparison, target) = modifier_target(chars)?;
+            DiceModifier::Reroll {
+                comparison,
+                target,
+                once,
+            }
+        }
+        ('!', _, _) => {
+            chars.next();
+            if chars.peek() == Some(&'!') {
+                chars.next();
+            }
+            DiceModifier::Explode { once: false }
+        }
+        ('x', _, Dialect::Foundry) => {
+            chars.next();
+            let once = chars.peek() == Some(&'o');
+            if once {
+                chars.next();
+            }
+            DiceModifier::Explode { once }
+        }
+        ('c', Some('s'), _) | ('c', Some('f'), Dialect::Roll20) => {
+            chars.next();
+            chars.next();
+            let (comparison, target) = modifier_target(chars)?;
+            match dialect {
+                Dialect::Roll20 => DiceModifier::Ignored,
+                Dialect::Foundry => DiceModifier::CountSuccesses { comparison, target },
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(modifier))
+}
+
+/// Parses an optional amount, defaulting to `1`.
+fn optional_amount(chars: &mut Peekable<Chars>) -> Result<usize, ExpressionError> {
+    if chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+        match number(chars)? {
+            Token::Number(amount) => Ok(amount as usize),
+            token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        }
+    } else {
+        Ok(1)
+    }
+}
+
+/// Parses a target like `<2` or `>=19`, where a plain number means equality.
+fn modifier_target(chars: &mut Peekable<Chars>) -> Result<(Comparison, i32), ExpressionError> {
+    let comparison = match chars.peek() {
+        Some('<') | Some('>') | Some('=') => {
+            let symbol = chars.next();
+            let or_equal = chars.peek() == Some(&'=') && symbol != Some('=');
+            if or_equal {
+                chars.next();
+            }
+            match (symbol, or_equal) {
+                (Some('<'), true) => Comparison::LowerOrEqual,
+                (Some('<'), false) => Comparison::Lower,
+                (Some('>'), true) => Comparison::GreaterOrEqual,
+                (Some('>'), false) => Comparison::Greater,
+                _ => Comparison::Equal,
+            }
+        }
+        _ => Comparison::Equal,
+    };
+    match chars.peek() {
+        Some(c) if c.is_ascii_digit() => match number(chars)? {
+            Token::Number(target) => Ok((comparison, target)),
+            token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        },
+        Some(&c) => Err(ExpressionError::UnexpectedCharacter(c)),
+        None => Err(ExpressionError::UnexpectedEnd),
+    }
+}
+
+fn number(chars: &mut Peekable<Chars>) -> Result<Token, ExpressionError> {
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    digits
+        .parse()
+        .map(Token::Number)
+        .map_err(|_| ExpressionError::InvalidNumber(digits))
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    evaluator: &'a Evaluator,
+    /// Variables bound by the input so far, only kept by the evaluator once everything succeeded.
+    bindings: HashMap<String, Die>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(ExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExpressionError> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    fn program(&mut self) -> Result<Die, ExpressionError> {
+        while self.peek() == Some(&Token::Let) {
+            self.position += 1;
+            let name = match self.next()? {
+                Token::Identifier(name) => name,
+                token => return Err(ExpressionError::UnexpectedToken(token.to_string())),
+            };
+            self.expect(Token::Assign)?;
+            let die = self.comparison()?;
+            self.expect(Token::Semicolon)?;
+            self.bindings.insert(name, die);
+        }
+        let result = self.comparison()?;
+        match self.peek() {
+            Some(token) => Err(ExpressionError::UnexpectedToken(token.to_string())),
+            None => Ok(result),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Die, ExpressionError> {
+        let left = self.additive()?;
+        if let Some(&Token::Comparison(comparison)) = self.peek() {
+            self.position += 1;
+            let right = self.additive()?;
+            Ok(left
+                .add_independent(&negate(&right))
+                .conditional_chain(&mut |&difference| {
+                    Die::from(comparison.holds(difference) as i32)
+                }))
+        } else {
+            Ok(left)
+        }
+    }
+
+    fn additive(&mut self) -> Result<Die, ExpressionError> {
+        let mut result = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.position += 1;
+                    result = &result + &self.unary()?;
+                }
+                Some(Token::Minus) => {
+                    self.position += 1;
+                    result = &result + &negate(&self.unary()?);
+                }
+                _ => return Ok(result),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Die, ExpressionError> {
+        if self.peek() == Some(&Token::Minus) {
+            self.position += 1;
+            Ok(negate(&self.unary()?))
+        } else {
+            self.atom()
+        }
+    }
+
+    fn atom(&mut self) -> Result<Die, ExpressionError> {
+        match self.next()? {
+            Token::Number(amount) => {
+                if self.peek() == Some(&Token::Dice) {
+                    self.position += 1;
+                    let amount = amount as usize;
+                    if amount > MAX_DICE {
+                        return Err(ExpressionError::TooManyDice(amount));
+                    }
+                    self.dice(amount)
+                } else {
+                    Ok(Die::from(amount))
+                }
+            }
+            Token::Dice => self.dice(1),
+            Token::Identifier(name) => self
+                .bindings
+                .get(&name)
+                .or_else(|| self.evaluator.get_variable(&name))
+                .cloned()
+                .ok_or(ExpressionError::UnknownVariable(name)),
+            Token::OpenParen => {
+                let result = self.comparison()?;
+                self.expect(Token::CloseParen)?;
+                Ok(result)
+            }
+            token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    /// Rolls `amount` dice with the following sides and modifiers.
+    fn dice(&mut self, amount: usize) -> Result<Die, ExpressionError> {
+        let sides = self.sides()?;
+        let mut die = Die::new(sides);
+        let mut pool_modifier = None;
+        while let Some(Token::Modifier(modifier)) = self.peek().cloned() {
+            self.position += 1;
+            if modifier.is_pool_modifier() {
+                if pool_modifier.is_some() {
+                    return Err(ExpressionError::UnexpectedToken(modifier.to_string()));
+                }
+                pool_modifier = Some(modifier);
+                continue;
+            }
+            die = match modifier {
+                DiceModifier::Reroll {
+                    comparison,
+                    target,
+                    once,
+                } => {
+                    let should_reroll = |&value: &i32| comparison.holds(value - target);
+                    if !once
+                        && die
+                            .get_probabilities()
+                            .iter()
+                            .all(|prob| should_reroll(&prob.value))
+                    {
+                        return Err(ExpressionError::ImpossibleModifier(modifier.to_string()));
+                    }
+                    RerollDice::new(should_reroll, once).apply(&[die])
+                }
+                DiceModifier::Explode { once } => match self.evaluator.tolerance {
+                    Some(tolerance) if !once => {
+                        if die
+                            .get_probabilities()
+                            .iter()
+                            .all(|prob| ExplodingCondition::Equal.matches(prob.value, sides))
+                        {
+                            return Err(ExpressionError::ImpossibleModifier(modifier.to_string()));
+                        }
+                        ExplodeDice::unbounded(ExplodingCondition::Equal, sides, tolerance)
+                    }
+                    _ => ExplodeDice::new(
+                        ExplodingCondition::Equal,
+                        sides,
+                        if once { 1 } else { EXPLOSION_DEPTH },
+                    ),
+                }
+                .apply(&[die]),
+                _ => die,
+            };
+        }
+        let pool = vec![die; amount];
+        Ok(match pool_modifier {
+            Some(DiceModifier::Keep { highest, amount }) => {
+                KeepDice::new(amount, drop_type(highest)).apply(&pool)
+            }
+            Some(DiceModifier::Drop { highest, amount }) => {
+                DropDice::new(amount, drop_type(highest)).apply(&pool)
+            }
+            Some(DiceModifier::CountSuccesses { comparison, target }) => {
+                CountSuccesses::new(|&value| comparison.holds(value - target)).apply(&pool)
+            }
+            Some(DiceModifier::Custom { suffix, argument }) => {
+                (self.evaluator.suffixes[&suffix])(&pool, argument)
+            }
+            _ => Die::sum_all(&pool),
+        })
+    }
+
+    fn sides(&mut self) -> Result<i32, ExpressionError> {
+        match self.next()? {
+            Token::Number(sides) if sides > MAX_SIDES => Err(ExpressionError::TooManySides(sides)),
+            Token::Number(sides) => Ok(sides),
+            token => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        }
+    }
+}
+
+fn drop_type(highest: bool) -> DropType {
+    if highest {
+        DropType::High
+    } else {
+        DropType::Low
+    }
+}
+
+fn negate(die: &Die) -> Die {
+    -die
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ALLOWED_ERROR;
+
+    #[test]
+    fn arithmetic() {
+        let mut evaluator = Evaluator::new();
+        assert_eq!(
+            evaluator.evaluate("2d6 + d8 - 1"),
+            Ok(&(&(&Die::new(6) + &Die::new(6)) + &Die::new(8)) + -1)
+        );
+        assert_eq!(evaluator.evaluate("-(3)"), Ok(Die::from(-3)));
+        assert_eq!(evaluator.evaluate("d4 - d4").unwrap().get_min(), -3);
+    }
+
+    #[test]
+    fn comparisons() {
+        let mut evaluator = Evaluator::new();
+        let at_least = evaluator.evaluate("d6 >= 5").unwrap();
+        assert!((at_least.get_mean() - 1.0 / 3.0).abs() < ALLOWED_ERROR);
+        let equal = evaluator.evaluate("d6 == d6").unwrap();
+        assert!((equal.get_mean() - 1.0 / 6.0).abs() < ALLOWED_ERROR);
+        let not_equal = evaluator.evaluate("d6 != d6").unwrap();
+        assert!((not_equal.get_mean() - 5.0 / 6.0).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    fn variables() {
+        let mut evaluator = Evaluator::new();
+        let hit = evaluator
+            .evaluate("let ATTACK = d20 + 5; let DC = 16; ATTACK >= DC")
+            .unwrap();
+        assert!((hit.get_mean() - 0.5).abs() < ALLOWED_ERROR);
+        assert_eq!(evaluator.get_variable("DC"), Some(&Die::from(16)));
+        assert_eq!(evaluator.evaluate("ATTACK"), Ok(Die::new(20).add_flat(5)));
+        evaluator.set_variable("_BONUS", Die::new(4));
+        assert_eq!(evaluator.evaluate("_BONUS").unwrap().get_max(), 4);
+
+        assert_eq!(
+            evaluator.evaluate("let DC = 10; let DAMAGE = 2d6; DC +"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(evaluator.get_variable("DC"), Some(&Die::from(16)));
+        assert_eq!(evaluator.get_variable("DAMAGE"), None);
+    }
+
+    #[test]
+    fn roll20() {
+        let mut evaluator = Evaluator::new();
+        let advantage = evaluator.evaluate("2d20kh1").unwrap();
+        assert!((advantage.get_mean() - 13.825).abs() < ALLOWED_ERROR);
+        assert_eq!(evaluator.evaluate("2d20k"), Ok(advantage));
+        let disadvantage = evaluator.evaluate("2d20dh1").unwrap();
+        assert_eq!(evaluator.evaluate("2d20kl1"), Ok(disadvantage.clone()));
+        assert!((disadvantage.get_mean() - 7.175).abs() < ALLOWED_ERROR);
+        assert_eq!(evaluator.evaluate("4d6dl").unwrap().get_min(), 3);
+
+        let rerolled = evaluator.evaluate("d6r<3").unwrap();
+        assert_eq!(rerolled, Die::from_values(&[3, 4, 5, 6]));
+        assert!((rerolled.get_mean() - 4.5).abs() < ALLOWED_ERROR);
+        let rerolled_once = evaluator.evaluate("d4ro1").unwrap();
+        assert!((rerolled_once.get_mean() - 2.875).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            evaluator.evaluate("d6r<7"),
+            Err(ExpressionError::ImpossibleModifier("rr<7".to_string()))
+        );
+        assert_eq!(evaluator.evaluate("d6ro<7"), Ok(Die::new(6)));
+
+        let exploding = evaluator.evaluate("d6!").unwrap();
+        assert!((exploding.get_mean() - 4.2).abs() < 1e-6);
+        assert_eq!(evaluator.evaluate("d6!!"), Ok(exploding));
+        assert_eq!(evaluator.evaluate("d20cs>19cf<2"), Ok(Die::new(20)));
+        assert_eq!(
+            evaluator.evaluate("d6x"),
+            Err(ExpressionError::UnexpectedToken("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn foundry() {
+        let mut evaluator = Evaluator::with_dialect(Dialect::Foundry);
+        let rerolled_once = evaluator.evaluate("d4r1").unwrap();
+        assert!((rerolled_once.get_mean() - 2.875).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            evaluator.evaluate("d6rr<3"),
+            Ok(Die::from_values(&[3, 4, 5, 6]))
+        );
+        let exploding_once = evaluator.evaluate("d6xo").unwrap();
+        assert_eq!(exploding_once.get_max(), 12);
+        assert!((exploding_once.get_mean() - 3.5 * 7.0 / 6.0).abs() < ALLOWED_ERROR);
+        let successes = evaluator.evaluate("4d6cs>=5").unwrap();
+        assert_eq!(successes, Die::binomial(4, 1.0 / 3.0));
+        assert!((successes.get_mean() - 4.0 / 3.0).abs() < ALLOWED_ERROR);
+        assert_eq!(
+            evaluator.evaluate("4d6kh3cs>4"),
+            Err(ExpressionError::UnexpectedToken("cs>4".to_string()))
+        );
+        assert_eq!(
+            evaluator.evaluate("d6r<"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn suffixes() {
+        let mut evaluator = Evaluator::new();
+        // counts every die showing its highest side
+        evaluator.register_suffix("h", |pool, _| {
+            Die::sum_all(
+                &pool
+                    .iter()
+                    .map(|die| {
+                        let max = die.get_max();
+                        die.conditional_chain(&mut |&value| Die::from((value == max) as i32))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        });
+        evaluator.register_suffix("hx", |pool, argument| {
+            Die::from(pool.len() as i32 * argument.unwrap_or(0))
+        });
+        assert_eq!(evaluator.evaluate("3d6h"), Ok(Die::binomial(3, 1.0 / 6.0)));
+        assert_eq!(evaluator.evaluate("3d6hx2 + 1"), Ok(Die::from(7)));
+        assert_eq!(
+            evaluator.evaluate("3d6hkh1"),
+            Err(ExpressionError::UnexpectedToken("kh1".to_string()))
+        );
+        assert!(format!("{evaluator:?}").contains("suffixes"));
+    }
+
+    #[test]
+    fn tolerance() {
+        let mut evaluator = Evaluator::new();
+        evaluator.set_tolerance(1e-9);
+        let exploding = evaluator.evaluate("d6!").unwrap();
+        let compounding = evaluator.evaluate("d6!!").unwrap();
+        assert!((compounding.get_mean() - exploding.get_mean()).abs() < ALLOWED_ERROR);
+        assert!(compounding.truncated_mass() > 0.0);
+        assert!(compounding.truncated_mass() < 1e-9);
+        assert_eq!(
+            evaluator.evaluate("d1!"),
+            Err(ExpressionError::ImpossibleModifier("x".to_string()))
+        );
+        assert_eq!(
+            evaluator.evaluate("d1!").unwrap_err().to_string(),
+            "modifier `x` applies to every side of the die"
+        );
+        assert_eq!(Evaluator::new().evaluate("d1!").unwrap().get_max(), 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "tolerance has to be positive and finite")]
+    fn invalid_tolerance() {
+        Evaluator::new().set_tolerance(0.0);
+    }
+
+    #[test]
+    fn errors() {
+        let mut evaluator = Evaluator::new();
+        assert_eq!(
+            evaluator.evaluate("MISSING"),
+            Err(ExpressionError::UnknownVariable("MISSING".to_string()))
+        );
+        assert_eq!(
+            evaluator.evaluate("d6 +"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            evaluator.evaluate("d6 * 2"),
+            Err(ExpressionError::UnexpectedCharacter('*'))
+        );
+        assert_eq!(
+            evaluator.evaluate("let lower = 1; lower"),
+            Err(ExpressionError::UnexpectedToken("lower".to_string()))
+        );
+        assert_eq!(
+            evaluator.evaluate("(d6"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            evaluator.evaluate("d6 d6"),
+            Err(ExpressionError::UnexpectedToken("d".to_string()))
+        );
+        assert_eq!(
+            evaluator.evaluate("1000000000d6"),
+            Err(ExpressionError::TooManyDice(1_000_000_000))
+        );
+        assert_eq!(evaluator.evaluate("1000d2").unwrap().get_max(), 2000);
+        assert_eq!(
+            evaluator.evaluate("d2000000000"),
+            Err(ExpressionError::TooManySides(2_000_000_000))
+        );
+        assert_eq!(evaluator.evaluate("d10000").unwrap().get_max(), 10_000);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::common::*;
+use crate::Probability;
+
+/// Column widths and precision used when rendering distributions as text.
+///
+/// The [default][`FormatOptions::default()`] matches the layout of
+/// [`get_results`][`crate::ProbabilityDistribution::get_results`] and
+/// [`get_details`][`crate::ProbabilityDistribution::get_details`].
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, FormatOptions, NormalInitializer, ProbabilityDistribution };
+/// let options = FormatOptions {
+///     number_width: 2,
+///     decimals: 1,
+///     bar_length: 4,
+///     ..FormatOptions::default()
+/// };
+/// assert_eq!(
+///     Die::new(2).display_with(options).to_string(),
+///     " 1 : 50.0 : ##--\n 2 : 50.0 : ##--\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Width of the name column of the details.
+    pub name_width: usize,
+    /// Width of every number column.
+    pub number_width: usize,
+    /// Amount of decimal places of fractional numbers.
+    pub decimals: usize,
+    /// Length of the bar visualizing each chance, `0` to leave it out.
+    pub bar_length: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            name_width: 20,
+            number_width: 10,
+            decimals: 3,
+            bar_length: 50,
+        }
+    }
+}
+
+/// Adapter rendering the results of a distribution via [`Display`][`std::fmt::Display`] with
+/// the given [`FormatOptions`], as returned by
+/// [`display_with`][`crate::ProbabilityDistribution::display_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct Formatted<'a, T> {
+    probabilities: &'a [Probability<T>],
+    options: FormatOptions,
+}
+
+impl<'a, T> Formatted<'a, T> {
+    /// Prepares the given probabilities to be displayed with the given options.
+    pub fn new(probabilities: &'a [Probability<T>], options: FormatOptions) -> Self {
+        Formatted {
+            probabilities,
+            options,
+        }
+    }
+}
+
+impl<T> std::fmt::Display for Formatted<'_, T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for prob in self.probabilities {
+            writeln!(f, "{}", format_row(&prob.value, prob.chance, &self.options))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+//! Ready made analyses of specific games, built on top of the general [dice][`crate::Die`] and
+//! pool machinery. Only available with the `games` feature.
+
+pub(crate) use crate::reroll::sorted_rolls;
+
+pub mod blades;
+pub mod pbta;
+pub mod poker_dice;
+pub mod risk;
+pub mod savage_worlds;
+pub mod shadowrun;
+pub mod vampire;
+pub mod warhammer;
+pub mod yahtzee;
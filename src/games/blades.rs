@@ -0,0 +1,87 @@
+//! Action rolls of Blades in the Dark: roll a d6 per dot and take the highest one, `1` to `3`
+//! failing, `4` or `5` being a partial success, a `6` a full success and more than one `6` a
+//! critical.
+//!
+//! Without any dice, two d6 are rolled and the lowest one is taken instead, which can't ever
+//! turn out critical.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::blades::{ outcome_chances, Outcome };
+//! let chances = outcome_chances(2);
+//! assert_eq!(chances[0].0, Outcome::Failure);
+//! assert!((chances[0].1 - 0.25).abs() < 1e-12);
+//! assert!((chances[3].1 - 1.0 / 36.0).abs() < 1e-12);
+//! ```
+
+use crate::{Die, NormalInitializer, ProbabilityDistribution};
+
+/// Result of an action roll, ordered from worst to best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Outcome {
+    /// Highest die `1` to `3`.
+    Failure,
+    /// Highest die `4` or `5`.
+    Partial,
+    /// A single `6`.
+    Success,
+    /// Two or more `6`s.
+    Critical,
+}
+
+/// Returns the chance of every [`Outcome`] when rolling the given amount of dice.
+///
+/// Rolling at least one die, the highest die decides the outcome, up to the point of it being
+/// a `6`, where the amount of `6`s tells a success and a critical apart.
+pub fn outcome_chances(dice: usize) -> Vec<(Outcome, f64)> {
+    let (decisive, sixes) = if dice == 0 {
+        (Die::new(6).worst_of_n(2), Die::empty())
+    } else {
+        (
+            Die::new(6).best_of_n(dice),
+            Die::binomial(dice as u32, 1.0 / 6.0),
+        )
+    };
+    let failure = 1.0 - decisive.get_survival(3);
+    let partial = decisive.get_survival(3) - decisive.get_survival(5);
+    let critical = sixes.get_survival(1);
+    let success = 1.0 - failure - partial - critical;
+    vec![
+        (Outcome::Failure, failure),
+        (Outcome::Partial, partial),
+        (Outcome::Success, success.max(0.0)),
+        (Outcome::Critical, critical),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_rolls() {
+        let expected = |dice: i32| {
+            let none = (5.0f64 / 6.0).powi(dice);
+            let single = f64::from(dice) / 6.0 * (5.0f64 / 6.0).powi(dice - 1);
+            [
+                0.5f64.powi(dice),
+                none - 0.5f64.powi(dice),
+                single,
+                1.0 - none - single,
+            ]
+        };
+        for dice in 1..=4 {
+            for (&(_, chance), expected) in
+                outcome_chances(dice as usize).iter().zip(expected(dice))
+            {
+                assert!((chance - expected).abs() < 1e-12);
+            }
+        }
+
+        let desperate = outcome_chances(0);
+        assert!((desperate[0].1 - 0.75).abs() < 1e-12);
+        assert!((desperate[1].1 - 8.0 / 36.0).abs() < 1e-12);
+        assert!((desperate[2].1 - 1.0 / 36.0).abs() < 1e-12);
+        assert_eq!(desperate[3], (Outcome::Critical, 0.0));
+    }
+}
@@ -0,0 +1,190 @@
+//! Moves of games Powered by the Apocalypse: roll 2d6 and add a stat, `6` or less misses, `7` to
+//! `9` is a weak hit and `10` or more a strong hit. Advanced moves additionally set `12` or more
+//! apart.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::pbta::{ band_chances, Band };
+//! let chances = band_chances(1, false);
+//! assert_eq!(chances.len(), 3);
+//! assert_eq!(chances[0].0, Band::Miss);
+//! assert!((chances[0].1 - 10.0 / 36.0).abs() < 1e-12);
+//! ```
+
+use crate::{Die, FormatOptions, NormalInitializer, ProbabilityDistribution};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// Result of a move, ordered from worst to best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Band {
+    /// `6` or less.
+    Miss,
+    /// `7` to `9`.
+    WeakHit,
+    /// `10` or more, or `10` and `11` for advanced moves.
+    StrongHit,
+    /// `12` or more on advanced moves.
+    Advanced,
+}
+
+impl Band {
+    /// Returns the band the given total falls into.
+    pub fn of(total: i32, advanced: bool) -> Band {
+        match total {
+            ..=6 => Band::Miss,
+            7..=9 => Band::WeakHit,
+            12.. if advanced => Band::Advanced,
+            _ => Band::StrongHit,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Band::Miss => "6-",
+            Band::WeakHit => "7-9",
+            Band::StrongHit => "10+",
+            Band::Advanced => "12+",
+        }
+    }
+}
+
+/// Returns the chance of every [`Band`] when rolling 2d6 plus `stat`. Leaves out
+/// [`Band::Advanced`] unless it's an advanced move.
+pub fn band_chances(stat: i32, advanced: bool) -> Vec<(Band, f64)> {
+    let total = Die::new(6).repeat(2).add_flat(stat);
+    let at_least = |target: i32| total.get_survival(target - 1);
+    let mut chances = vec![
+        (Band::Miss, 1.0 - at_least(7)),
+        (Band::WeakHit, at_least(7) - at_least(10)),
+    ];
+    if advanced {
+        chances.push((Band::StrongHit, at_least(10) - at_least(12)));
+        chances.push((Band::Advanced, at_least(12)));
+    } else {
+        chances.push((Band::StrongHit, at_least(10)));
+    }
+    chances
+}
+
+/// Chances of the bands of a single stat of a [`MoveTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRow {
+    /// The stat added to the roll.
+    pub stat: i32,
+    /// Chance of every band, from worst to best.
+    pub bands: Vec<(Band, f64)>,
+}
+
+/// Table of the [band chances][`band_chances`] across a range of stats, like a
+/// [`ModifierSweep`][`crate::ModifierSweep`] for moves.
+///
+/// # Examples
+/// ```
+/// # use die_stats::games::pbta::MoveTable;
+/// let table = MoveTable::new(-1..=3, true);
+/// assert_eq!(table.rows().len(), 5);
+/// assert!(table.to_csv().starts_with("stat,6-,7-9,10+,12+\n-1,"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveTable {
+    advanced: bool,
+    rows: Vec<MoveRow>,
+}
+
+impl MoveTable {
+    /// Collects the band chances of every stat in the range.
+    pub fn new(stats: RangeInclusive<i32>, advanced: bool) -> Self {
+        MoveTable {
+            advanced,
+            rows: stats
+                .map(|stat| MoveRow {
+                    stat,
+                    bands: band_chances(stat, advanced),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns one row per stat, in the order of the range.
+    pub fn rows(&self) -> &[MoveRow] {
+        &self.rows
+    }
+
+    /// Renders the table as CSV with a header row, using plain chances instead of percentages.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("stat");
+        for band in self.bands() {
+            let _ = write!(out, ",{}", band.label());
+        }
+        out.push('\n');
+        for row in &self.rows {
+            let _ = write!(out, "{}", row.stat);
+            for (_, chance) in &row.bands {
+                let _ = write!(out, ",{}", chance);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn bands(&self) -> Vec<Band> {
+        let mut bands = vec![Band::Miss, Band::WeakHit, Band::StrongHit];
+        if self.advanced {
+            bands.push(Band::Advanced);
+        }
+        bands
+    }
+}
+
+impl std::fmt::Display for MoveTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let FormatOptions {
+            number_width,
+            decimals,
+            ..
+        } = FormatOptions::default();
+        write!(f, "{:>number_width$}", "Stat")?;
+        for band in self.bands() {
+            write!(f, " : {:>number_width$}", band.label())?;
+        }
+        writeln!(f)?;
+        for row in &self.rows {
+            write!(f, "{:>+number_width$}", row.stat)?;
+            for (_, chance) in &row.bands {
+                write!(f, " : {:>number_width$.decimals$}", chance * 100.0)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves() {
+        assert_eq!(Band::of(6, true), Band::Miss);
+        assert_eq!(Band::of(9, true), Band::WeakHit);
+        assert_eq!(Band::of(12, false), Band::StrongHit);
+        assert_eq!(Band::of(12, true), Band::Advanced);
+
+        let plain = band_chances(0, false);
+        let expected = [15.0 / 36.0, 15.0 / 36.0, 6.0 / 36.0];
+        for (&(_, chance), expected) in plain.iter().zip(expected) {
+            assert!((chance - expected).abs() < 1e-12);
+        }
+        let advanced = band_chances(0, true);
+        assert!((advanced[2].1 - 5.0 / 36.0).abs() < 1e-12);
+        assert!((advanced[3].1 - 1.0 / 36.0).abs() < 1e-12);
+
+        let table = MoveTable::new(0..=1, false);
+        assert_eq!(table.rows()[1].bands, band_chances(1, false));
+        assert_eq!(table.to_csv().lines().count(), 3);
+        let display = table.to_string();
+        assert_eq!(display.lines().count(), 3);
+        assert!(display.lines().nth(2).unwrap().starts_with("        +1"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Chances of the hands of poker dice, rolling five dice at once and judging them like a poker
+//! hand.
+//!
+//! Which hands exist and how they are recognized is decided by a [`HandRanking`], the
+//! [`StandardRanking`] covers the usual hands from a bust up to five of a kind.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::poker_dice::{ hand_chances, Hand, StandardRanking };
+//! let chances = hand_chances(6, &StandardRanking);
+//! assert_eq!(chances.len(), 8);
+//! assert_eq!(chances.last().unwrap().0, Hand::FiveOfAKind);
+//! assert!((chances.last().unwrap().1 - 6.0 / 7776.0).abs() < 1e-12);
+//! ```
+
+use super::sorted_rolls;
+use crate::{Die, NormalInitializer};
+use std::collections::BTreeMap;
+
+/// Amount of dice making up a hand.
+pub const DICE: usize = 5;
+
+/// Decides which hand some rolled dice make up.
+///
+/// Closures taking the sorted dice are rankings as well, returning any ordered type as the hand.
+///
+/// # Examples
+/// ```
+/// # use die_stats::games::poker_dice::hand_chances;
+/// // only tell apart whether any pair was rolled
+/// let paired = |dice: &[i32]| dice.windows(2).any(|pair| pair[0] == pair[1]);
+/// let chances = hand_chances(6, &paired);
+/// assert!((chances[0].1 - 720.0 / 7776.0).abs() < 1e-12);
+/// ```
+pub trait HandRanking {
+    /// Hands ordered from worst to best.
+    type Hand: Ord;
+
+    /// Classifies the given dice, sorted from lowest to highest.
+    fn classify(&self, dice: &[i32]) -> Self::Hand;
+}
+
+impl<F, H> HandRanking for F
+where
+    F: Fn(&[i32]) -> H,
+    H: Ord,
+{
+    type Hand = H;
+
+    fn classify(&self, dice: &[i32]) -> H {
+        self(dice)
+    }
+}
+
+/// Hands of the [`StandardRanking`], ordered from worst to best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Hand {
+    /// Nothing of the below.
+    Bust,
+    /// Two dice showing the same value.
+    OnePair,
+    /// Two pairs of different values.
+    TwoPair,
+    /// Three dice showing the same value.
+    ThreeOfAKind,
+    /// Every die showing consecutive values.
+    Straight,
+    /// Three of a kind and a pair.
+    FullHouse,
+    /// Four dice showing the same value.
+    FourOfAKind,
+    /// Every die showing the same value.
+    FiveOfAKind,
+}
+
+/// The usual ranking of poker dice hands, see [`Hand`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StandardRanking;
+
+impl HandRanking for StandardRanking {
+    type Hand = Hand;
+
+    fn classify(&self, dice: &[i32]) -> Hand {
+        let mut groups: Vec<usize> = dice.chunk_by(|a, b| a == b).map(<[i32]>::len).collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        let consecutive = dice
+            .windows(2)
+            .all(|pair| pair[0].checked_add(1) == Some(pair[1]));
+        match groups.as_slice() {
+            [5, ..] => Hand::FiveOfAKind,
+            [4, ..] => Hand::FourOfAKind,
+            [3, 2, ..] => Hand::FullHouse,
+            _ if dice.len() > 1 && consecutive => Hand::Straight,
+            [3, ..] => Hand::ThreeOfAKind,
+            [2, 2, ..] => Hand::TwoPair,
+            [2, ..] => Hand::OnePair,
+            _ => Hand::Bust,
+        }
+    }
+}
+
+/// Returns the chance of every hand of `ranking` when rolling five dice with the given amount of
+/// faces, ordered from worst to best hand. Hands that can't be rolled are left out.
+pub fn hand_chances<R>(faces: i32, ranking: &R) -> Vec<(R::Hand, f64)>
+where
+    R: HandRanking,
+{
+    let mut chances = BTreeMap::new();
+    for (dice, chance) in sorted_rolls(&Die::new(faces), DICE) {
+        *chances.entry(ranking.classify(&dice)).or_insert(0.0) += chance;
+    }
+    chances.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_hands() {
+        let expected = [
+            (Hand::Bust, 480.0),
+            (Hand::OnePair, 3600.0),
+            (Hand::TwoPair, 1800.0),
+            (Hand::ThreeOfAKind, 1200.0),
+            (Hand::Straight, 240.0),
+            (Hand::FullHouse, 300.0),
+            (Hand::FourOfAKind, 150.0),
+            (Hand::FiveOfAKind, 6.0),
+        ];
+        let chances = hand_chances(6, &StandardRanking);
+        assert_eq!(chances.len(), expected.len());
+        for ((hand, chance), (expected_hand, ways)) in chances.iter().zip(expected) {
+            assert_eq!(*hand, expected_hand);
+            assert!((chance - ways / 7776.0).abs() < 1e-12);
+        }
+
+        assert_eq!(StandardRanking.classify(&[2, 3, 4, 5, 6]), Hand::Straight);
+        assert_eq!(StandardRanking.classify(&[1, 1, 4, 4, 4]), Hand::FullHouse);
+        // a single face only ever rolls five of a kind
+        assert_eq!(
+            hand_chances(1, &StandardRanking),
+            vec![(Hand::FiveOfAKind, 1.0)]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Battles of Risk: the attacker rolls up to three d6, the defender up to two, and the highest
+//! dice of both sides are compared pairwise, every tie going to the defender. Each lost
+//! comparison costs an army.
+//!
+//! The attacker has to leave an army behind, so a battle ends once the attacker is down to a
+//! single army or the defender has none left.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::risk::{ round_losses, Losses };
+//! let losses = round_losses(3, 2);
+//! assert_eq!(losses.len(), 3);
+//! assert_eq!(losses[0].0, Losses { attacker: 0, defender: 2 });
+//! assert!((losses[0].1 - 2890.0 / 7776.0).abs() < 1e-12);
+//! ```
+
+use super::sorted_rolls;
+use crate::{Die, NormalInitializer, Probability};
+use std::collections::{BTreeMap, HashMap};
+
+/// Most dice the attacker may roll in a round.
+pub const ATTACKER_DICE: usize = 3;
+/// Most dice the defender may roll in a round.
+pub const DEFENDER_DICE: usize = 2;
+
+/// Armies lost by both sides in a single round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Losses {
+    /// Armies lost by the attacker.
+    pub attacker: i32,
+    /// Armies lost by the defender.
+    pub defender: i32,
+}
+
+/// Returns the chance of every split of the losses of a round, ordered by the losses of the
+/// attacker.
+///
+/// Both sides' dice are sorted from highest to lowest and compared pairwise, as many pairs as
+/// the side rolling fewer dice has.
+pub fn round_losses(attacker_dice: usize, defender_dice: usize) -> Vec<(Losses, f64)> {
+    let die = Die::new(6);
+    let defender_rolls = sorted_rolls(&die, defender_dice);
+    let mut losses: BTreeMap<Losses, f64> = BTreeMap::new();
+    for (attacker, attacker_chance) in sorted_rolls(&die, attacker_dice) {
+        for (defender, defender_chance) in &defender_rolls {
+            let attacker_lost = attacker
+                .iter()
+                .rev()
+                .zip(defender.iter().rev())
+                .filter(|(attacking, defending)| attacking <= defending)
+                .count() as i32;
+            let compared = attacker_dice.min(defender_dice) as i32;
+            let split = Losses {
+                attacker: attacker_lost,
+                defender: compared - attacker_lost,
+            };
+            *losses.entry(split).or_insert(0.0) += attacker_chance * defender_chance;
+        }
+    }
+    losses.into_iter().collect()
+}
+
+/// A battle fought round after round with as many dice as allowed, until either side can't go
+/// on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Battle {
+    attackers: i32,
+    defenders: i32,
+}
+
+/// Result of fighting a [`Battle`] to its end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BattleOutcome {
+    /// Chance of the attacker wiping out the defender.
+    pub attacker_wins: f64,
+    /// Distribution of the armies the attacker has left, `1` when losing.
+    pub attacker_remaining: Die,
+    /// Distribution of the armies the defender has left, `0` when losing.
+    pub defender_remaining: Die,
+}
+
+impl Battle {
+    /// Creates a battle of `attackers` attacking armies, including the one left behind, against
+    /// `defenders` defending armies.
+    pub fn new(attackers: i32, defenders: i32) -> Self {
+        Battle {
+            attackers,
+            defenders,
+        }
+    }
+
+    /// Fights the battle until the attacker is down to a single army or the defender has none
+    /// left.
+    pub fn resolve(&self) -> BattleOutcome {
+        // chances of every (attackers, defenders) still fighting, every round taking away at
+        // least one army from either side
+        let mut fighting: HashMap<(i32, i32), f64> =
+            HashMap::from([((self.attackers, self.defenders), 1.0)]);
+        let mut rounds: HashMap<(usize, usize), Vec<(Losses, f64)>> = HashMap::new();
+        let mut attacker_remaining: HashMap<i32, f64> = HashMap::new();
+        let mut defender_remaining: HashMap<i32, f64> = HashMap::new();
+        let mut attacker_wins = 0.0;
+        while !fighting.is_empty() {
+            let mut next = HashMap::with_capacity(fighting.len() * 3);
+            for ((attackers, defenders), chance) in fighting {
+                if attackers <= 1 || defenders <= 0 {
+                    *attacker_remaining.entry(attackers).or_insert(0.0) += chance;
+                    *defender_remaining.entry(defenders).or_insert(0.0) += chance;
+                    if defenders <= 0 {
+                        attacker_wins += chance;
+                    }
+                    continue;
+                }
+                let dice = (
+                    ATTACKER_DICE.min(attackers as usize - 1),
+                    DEFENDER_DICE.min(defenders as usize),
+                );
+                let losses = rounds
+                    .entry(dice)
+                    .or_insert_with(|| round_losses(dice.0, dice.1));
+                for &(lost, lost_chance) in losses.iter() {
+                    *next
+                        .entry((attackers - lost.attacker, defenders - lost.defender))
+                        .or_insert(0.0) += chance * lost_chance;
+                }
+            }
+            fighting = next;
+        }
+        BattleOutcome {
+            attacker_wins,
+            attacker_remaining: to_die(attacker_remaining),
+            defender_remaining: to_die(defender_remaining),
+        }
+    }
+}
+
+fn to_die(accumulated: HashMap<i32, f64>) -> Die {
+    Die::from_probabilities(
+        accumulated
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProbabilityDistribution;
+
+    #[test]
+    fn battles() {
+        let duel = round_losses(1, 1);
+        assert_eq!(
+            duel[0].0,
+            Losses {
+                attacker: 0,
+                defender: 1
+            }
+        );
+        assert!((duel[0].1 - 15.0 / 36.0).abs() < 1e-12);
+
+        let full = round_losses(3, 2);
+        let expected = [2890.0 / 7776.0, 2611.0 / 7776.0, 2275.0 / 7776.0];
+        for (&(_, chance), expected) in full.iter().zip(expected) {
+            assert!((chance - expected).abs() < 1e-12);
+        }
+
+        let short = Battle::new(2, 1).resolve();
+        assert!((short.attacker_wins - 15.0 / 36.0).abs() < 1e-12);
+        assert_eq!(short.attacker_remaining, Die::from_values(&[1, 2]));
+
+        let long = Battle::new(10, 5).resolve();
+        assert!(long.attacker_wins > 0.5);
+        assert_eq!(long.attacker_remaining.get_min(), 1);
+        assert_eq!(long.attacker_remaining.get_max(), 10);
+        assert_eq!(long.defender_remaining.get_max(), 5);
+        assert!(
+            (long.defender_remaining.get_survival(0) - (1.0 - long.attacker_wins)).abs() < 1e-12
+        );
+
+        let hopeless = Battle::new(1, 3).resolve();
+        assert_eq!(hopeless.attacker_wins, 0.0);
+        assert_eq!(hopeless.defender_remaining, Die::from(3));
+    }
+}
@@ -0,0 +1,108 @@
+//! Trait rolls of Savage Worlds: the trait die and, for wild cards, a d6 wild die both ace
+//! (explode on their highest face without limit), the better of them counts and every full `4`
+//! above the target number is a raise.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::savage_worlds::TraitRoll;
+//! # use die_stats::ProbabilityDistribution;
+//! // wild card with a d8 and +1 against the usual target number of 4
+//! let raises = TraitRoll::new(8).wild_card().modifier(1).raises(4);
+//! let failure = raises.get_probabilities()[0].chance;
+//! assert!((failure - 2.0 / 8.0 * 2.0 / 6.0).abs() < 1e-9);
+//! ```
+
+use crate::{
+    Die, DropInitializer, DropType, ExplodingCondition, NormalInitializer, ProbabilityDistribution,
+};
+
+/// Every full amount of this above the target number counts as a raise.
+pub const RAISE: i32 = 4;
+/// Faces of the wild die rolled alongside the trait die of wild cards.
+pub const WILD_DIE: i32 = 6;
+/// Chance of acing yet another time, below which acing is cut off.
+pub const TOLERANCE: f64 = 1e-12;
+
+/// A single trait roll, built up from the trait die.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraitRoll {
+    trait_die: i32,
+    wild_card: bool,
+    modifier: i32,
+}
+
+impl TraitRoll {
+    /// Creates the roll of an extra, rolling just the trait die with the given faces.
+    ///
+    /// # Panics
+    /// Panics if the trait die has less than two faces, since it would ace forever.
+    pub fn new(trait_die: i32) -> Self {
+        assert!(trait_die >= 2, "the trait die needs at least two faces");
+        TraitRoll {
+            trait_die,
+            wild_card: false,
+            modifier: 0,
+        }
+    }
+
+    /// Rolls the wild die alongside the trait die, keeping the better one.
+    pub fn wild_card(mut self) -> Self {
+        self.wild_card = true;
+        self
+    }
+
+    /// Adds a flat amount to the total, on top of any previous modifiers.
+    pub fn modifier(mut self, modifier: i32) -> Self {
+        self.modifier += modifier;
+        self
+    }
+
+    /// Returns the distribution of the total of the roll.
+    pub fn total(&self) -> Die {
+        let aced = |faces: i32| {
+            Die::new(faces).explode_unbounded(ExplodingCondition::Equal, faces, TOLERANCE)
+        };
+        let best = if self.wild_card {
+            Die::keep_from_pool(&[aced(self.trait_die), aced(WILD_DIE)], 1, DropType::High)
+        } else {
+            aced(self.trait_die)
+        };
+        best.add_flat(self.modifier)
+    }
+
+    /// Returns the distribution of [degrees of success][`Die::degrees_of_success`] against
+    /// `target`: `-1` for a failure, `0` for a plain success and the amount of raises otherwise.
+    pub fn raises(&self, target: i32) -> Die {
+        self.total().degrees_of_success(target, RAISE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trait_rolls() {
+        let extra = TraitRoll::new(4).raises(4);
+        let chances: Vec<f64> = extra
+            .get_probabilities()
+            .iter()
+            .map(|prob| prob.chance)
+            .collect();
+        // 1 to 3 fail, a 4 aces into 5 to 8 for a success and beyond for raises
+        assert!((chances[0] - 0.75).abs() < 1e-9);
+        assert!((chances[1] - 0.25 * 0.75).abs() < 1e-9);
+        assert!((chances[2] - 0.25 * 0.25 * 0.75).abs() < 1e-9);
+
+        let wild_card = TraitRoll::new(4).wild_card().raises(4);
+        assert!((wild_card.get_probabilities()[0].chance - 0.75 * 0.5).abs() < 1e-9);
+        assert!(wild_card.get_mean() > extra.get_mean());
+
+        let modified = TraitRoll::new(6).modifier(2).modifier(-1);
+        assert_eq!(modified.total().get_min(), 2);
+        assert_eq!(
+            modified.raises(8),
+            modified.total().degrees_of_success(8, RAISE)
+        );
+    }
+}
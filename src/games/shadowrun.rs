@@ -0,0 +1,156 @@
+//! Dice pools of Shadowrun: every d6 showing `5` or `6` is a hit, more than half of the dice
+//! showing `1` is a glitch and a glitch without any hits a critical glitch.
+//!
+//! When pushing the limit with Edge, the rule of six rerolls every `6` and adds the hits of the
+//! reroll, again and again for as long as `6`s come up. Glitches are judged on the initial roll
+//! only.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::shadowrun::DicePool;
+//! # use die_stats::ProbabilityDistribution;
+//! let outcome = DicePool::new(6).evaluate();
+//! assert!((outcome.hits.get_mean() - 2.0).abs() < 1e-9);
+//! // four or more ones out of six dice
+//! assert!((outcome.glitch - 0.0087).abs() < 1e-4);
+//! ```
+
+use crate::{Die, NormalInitializer, Probability};
+use std::collections::HashMap;
+
+/// Chance of yet another `6` in a row, below which the rule of six is cut off.
+pub const TOLERANCE: f64 = 1e-12;
+
+/// A pool of d6s, built up from the amount of dice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DicePool {
+    dice: usize,
+    rule_of_six: bool,
+}
+
+/// Result of evaluating a [`DicePool`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    /// Distribution of the amount of hits.
+    pub hits: Die,
+    /// Chance of more than half of the dice showing `1`.
+    pub glitch: f64,
+    /// Chance of a glitch without any hits.
+    pub critical_glitch: f64,
+}
+
+impl DicePool {
+    /// Creates a pool of `dice` d6s.
+    pub fn new(dice: usize) -> Self {
+        DicePool {
+            dice,
+            rule_of_six: false,
+        }
+    }
+
+    /// Rerolls every `6` and adds the hits of the reroll, as when pushing the limit with Edge.
+    pub fn rule_of_six(mut self) -> Self {
+        self.rule_of_six = true;
+        self
+    }
+
+    /// Returns the hits and the chances to glitch of the pool.
+    ///
+    /// The hits and ones of the dice are tracked together, since glitches depend on both.
+    pub fn evaluate(&self) -> Outcome {
+        let outcomes = self.die_outcomes();
+        // chances of (hits, ones)
+        let mut states: HashMap<(i32, usize), f64> = HashMap::from([((0, 0), 1.0)]);
+        for _ in 0..self.dice {
+            let mut next = HashMap::with_capacity(states.len() * outcomes.len());
+            for (&(hits, ones), &chance) in &states {
+                for &(die_hits, die_ones, die_chance) in &outcomes {
+                    *next
+                        .entry((hits + die_hits, ones + die_ones))
+                        .or_insert(0.0) += chance * die_chance;
+                }
+            }
+            states = next;
+        }
+
+        let mut hits = HashMap::new();
+        let (mut glitch, mut critical_glitch) = (0.0, 0.0);
+        for ((state_hits, ones), chance) in states {
+            *hits.entry(state_hits).or_insert(0.0) += chance;
+            if ones * 2 > self.dice {
+                glitch += chance;
+                if state_hits == 0 {
+                    critical_glitch += chance;
+                }
+            }
+        }
+        Outcome {
+            hits: Die::from_probabilities(
+                hits.into_iter()
+                    .map(|(value, chance)| Probability { value, chance })
+                    .collect(),
+            ),
+            glitch,
+            critical_glitch,
+        }
+    }
+
+    /// Hits and ones of a single die, with their chances.
+    fn die_outcomes(&self) -> Vec<(i32, usize, f64)> {
+        let face = 1.0 / 6.0;
+        let mut outcomes = vec![(0, 1, face), (0, 0, 3.0 * face), (1, 0, face)];
+        if !self.rule_of_six {
+            outcomes.push((1, 0, face));
+            return outcomes;
+        }
+        // every reroll hits on a 5 and stops, hits on a 6 and continues or stops on anything
+        // else, so k more hits happen with a chance of (1/6)^k * (4/6 + 6/6 * [k > 0])
+        let mut continuing = 1.0;
+        for extra_hits in 0.. {
+            let chance = continuing
+                * if extra_hits == 0 {
+                    4.0 * face
+                } else {
+                    10.0 * face
+                };
+            outcomes.push((1 + extra_hits, 0, face * chance));
+            continuing *= face;
+            if continuing < TOLERANCE {
+                break;
+            }
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProbabilityDistribution;
+
+    #[test]
+    fn pools() {
+        let single = DicePool::new(1).evaluate();
+        assert_eq!(single.hits, Die::from_values(&[0, 1]));
+        assert!((single.hits.get_mean() - 1.0 / 3.0).abs() < 1e-12);
+        assert!((single.glitch - 1.0 / 6.0).abs() < 1e-12);
+        assert!((single.critical_glitch - 1.0 / 6.0).abs() < 1e-12);
+
+        let pair = DicePool::new(2).evaluate();
+        assert!((pair.glitch - 1.0 / 36.0).abs() < 1e-12);
+        assert!((pair.critical_glitch - 1.0 / 36.0).abs() < 1e-12);
+
+        // three ones and a hit is a glitch, but not a critical one
+        let four = DicePool::new(4).evaluate();
+        assert!(four.critical_glitch < four.glitch);
+
+        let edge = DicePool::new(5).rule_of_six().evaluate();
+        assert!((edge.hits.get_mean() - 5.0 * 0.4).abs() < 1e-9);
+        assert!(edge.hits.get_max() > 5);
+        assert!((edge.glitch - DicePool::new(5).evaluate().glitch).abs() < 1e-9);
+
+        let empty = DicePool::new(0).evaluate();
+        assert_eq!(empty.hits, Die::from(0));
+        assert_eq!(empty.glitch, 0.0);
+    }
+}
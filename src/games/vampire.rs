@@ -0,0 +1,178 @@
+//! Dice pools of Vampire: The Masquerade 5th edition, split between normal and hunger d10s.
+//!
+//! Every die showing `6` or more is a success and every pair of `10`s a critical, counting as
+//! four successes instead of two. A critical win involving a `10` on a hunger die is a messy
+//! critical, a failure with a `1` on a hunger die a bestial failure.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::vampire::HungerPool;
+//! # use die_stats::ProbabilityDistribution;
+//! let roll = HungerPool::new(3, 2).roll();
+//! // half of the dice succeed on average, criticals add a little on top
+//! assert!(roll.successes().get_mean() > 2.5);
+//! assert!(roll.messy_critical(3) > 0.0);
+//! assert!(roll.bestial_failure(3) > roll.bestial_failure(1));
+//! ```
+
+use crate::{Die, NormalInitializer, Probability, ProbabilityDistribution};
+use std::collections::HashMap;
+
+/// Lowest face counting as a success.
+pub const SUCCESS: i32 = 6;
+/// Faces of every die of the pool.
+pub const FACES: i32 = 10;
+
+/// A pool of normal and hunger d10s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HungerPool {
+    normal: usize,
+    hunger: usize,
+}
+
+/// One possible outcome of a [`HungerPool`], regardless of the difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Outcome {
+    /// Amount of successes, including the bonus of criticals.
+    pub successes: i32,
+    /// Whether at least two `10`s were rolled.
+    pub critical: bool,
+    /// Whether the critical involves a `10` on a hunger die.
+    pub messy: bool,
+    /// Whether any hunger die shows a `1`.
+    pub hunger_one: bool,
+}
+
+/// Joint distribution of the [outcomes][`Outcome`] of a [`HungerPool`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HungerRoll {
+    outcomes: Vec<(Outcome, f64)>,
+}
+
+impl HungerPool {
+    /// Creates a pool of `normal` normal dice and `hunger` hunger dice.
+    pub fn new(normal: usize, hunger: usize) -> Self {
+        HungerPool { normal, hunger }
+    }
+
+    /// Rolls the pool, keeping apart which kind of die produced which face.
+    pub fn roll(&self) -> HungerRoll {
+        // chances of (successes without the critical bonus, tens, hunger ten, hunger one)
+        let mut states: HashMap<(i32, i32, bool, bool), f64> =
+            HashMap::from([((0, 0, false, false), 1.0)]);
+        let kinds =
+            std::iter::repeat_n(false, self.normal).chain(std::iter::repeat_n(true, self.hunger));
+        let die = Die::new(FACES);
+        for is_hunger in kinds {
+            let mut next = HashMap::with_capacity(states.len() * 4);
+            for (&(successes, tens, hunger_ten, hunger_one), &chance) in &states {
+                for prob in die.iter() {
+                    let ten = prob.value == FACES;
+                    let state = (
+                        successes + i32::from(prob.value >= SUCCESS),
+                        tens + i32::from(ten),
+                        hunger_ten || (is_hunger && ten),
+                        hunger_one || (is_hunger && prob.value == 1),
+                    );
+                    *next.entry(state).or_insert(0.0) += chance * prob.chance;
+                }
+            }
+            states = next;
+        }
+
+        let mut outcomes: HashMap<Outcome, f64> = HashMap::new();
+        for ((successes, tens, hunger_ten, hunger_one), chance) in states {
+            let critical = tens >= 2;
+            let outcome = Outcome {
+                successes: successes + tens / 2 * 2,
+                critical,
+                messy: critical && hunger_ten,
+                hunger_one,
+            };
+            *outcomes.entry(outcome).or_insert(0.0) += chance;
+        }
+        let mut outcomes: Vec<(Outcome, f64)> = outcomes.into_iter().collect();
+        outcomes.sort_by_key(|&(outcome, _)| outcome);
+        HungerRoll { outcomes }
+    }
+}
+
+impl HungerRoll {
+    /// Returns every outcome with its chance, ordered by the amount of successes.
+    pub fn outcomes(&self) -> &[(Outcome, f64)] {
+        &self.outcomes
+    }
+
+    /// Returns the distribution of the amount of successes.
+    pub fn successes(&self) -> Die {
+        let mut accumulated: HashMap<i32, f64> = HashMap::new();
+        for &(outcome, chance) in &self.outcomes {
+            *accumulated.entry(outcome.successes).or_insert(0.0) += chance;
+        }
+        Die::from_probabilities(
+            accumulated
+                .into_iter()
+                .map(|(value, chance)| Probability { value, chance })
+                .collect(),
+        )
+    }
+
+    /// Returns the chance of at least `difficulty` successes.
+    pub fn win(&self, difficulty: i32) -> f64 {
+        self.chance(|outcome| outcome.successes >= difficulty)
+    }
+
+    /// Returns the chance of a win thanks to or alongside a critical.
+    pub fn critical_win(&self, difficulty: i32) -> f64 {
+        self.chance(|outcome| outcome.successes >= difficulty && outcome.critical)
+    }
+
+    /// Returns the chance of a critical win involving a `10` on a hunger die.
+    pub fn messy_critical(&self, difficulty: i32) -> f64 {
+        self.chance(|outcome| outcome.successes >= difficulty && outcome.messy)
+    }
+
+    /// Returns the chance of failing with a `1` on a hunger die.
+    pub fn bestial_failure(&self, difficulty: i32) -> f64 {
+        self.chance(|outcome| outcome.successes < difficulty && outcome.hunger_one)
+    }
+
+    fn chance<F>(&self, condition: F) -> f64
+    where
+        F: Fn(&Outcome) -> bool,
+    {
+        self.outcomes
+            .iter()
+            .filter(|(outcome, _)| condition(outcome))
+            .map(|&(_, chance)| chance)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hunger_pools() {
+        let single = HungerPool::new(1, 0).roll();
+        assert!((single.win(1) - 0.5).abs() < 1e-12);
+        assert_eq!(single.bestial_failure(1), 0.0);
+
+        let hungry = HungerPool::new(0, 1).roll();
+        assert!((hungry.bestial_failure(1) - 0.1).abs() < 1e-12);
+        assert_eq!(hungry.critical_win(1), 0.0);
+
+        let mixed = HungerPool::new(1, 1).roll();
+        assert!((mixed.critical_win(1) - 0.01).abs() < 1e-12);
+        assert!((mixed.messy_critical(4) - 0.01).abs() < 1e-12);
+        assert_eq!(mixed.messy_critical(5), 0.0);
+        assert_eq!(mixed.successes().get_max(), 4);
+
+        let calm = HungerPool::new(2, 0).roll();
+        assert!((calm.critical_win(4) - 0.01).abs() < 1e-12);
+        assert_eq!(calm.messy_critical(1), 0.0);
+        let total: f64 = calm.outcomes().iter().map(|&(_, chance)| chance).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,140 @@
+//! Attack sequences of Warhammer 40,000: a number of attacks, each having to hit, wound and get
+//! past the save of the target before dealing damage.
+//!
+//! Every roll is a d6 against a target number, where an unmodified `1` always fails and an
+//! unmodified `6` always hits or wounds. Each stage thins out the attacks binomially, the
+//! damage of all attacks getting through is summed up at the end.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::warhammer::AttackSequence;
+//! # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+//! // 10 attacks hitting on 3+, wounding on 4+, against a 5+ save, dealing d3 damage each
+//! let damage = AttackSequence::new(Die::from(10))
+//!     .hit_on(3)
+//!     .wound_on(4)
+//!     .save_on(5)
+//!     .damage(Die::new(3))
+//!     .damage_dealt();
+//! assert!((damage.get_mean() - 10.0 * 2.0 / 3.0 * 0.5 * 2.0 / 3.0 * 2.0).abs() < 1e-9);
+//! ```
+
+use crate::{Die, ProbabilityDistribution};
+
+/// Chain of stages each attack has to get through, built up from the amount of attacks.
+///
+/// Stages that aren't set are passed automatically, so the sequence can start or end anywhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttackSequence {
+    attacks: Die,
+    hit: Option<i32>,
+    wound: Option<i32>,
+    save: Option<i32>,
+    damage: Die,
+}
+
+impl AttackSequence {
+    /// Creates a sequence with the given amount of attacks, like `Die::from(4)` or `Die::new(6)`
+    /// for a random amount, each dealing `1` damage.
+    pub fn new(attacks: Die) -> Self {
+        AttackSequence {
+            attacks,
+            hit: None,
+            wound: None,
+            save: None,
+            damage: Die::from(1),
+        }
+    }
+
+    /// Makes every attack roll to hit, succeeding on `target` or more.
+    pub fn hit_on(mut self, target: i32) -> Self {
+        self.hit = Some(target);
+        self
+    }
+
+    /// Makes every hit roll to wound, succeeding on `target` or more.
+    pub fn wound_on(mut self, target: i32) -> Self {
+        self.wound = Some(target);
+        self
+    }
+
+    /// Lets the target save every wound on `target` or more, a target above `6` can't be saved.
+    pub fn save_on(mut self, target: i32) -> Self {
+        self.save = Some(target);
+        self
+    }
+
+    /// Sets the damage every unsaved wound deals.
+    pub fn damage(mut self, damage: Die) -> Self {
+        self.damage = damage;
+        self
+    }
+
+    /// Returns the distribution of the amount of hits.
+    pub fn hits(&self) -> Die {
+        thin(&self.attacks, self.hit.map_or(1.0, roll_chance))
+    }
+
+    /// Returns the distribution of the amount of wounds.
+    pub fn wounds(&self) -> Die {
+        thin(&self.hits(), self.wound.map_or(1.0, roll_chance))
+    }
+
+    /// Returns the distribution of the amount of wounds the target failed to save.
+    pub fn unsaved_wounds(&self) -> Die {
+        let saved = self
+            .save
+            .map_or(0.0, |target| f64::from((7 - target).clamp(0, 5)) / 6.0);
+        thin(&self.wounds(), 1.0 - saved)
+    }
+
+    /// Returns the distribution of the total damage dealt by every unsaved wound.
+    pub fn damage_dealt(&self) -> Die {
+        self.unsaved_wounds()
+            .conditional_chain(&mut |&wounds| self.damage.repeat(wounds.max(0) as usize))
+    }
+}
+
+/// Chance of a d6 to roll `target` or more, where `1` always fails and `6` always succeeds.
+fn roll_chance(target: i32) -> f64 {
+    f64::from((7 - target).clamp(1, 5)) / 6.0
+}
+
+/// Lets every one of the counted things through with a chance of `p`.
+fn thin(count: &Die, p: f64) -> Die {
+    count.conditional_chain(&mut |&amount| Die::binomial(amount.max(0) as u32, p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::assert_same_chances;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn attack_sequences() {
+        let unopposed = AttackSequence::new(Die::from(3));
+        assert_eq!(unopposed.damage_dealt(), Die::from(3));
+
+        let sequence = AttackSequence::new(Die::from(2)).hit_on(4).wound_on(4);
+        assert_same_chances(
+            sequence.hits().get_probabilities(),
+            Die::binomial(2, 0.5).get_probabilities(),
+        );
+        assert!((sequence.wounds().get_probabilities()[2].chance - 1.0 / 16.0).abs() < 1e-12);
+
+        // 1s always fail and 6s always succeed
+        let always = AttackSequence::new(Die::from(1)).hit_on(1).hits();
+        assert!((always.get_mean() - 5.0 / 6.0).abs() < 1e-12);
+        let never = AttackSequence::new(Die::from(1)).hit_on(7).hits();
+        assert!((never.get_mean() - 1.0 / 6.0).abs() < 1e-12);
+        let unsavable = AttackSequence::new(Die::from(4)).save_on(7);
+        assert_eq!(unsavable.unsaved_wounds(), Die::from(4));
+
+        let random = AttackSequence::new(Die::new(6))
+            .damage(Die::new(6))
+            .save_on(4);
+        assert!((random.damage_dealt().get_mean() - 3.5 * 0.5 * 3.5).abs() < 1e-9);
+        assert_eq!(random.damage_dealt().get_max(), 36);
+    }
+}
@@ -0,0 +1,289 @@
+//! Chances of the scoring categories of Yahtzee, played with five six-sided dice and up to two
+//! rerolls per turn.
+//!
+//! Between rolls the dice are held to maximize the chance of the category in question, so every
+//! chance is the best one achievable when going for that category alone.
+//!
+//! # Examples
+//! ```
+//! # use die_stats::games::yahtzee::{ Category, Turn };
+//! // a fresh turn, three rolls left
+//! let fresh = Turn::new(&[], 2);
+//! assert!((fresh.chance(Category::Yahtzee) - 0.046).abs() < 1e-3);
+//! // holding three sixes, two dice left to roll, no more rerolls
+//! let sixes = Turn::new(&[6, 6, 6], 0);
+//! assert!((sixes.chance(Category::Yahtzee) - 1.0 / 36.0).abs() < 1e-9);
+//! ```
+
+use super::sorted_rolls;
+use crate::{Die, NormalInitializer};
+use std::collections::HashMap;
+
+/// Amount of dice rolled each turn.
+pub const DICE: usize = 5;
+/// Amount of faces of each die.
+pub const FACES: i32 = 6;
+
+/// One of the thirteen boxes of the score card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Category {
+    /// Sum of all ones.
+    Ones,
+    /// Sum of all twos.
+    Twos,
+    /// Sum of all threes.
+    Threes,
+    /// Sum of all fours.
+    Fours,
+    /// Sum of all fives.
+    Fives,
+    /// Sum of all sixes.
+    Sixes,
+    /// Sum of all dice, if at least three show the same value.
+    ThreeOfAKind,
+    /// Sum of all dice, if at least four show the same value.
+    FourOfAKind,
+    /// `25`, if three dice show one value and the other two another one.
+    FullHouse,
+    /// `30`, if four dice show consecutive values.
+    SmallStraight,
+    /// `40`, if all five dice show consecutive values.
+    LargeStraight,
+    /// `50`, if all five dice show the same value.
+    Yahtzee,
+    /// Sum of all dice.
+    Chance,
+}
+
+impl Category {
+    /// Every category, in the order of the score card.
+    pub const ALL: [Category; 13] = [
+        Category::Ones,
+        Category::Twos,
+        Category::Threes,
+        Category::Fours,
+        Category::Fives,
+        Category::Sixes,
+        Category::ThreeOfAKind,
+        Category::FourOfAKind,
+        Category::FullHouse,
+        Category::SmallStraight,
+        Category::LargeStraight,
+        Category::Yahtzee,
+        Category::Chance,
+    ];
+
+    /// Returns the score of the given dice in this category, `0` if they don't qualify.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::games::yahtzee::Category;
+    /// assert_eq!(Category::Threes.score(&[3, 3, 1, 5, 3]), 9);
+    /// assert_eq!(Category::FullHouse.score(&[2, 5, 2, 5, 5]), 25);
+    /// assert_eq!(Category::SmallStraight.score(&[1, 2, 3, 5, 6]), 0);
+    /// ```
+    pub fn score(self, dice: &[i32]) -> i32 {
+        let mut counts = [0; FACES as usize + 1];
+        for &value in dice {
+            if (1..=FACES).contains(&value) {
+                counts[value as usize] += 1;
+            }
+        }
+        let total: i32 = dice.iter().sum();
+        let most = counts.iter().copied().max().unwrap_or(0);
+        let longest = counts[1..]
+            .split(|&count| count == 0)
+            .map(<[i32]>::len)
+            .max()
+            .unwrap_or(0);
+        match self {
+            Category::Ones
+            | Category::Twos
+            | Category::Threes
+            | Category::Fours
+            | Category::Fives
+            | Category::Sixes => {
+                let face = self as i32 + 1;
+                face * counts[face as usize]
+            }
+            Category::ThreeOfAKind if most >= 3 => total,
+            Category::FourOfAKind if most >= 4 => total,
+            Category::FullHouse if counts.contains(&3) && counts.contains(&2) => 25,
+            Category::SmallStraight if longest >= 4 => 30,
+            Category::LargeStraight if longest >= 5 => 40,
+            Category::Yahtzee if most == 5 => 50,
+            Category::Chance => total,
+            _ => 0,
+        }
+    }
+}
+
+/// State of a turn: the dice held from the previous roll, and the rerolls left after rolling
+/// the rest of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn {
+    held: Vec<i32>,
+    rerolls: usize,
+}
+
+impl Turn {
+    /// Creates a turn holding the given dice and rolling the other ones, with `rerolls` rerolls
+    /// left afterwards. A fresh turn holds nothing and has `2` rerolls left.
+    ///
+    /// # Panics
+    /// Panics if more than five dice are held or any of them is not between `1` and `6`.
+    pub fn new(held: &[i32], rerolls: usize) -> Self {
+        assert!(held.len() <= DICE, "at most {} dice can be held", DICE);
+        assert!(
+            held.iter().all(|value| (1..=FACES).contains(value)),
+            "held dice have to be between 1 and {}",
+            FACES
+        );
+        let mut held = held.to_vec();
+        held.sort_unstable();
+        Turn { held, rerolls }
+    }
+
+    /// Returns the chance of scoring anything in `category` by the end of the turn, holding the
+    /// dice that maximize it between rolls.
+    pub fn chance(&self, category: Category) -> f64 {
+        Solver::new(category).after_roll(&self.held, self.rerolls)
+    }
+
+    /// Returns the [chance][`Turn::chance`] of every category, in the order of the score card.
+    pub fn chances(&self) -> Vec<(Category, f64)> {
+        Category::ALL
+            .iter()
+            .map(|&category| (category, self.chance(category)))
+            .collect()
+    }
+
+    /// Returns the dice to hold out of the rolled `dice` to maximize the chance of `category`
+    /// with the rerolls of this turn, together with that chance.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::games::yahtzee::{ Category, Turn };
+    /// let (held, chance) = Turn::new(&[], 1).best_hold(&[2, 3, 4, 6, 6], Category::LargeStraight);
+    /// assert_eq!(held, vec![2, 3, 4, 6]);
+    /// assert!((chance - 1.0 / 6.0).abs() < 1e-9);
+    /// ```
+    pub fn best_hold(&self, dice: &[i32], category: Category) -> (Vec<i32>, f64) {
+        let mut dice = dice.to_vec();
+        dice.sort_unstable();
+        Solver::new(category).best_hold(&dice, self.rerolls)
+    }
+}
+
+/// Memoized chances of a single category.
+struct Solver {
+    category: Category,
+    /// Every sorted outcome of rolling the given amount of dice, with its chance.
+    rolls: Vec<Vec<(Vec<i32>, f64)>>,
+    memo: HashMap<(Vec<i32>, usize), f64>,
+}
+
+impl Solver {
+    fn new(category: Category) -> Self {
+        let die = Die::new(FACES);
+        let rolls = (0..=DICE)
+            .map(|amount| sorted_rolls(&die, amount))
+            .collect();
+        Solver {
+            category,
+            rolls,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Chance of success after rolling every die besides `held`, with `rerolls` left afterwards.
+    fn after_roll(&mut self, held: &[i32], rerolls: usize) -> f64 {
+        let rolled_dice = DICE - held.len();
+        let mut total = 0.0;
+        for index in 0..self.rolls[rolled_dice].len() {
+            let (rolled, chance) = &self.rolls[rolled_dice][index];
+            let chance = *chance;
+            let mut dice = held.to_vec();
+            dice.extend_from_slice(rolled);
+            dice.sort_unstable();
+            total += chance * self.before_hold(dice, rerolls);
+        }
+        total
+    }
+
+    /// Chance of success with the given rolled dice, holding the best ones for the rerolls.
+    fn before_hold(&mut self, dice: Vec<i32>, rerolls: usize) -> f64 {
+        let met = self.category.score(&dice) > 0;
+        if met || rerolls == 0 {
+            return f64::from(u8::from(met));
+        }
+        if let Some(&chance) = self.memo.get(&(dice.clone(), rerolls)) {
+            return chance;
+        }
+        let (_, chance) = self.best_hold(&dice, rerolls);
+        self.memo.insert((dice, rerolls), chance);
+        chance
+    }
+
+    fn best_hold(&mut self, dice: &[i32], rerolls: usize) -> (Vec<i32>, f64) {
+        let mut best = (dice.to_vec(), -1.0);
+        let mut tried: Vec<Vec<i32>> = Vec::new();
+        for mask in 0..1u32 << dice.len() {
+            let held: Vec<i32> = (0..dice.len())
+                .filter(|&index| mask & (1 << index) != 0)
+                .map(|index| dice[index])
+                .collect();
+            if tried.contains(&held) {
+                continue;
+            }
+            let chance = if rerolls == 0 {
+                f64::from(u8::from(self.category.score(dice) > 0))
+            } else {
+                self.after_roll(&held, rerolls - 1)
+            };
+            // prefer holding more dice on ties
+            if chance > best.1 + 1e-12 || (chance > best.1 - 1e-12 && held.len() > best.0.len()) {
+                best = (held.clone(), chance);
+            }
+            tried.push(held);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoring() {
+        assert_eq!(Category::Yahtzee.score(&[4; 5]), 50);
+        assert_eq!(Category::FullHouse.score(&[4; 5]), 0);
+        assert_eq!(Category::FourOfAKind.score(&[4, 4, 4, 4, 1]), 17);
+        assert_eq!(Category::ThreeOfAKind.score(&[4, 4, 1, 2, 3]), 0);
+        assert_eq!(Category::SmallStraight.score(&[6, 3, 4, 5, 5]), 30);
+        assert_eq!(Category::LargeStraight.score(&[6, 3, 4, 5, 2]), 40);
+        assert_eq!(Category::Chance.score(&[1, 2, 3, 4, 5]), 15);
+    }
+
+    #[test]
+    fn chances() {
+        let single_roll = Turn::new(&[], 0);
+        assert!((single_roll.chance(Category::Yahtzee) - 6.0 / 7776.0).abs() < 1e-12);
+        assert!((single_roll.chance(Category::LargeStraight) - 240.0 / 7776.0).abs() < 1e-12);
+        assert!((single_roll.chance(Category::FullHouse) - 300.0 / 7776.0).abs() < 1e-12);
+        assert!(
+            (single_roll.chance(Category::Sixes) - (1.0 - (5.0f64 / 6.0).powi(5))).abs() < 1e-12
+        );
+        assert!((single_roll.chance(Category::Chance) - 1.0).abs() < 1e-12);
+
+        let full_turn = Turn::new(&[], 2);
+        assert!((full_turn.chance(Category::Yahtzee) - 0.046029).abs() < 1e-6);
+        assert!(full_turn.chance(Category::Yahtzee) < full_turn.chance(Category::FourOfAKind));
+        assert_eq!(full_turn.chances().len(), 13);
+
+        let held = Turn::new(&[1, 2, 3, 4], 0);
+        assert!((held.chance(Category::LargeStraight) - 1.0 / 6.0).abs() < 1e-12);
+        assert!((held.chance(Category::SmallStraight) - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{Probability, ProbabilityDistribution};
+use core::ops::Add;
+use std::collections::BTreeMap;
+
+/// Minimal storage needed for a custom [probability distribution][`ProbabilityDistribution`].
+///
+/// Every type implementing this trait gets [`ProbabilityDistribution`], including all of its
+/// combinators and statistics, for free. Implementors only have to hand out their probabilities
+/// and be able to store new ones, which are always sorted by value and free of duplicates.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ HasProbabilities, Probability, ProbabilityDistribution };
+/// #[derive(Debug, Clone)]
+/// struct Damage(Vec<Probability<i64>>);
+///
+/// impl HasProbabilities<i64> for Damage {
+///     fn probabilities(&self) -> &[Probability<i64>] {
+///         &self.0
+///     }
+///
+///     fn from_sorted_probabilities(probabilities: Vec<Probability<i64>>) -> Self {
+///         Damage(probabilities)
+///     }
+/// }
+///
+/// let hit = Damage(vec![
+///     Probability { value: 0, chance: 0.5 },
+///     Probability { value: 10, chance: 0.5 },
+/// ]);
+/// let two_hits = hit.add_independent(&hit).add_flat(1);
+/// assert_eq!(two_hits.get_max(), 21);
+/// assert_eq!(two_hits.get_min(), 1);
+/// ```
+pub trait HasProbabilities<T> {
+    /// Returns the stored probabilities.
+    fn probabilities(&self) -> &[Probability<T>];
+
+    /// Creates a new instance storing the given probabilities, sorted by value and merged.
+    fn from_sorted_probabilities(probabilities: Vec<Probability<T>>) -> Self;
+}
+
+impl<T, P> ProbabilityDistribution<T> for P
+where
+    P: HasProbabilities<T>,
+    T: Copy + Ord + Add<Output = T> + From<i32>,
+{
+    fn add_dependent<F, D>(&self, callback_fn: &F) -> Self
+    where
+        F: Fn(&T) -> D,
+        D: ProbabilityDistribution<T>,
+    {
+        let mut accumulated = BTreeMap::new();
+        for outer_prob in self.probabilities() {
+            for inner_prob in callback_fn(&outer_prob.value).get_probabilities() {
+                *accumulated
+                    .entry(outer_prob.value + inner_prob.value)
+                    .or_insert(0.0) += outer_prob.chance * inner_prob.chance;
+            }
+        }
+        from_accumulated(accumulated)
+    }
+
+    fn add_flat(&self, flat_increase: i32) -> Self {
+        P::from_sorted_probabilities(
+            self.probabilities()
+                .iter()
+                .map(|prob| Probability {
+                    value: prob.value + flat_increase.into(),
+                    chance: prob.chance,
+                })
+                .collect(),
+        )
+    }
+
+    fn add_independent(&self, probability_distribution: &impl ProbabilityDistribution<T>) -> Self {
+        let mut accumulated = BTreeMap::new();
+        for outer_prob in self.probabilities() {
+            for inner_prob in probability_distribution.get_probabilities() {
+                *accumulated
+                    .entry(outer_prob.value + inner_prob.value)
+                    .or_insert(0.0) += outer_prob.chance * inner_prob.chance;
+            }
+        }
+        from_accumulated(accumulated)
+    }
+
+    fn conditional_chain<F, D>(&self, callback_fn: &mut F) -> Self
+    where
+        F: FnMut(&T) -> D,
+        D: ProbabilityDistribution<T>,
+    {
+        let mut accumulated = BTreeMap::new();
+        for outer_prob in self.probabilities() {
+            for inner_prob in callback_fn(&outer_prob.value).get_probabilities() {
+                *accumulated.entry(inner_prob.value).or_insert(0.0) +=
+                    outer_prob.chance * inner_prob.chance;
+            }
+        }
+        from_accumulated(accumulated)
+    }
+
+    fn get_probabilities(&self) -> &[Probability<T>] {
+        self.probabilities()
+    }
+}
+
+fn from_accumulated<T, P>(accumulated: BTreeMap<T, f64>) -> P
+where
+    P: HasProbabilities<T>,
+{
+    P::from_sorted_probabilities(
+        accumulated
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ALLOWED_ERROR;
+    use crate::{Die, NormalInitializer};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Coin(Vec<Probability<i32>>);
+
+    impl HasProbabilities<i32> for Coin {
+        fn probabilities(&self) -> &[Probability<i32>] {
+            &self.0
+        }
+
+        fn from_sorted_probabilities(probabilities: Vec<Probability<i32>>) -> Self {
+            Coin(probabilities)
+        }
+    }
+
+    fn coin() -> Coin {
+        Coin(vec![
+            Probability {
+                value: 0,
+                chance: 0.5,
+            },
+            Probability {
+                value: 1,
+                chance: 0.5,
+            },
+        ])
+    }
+
+    #[test]
+    fn combinators() {
+        let two = coin().add_independent(&coin());
+        assert_eq!(
+            two.get_probabilities()
+                .iter()
+                .map(|prob| (prob.value, prob.chance))
+                .collect::<Vec<_>>(),
+            vec![(0, 0.25), (1, 0.5), (2, 0.25)]
+        );
+        assert_eq!(two, coin().add_dependent(&|_| coin()));
+        assert_eq!(coin().add_flat(2).get_min(), 2);
+        let flipped = coin().conditional_chain(&mut |&value| {
+            Coin(vec![Probability {
+                value: 1 - value,
+                chance: 1.0,
+            }])
+        });
+        assert_eq!(flipped, coin());
+        assert!((two.get_variance() - 0.5).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    fn mixed_combinators() {
+        let with_die = coin().add_dependent(&|_| Die::new(2));
+        assert_eq!(with_die.get_min(), 1);
+        assert_eq!(with_die.get_max(), 3);
+        let from_die = Die::new(2).conditional_chain(&mut |_| coin());
+        assert_eq!(from_die, Die::from_values(&[0, 1]));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{Die, NormalInitializer, Probability, ProbabilityDistribution};
+
+/// Every joint outcome of rolling several dice together with its chance, keeping the outcomes
+/// apart instead of combining them right away, as created by [`Die::product_with`].
+///
+/// Outcomes are sorted by value, so pairs are ordered by their first roll, then by their second
+/// one. Projecting the outcomes back to a single value via [`JointDie::project`] creates a
+/// regular [`Die`] again.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+/// let attack = Die::new(20).product_with(&Die::new(8));
+/// // hit on 11 or more, dealing double damage on a natural 20
+/// let damage = attack.project(|&(to_hit, damage)| match to_hit {
+///     20 => 2 * damage,
+///     11.. => damage,
+///     _ => 0,
+/// });
+/// assert!((damage.get_mean() - 2.475).abs() < 1e-9);
+/// let max_crit = attack.chance_where(|&(to_hit, damage)| to_hit == 20 && damage == 8);
+/// assert!((max_crit - 1.0 / 160.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JointDie<T> {
+    probabilities: Vec<Probability<T>>,
+}
+
+impl<T> JointDie<T> {
+    /// Returns every joint outcome with its chance, sorted by value.
+    pub fn get_probabilities(&self) -> &[Probability<T>] {
+        &self.probabilities
+    }
+
+    /// Creates a die by applying `projection_fn` to every joint outcome, merging the values that
+    /// end up the same.
+    pub fn project<F>(&self, projection_fn: F) -> Die
+    where
+        F: Fn(&T) -> i32,
+    {
+        Die::from_probabilities(
+            self.probabilities
+                .iter()
+                .map(|prob| Probability {
+                    value: projection_fn(&prob.value),
+                    chance: prob.chance,
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the chance of a joint outcome fulfilling `condition_fn`.
+    pub fn chance_where<F>(&self, condition_fn: F) -> f64
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.probabilities
+            .iter()
+            .filter(|prob| condition_fn(&prob.value))
+            .map(|prob| prob.chance)
+            .sum::<f64>()
+            .min(1.0)
+    }
+}
+
+impl JointDie<(i32, i32)> {
+    /// Returns the distribution of the first roll of every pair on its own.
+    pub fn first(&self) -> Die {
+        self.project(|&(first, _)| first)
+    }
+
+    /// Returns the distribution of the second roll of every pair on its own.
+    pub fn second(&self) -> Die {
+        self.project(|&(_, second)| second)
+    }
+}
+
+impl Die {
+    /// Pairs up every roll of this die with every roll of an independent one, keeping the joint
+    /// outcomes instead of combining them.
+    ///
+    /// Useful whenever the rolls influence each other after the fact, like damage depending on
+    /// the attack roll. See [`JointDie`].
+    pub fn product_with(
+        &self,
+        probability_distribution: &impl ProbabilityDistribution<i32>,
+    ) -> JointDie<(i32, i32)> {
+        JointDie {
+            probabilities: self
+                .get_probabilities()
+                .iter()
+                .flat_map(|prob| {
+                    probability_distribution
+                        .get_probabilities()
+                        .iter()
+                        .map(move |other_prob| Probability {
+                            value: (prob.value, other_prob.value),
+                            chance: prob.chance * other_prob.chance,
+                        })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_same_chances, ALLOWED_ERROR};
+
+    #[test]
+    fn joint_outcomes() {
+        let (d4, d6) = (Die::new(4), Die::new(6));
+        let joint = d4.product_with(&d6);
+        assert_eq!(joint.get_probabilities().len(), 24);
+        assert_eq!(joint.get_probabilities()[0].value, (1, 1));
+        assert_eq!(joint.get_probabilities()[23].value, (4, 6));
+        assert_same_chances(joint.first().get_probabilities(), d4.get_probabilities());
+        assert_same_chances(joint.second().get_probabilities(), d6.get_probabilities());
+        assert!((joint.second().get_mean() - 3.5).abs() < ALLOWED_ERROR);
+        assert_same_chances(
+            joint.project(|&(a, b)| a + b).get_probabilities(),
+            d4.add_independent(&d6).get_probabilities(),
+        );
+        assert!((joint.chance_where(|&(a, b)| a > b) - 6.0 / 24.0).abs() < ALLOWED_ERROR);
+        assert!((joint.chance_where(|_| true) - 1.0).abs() < ALLOWED_ERROR);
+    }
+}
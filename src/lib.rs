@@ -6,32 +6,87 @@
 //! arithmetic implementations to mutate created die.
 //!
 //! Yet to be implemented but planned features:
-//! - [ ] `FromStr` to [`Die`]
+//! - [x] `FromStr` to [`Die`]
 //! - [ ] Complete arithmetic implementations for [`Die`]
 //! - [ ] Round results from getters to avoid floating point imprecisions
 //! - [ ] Expand given examples to actually show capabilities
-//! - [ ] Implement at least / at most for Die
-//! - [ ] Make it easier to create [`ProbabilityDistribution`] without needing to implement all the
-//! adding stuff etc.
+//! - [x] Implement at least / at most for Die
+//! - [x] Make it easier to create [`ProbabilityDistribution`] without needing to implement all the
+//!   adding stuff etc., see [`HasProbabilities`]
 //!
 //! [probability distributions]: `ProbabilityDistribution`
 //! [exploding]: `ExplodingInitializer`
 //! [roll x drop n highest/lowest]: `DropInitializer`
 
+#[cfg(feature = "color")]
+pub use crate::colored::Colored;
 pub use crate::{
-    common::compress_additive,
-    die::Die,
+    anydice::AnyDiceView,
+    common::{compress_additive, compress_additive_with_epsilon},
+    die::{Die, Margin, ParseDieError},
     drop_initializer::{DropInitializer, DropType},
-    exploding_initializer::{ExplodingCondition, ExplodingInitializer},
+    dyn_distribution::DynDistribution,
+    estimate::{DieEstimate, FaceEstimate},
+    event::{at_least, at_least_one, Event},
+    exploding_initializer::{ExplodingCondition, ExplodingInitializer, ExplosionStats},
+    expression::{Dialect, Evaluator, ExpressionError},
+    format_options::{FormatOptions, Formatted},
+    has_probabilities::HasProbabilities,
+    joint_die::JointDie,
+    markov::MarkovChain,
+    mechanic::{
+        CountDistinct, CountSuccesses, DropDice, ExplodeDice, ExplodeInto, KeepDice, LongestRun,
+        Mechanic, MechanicRegistry, PerDie, RerollDice, SumDice,
+    },
+    modifier_sweep::{ModifierSweep, SweepRow},
     normal_initializer::NormalInitializer,
+    overflow::{OverflowError, OverflowPolicy},
+    pool_builder::PoolBuilder,
     probability::Probability,
-    probability_distribution::{ProbabilityDistribution, ProbabilityIter},
+    probability_distribution::{
+        ChiSquaredResult, CumulativeIter, DominanceResult, PairIter, ProbabilityDistribution,
+        ProbabilityIter, SurvivalIter,
+    },
+    race::{Race, RaceTurn},
+    ranking::{choose_best, rank_by, Choice, Criterion, QuantileLevel, RankEntry, Ranking},
+    reroll::RerollPolicy,
+    sampler::Sampler,
+    step::Step,
+    streaming::{Outcomes, StreamingStats},
+    wide_die::WideDie,
 };
 
+mod anydice;
+#[cfg(feature = "color")]
+mod colored;
 mod common;
 mod die;
 mod drop_initializer;
+mod dyn_distribution;
+mod estimate;
+mod event;
 mod exploding_initializer;
+mod expression;
+mod format_options;
+#[cfg(feature = "games")]
+pub mod games;
+mod has_probabilities;
+mod joint_die;
+mod markov;
+mod mechanic;
+mod modifier_sweep;
 mod normal_initializer;
+mod overflow;
+mod polynomial;
+mod pool_builder;
 mod probability;
 mod probability_distribution;
+mod race;
+mod ranking;
+mod report;
+mod reroll;
+mod sampler;
+mod step;
+mod streaming;
+mod uniform_sum;
+mod wide_die;
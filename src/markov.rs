@@ -0,0 +1,404 @@
+//! Markov chains over the values of dice, for processes moving from state to state by rolls,
+//! like board positions, condition tracks or death saves.
+
+use crate::{Die, NormalInitializer, Probability, ProbabilityDistribution};
+use std::collections::{HashMap, VecDeque};
+
+/// Pivots smaller than this are treated as zero when solving linear systems.
+const SINGULAR: f64 = 1e-12;
+
+/// A Markov chain over `i32` states, where a [`Die`] decides the next state of every state.
+///
+/// States staying where they are with certainty are absorbing, like the last square of a board
+/// or the end of a condition track.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, MarkovChain, NormalInitializer, ProbabilityDistribution };
+/// // move a d3 forward on a board of 10 squares, stopping on the last one
+/// let board = MarkovChain::from_fn(0, |position| {
+///     Die::new(3).conditional_chain(&mut |&step| Die::from((position + step).min(10)))
+/// });
+/// assert_eq!(board.states().len(), 11);
+/// let first = board.distribution_after(&Die::from(0), 1);
+/// assert_eq!(first, Die::new(3));
+/// assert!((1..=3).all(|square| (first.get_chance(square) - 1.0 / 3.0).abs() < 1e-12));
+/// let turns = board.expected_steps_to_absorption(0).unwrap();
+/// assert!(turns > 10.0 / 2.0 && turns < 10.0 / 2.0 + 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkovChain {
+    states: Vec<i32>,
+    indices: HashMap<i32, usize>,
+    // chances of moving from the state of the row to the state of every index
+    transitions: Vec<Vec<(usize, f64)>>,
+}
+
+impl MarkovChain {
+    /// Creates a chain from every state reachable from `start`, where `transition_fn` returns the
+    /// distribution of the next state of the given one.
+    ///
+    /// The reachable states have to be finite, otherwise this never returns.
+    pub fn from_fn<F>(start: i32, mut transition_fn: F) -> Self
+    where
+        F: FnMut(i32) -> Die,
+    {
+        let mut next_states: HashMap<i32, Die> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(state) = queue.pop_front() {
+            if next_states.contains_key(&state) {
+                continue;
+            }
+            let next = transition_fn(state);
+            queue.extend(
+                next.iter()
+                    .map(|prob| prob.value)
+                    .filter(|value| !next_states.contains_key(value)),
+            );
+            next_states.insert(state, next);
+        }
+
+        let mut states: Vec<i32> = next_states.keys().copied().collect();
+        states.sort_unstable();
+        let indices: HashMap<i32, usize> = states
+            .iter()
+            .enumerate()
+            .map(|(index, &state)| (state, index))
+            .collect();
+        let transitions = states
+            .iter()
+            .map(|state| {
+                next_states[state]
+                    .iter()
+                    .filter(|prob| prob.chance > 0.0)
+                    .map(|prob| (indices[&prob.value], prob.chance))
+                    .collect()
+            })
+            .collect();
+        MarkovChain {
+            states,
+            indices,
+            transitions,
+        }
+    }
+
+    /// Returns every state of the chain, sorted from lowest to highest.
+    pub fn states(&self) -> &[i32] {
+        &self.states
+    }
+
+    /// Returns whether the chain never leaves the given state once it got there.
+    pub fn is_absorbing(&self, state: i32) -> bool {
+        self.indices
+            .get(&state)
+            .is_some_and(|&index| self.is_absorbing_index(index))
+    }
+
+    /// Returns the distribution of the states after `steps` steps, starting from a state
+    /// distributed like `start`.
+    ///
+    /// # Panics
+    /// Panics if `start` can result in a value that isn't a state of the chain.
+    pub fn distribution_after(&self, start: &Die, steps: usize) -> Die {
+        let mut current = self.to_vector(start);
+        for _ in 0..steps {
+            current = self.step(&current);
+        }
+        self.to_die(&current)
+    }
+
+    /// Returns the distribution of the states the chain settles into in the long run, or `None`
+    /// if there is no single such distribution, like when several states are absorbing.
+    pub fn stationary(&self) -> Option<Die> {
+        let n = self.states.len();
+        // (P^T - I) x = 0, replacing the last equation with the chances adding up to 1
+        let mut matrix = vec![vec![0.0; n]; n];
+        for (from, row) in self.transitions.iter().enumerate() {
+            for &(to, chance) in row {
+                matrix[to][from] += chance;
+            }
+        }
+        for (index, row) in matrix.iter_mut().enumerate() {
+            row[index] -= 1.0;
+        }
+        matrix[n - 1] = vec![1.0; n];
+        let mut rhs = vec![vec![0.0]; n];
+        rhs[n - 1][0] = 1.0;
+        let solution = solve(matrix, rhs)?;
+        let chances: Vec<f64> = solution.iter().map(|row| row[0].max(0.0)).collect();
+        Some(self.to_die(&chances))
+    }
+
+    /// Returns the distribution of the absorbing states the chain ends up in when starting from
+    /// `start`, or `None` if it may never get absorbed.
+    ///
+    /// # Panics
+    /// Panics if `start` isn't a state of the chain.
+    pub fn absorption_chances(&self, start: i32) -> Option<Die> {
+        let Absorption {
+            transient,
+            absorbing,
+            solution,
+        } = self.absorption(start)?;
+        let Some(row) = transient
+            .iter()
+            .position(|&index| index == self.index(start))
+        else {
+            return Some(Die::from(start));
+        };
+        Some(Die::from_probabilities(
+            absorbing
+                .iter()
+                .zip(&solution[row])
+                .filter(|(_, &chance)| chance > 0.0)
+                .map(|(&index, &chance)| Probability {
+                    value: self.states[index],
+                    chance,
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns the expected amount of steps until the chain gets absorbed when starting from
+    /// `start`, or `None` if it may never get absorbed.
+    ///
+    /// # Panics
+    /// Panics if `start` isn't a state of the chain.
+    pub fn expected_steps_to_absorption(&self, start: i32) -> Option<f64> {
+        let Absorption {
+            transient,
+            absorbing,
+            solution,
+        } = self.absorption(start)?;
+        let row = transient
+            .iter()
+            .position(|&index| index == self.index(start));
+        Some(row.map_or(0.0, |row| solution[row][absorbing.len()]))
+    }
+
+    /// Returns the distribution of the amount of steps until the chain gets absorbed when
+    /// starting from `start`, capped at `max_steps` like a [geometric
+    /// distribution][`Die::geometric`].
+    ///
+    /// # Panics
+    /// Panics if `start` isn't a state of the chain.
+    pub fn absorption_times(&self, start: i32, max_steps: usize) -> Die {
+        let mut current = self.to_vector(&Die::from(start));
+        let remaining = |current: &[f64]| -> f64 {
+            (0..self.states.len())
+                .filter(|&index| !self.is_absorbing_index(index))
+                .map(|index| current[index])
+                .sum()
+        };
+        let mut previous = 1.0;
+        let mut times = Vec::with_capacity(max_steps + 1);
+        for step in 0..=max_steps {
+            if step > 0 {
+                current = self.step(&current);
+            }
+            // everything not absorbed in time is counted as taking `max_steps`
+            let left = if step == max_steps {
+                0.0
+            } else {
+                remaining(&current)
+            };
+            times.push(Probability {
+                value: step as i32,
+                chance: (previous - left).max(0.0),
+            });
+            previous = left;
+        }
+        Die::from_probabilities(times.into_iter().filter(|prob| prob.chance > 0.0).collect())
+    }
+
+    fn index(&self, state: i32) -> usize {
+        *self
+            .indices
+            .get(&state)
+            .unwrap_or_else(|| panic!("{} is not a state of the chain", state))
+    }
+
+    fn is_absorbing_index(&self, index: usize) -> bool {
+        self.transitions[index]
+            .iter()
+            .all(|&(to, chance)| to == index || chance == 0.0)
+    }
+
+    fn to_vector(&self, distribution: &Die) -> Vec<f64> {
+        let mut vector = vec![0.0; self.states.len()];
+        for prob in distribution.iter() {
+            vector[self.index(prob.value)] += prob.chance;
+        }
+        vector
+    }
+
+    fn to_die(&self, vector: &[f64]) -> Die {
+        Die::from_probabilities(
+            self.states
+                .iter()
+                .zip(vector)
+                .filter(|(_, &chance)| chance > 0.0)
+                .map(|(&value, &chance)| Probability { value, chance })
+                .collect(),
+        )
+    }
+
+    fn step(&self, current: &[f64]) -> Vec<f64> {
+        let mut next = vec![0.0; current.len()];
+        for (from, row) in self.transitions.iter().enumerate() {
+            for &(to, chance) in row {
+                next[to] += current[from] * chance;
+            }
+        }
+        next
+    }
+
+    /// Solves `(I - Q) X = [R | 1]` over the transient and absorbing states reachable from
+    /// `start`, giving the absorption chances and the expected steps of every transient state.
+    fn absorption(&self, start: i32) -> Option<Absorption> {
+        let mut reachable = vec![false; self.states.len()];
+        let mut queue = VecDeque::from([self.index(start)]);
+        while let Some(index) = queue.pop_front() {
+            if !std::mem::replace(&mut reachable[index], true) {
+                queue.extend(self.transitions[index].iter().map(|&(to, _)| to));
+            }
+        }
+        let (absorbing, transient): (Vec<usize>, Vec<usize>) = (0..self.states.len())
+            .filter(|&index| reachable[index])
+            .partition(|&index| self.is_absorbing_index(index));
+        if absorbing.is_empty() {
+            return None;
+        }
+
+        let position = |index: usize, among: &[usize]| among.iter().position(|&i| i == index);
+        let n = transient.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        let mut rhs = vec![vec![0.0; absorbing.len() + 1]; n];
+        for (row, &from) in transient.iter().enumerate() {
+            matrix[row][row] += 1.0;
+            rhs[row][absorbing.len()] = 1.0;
+            for &(to, chance) in &self.transitions[from] {
+                if let Some(column) = position(to, &transient) {
+                    matrix[row][column] -= chance;
+                } else if let Some(column) = position(to, &absorbing) {
+                    rhs[row][column] += chance;
+                }
+            }
+        }
+        Some(Absorption {
+            transient,
+            absorbing,
+            solution: solve(matrix, rhs)?,
+        })
+    }
+}
+
+/// Indices of the transient and absorbing states reachable from some state, along with one row
+/// per transient state holding its absorption chances followed by its expected steps.
+struct Absorption {
+    transient: Vec<usize>,
+    absorbing: Vec<usize>,
+    solution: Vec<Vec<f64>>,
+}
+
+/// Solves `A X = B` via Gaussian elimination with partial pivoting, returning `None` if `A` is
+/// singular.
+fn solve(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|&a, &b| matrix[a][column].abs().total_cmp(&matrix[b][column].abs()))?;
+        if matrix[pivot][column].abs() < SINGULAR {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+        let (pivot_row, pivot_rhs) = (matrix[column].clone(), rhs[column].clone());
+        for row in 0..n {
+            if row == column {
+                continue;
+            }
+            let factor = matrix[row][column] / pivot_row[column];
+            if factor == 0.0 {
+                continue;
+            }
+            for (value, pivot_value) in matrix[row].iter_mut().zip(&pivot_row).skip(column) {
+                *value -= factor * pivot_value;
+            }
+            for (value, pivot_value) in rhs[row].iter_mut().zip(&pivot_rhs) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    for (row, values) in rhs.iter_mut().enumerate() {
+        for value in values.iter_mut() {
+            *value /= matrix[row][row];
+        }
+    }
+    Some(rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chains() {
+        // death saves: successes * 4 + failures, 10 or more succeeding, a 1 counting as two
+        // failures and a 20 waking up right away as -1
+        let saves = MarkovChain::from_fn(0, |state| {
+            let (successes, failures) = (state / 4, state % 4);
+            if state == -1 || successes == 3 || failures == 3 {
+                return Die::from(state);
+            }
+            Die::new(20).conditional_chain(&mut |&roll| match roll {
+                20 => Die::from(-1),
+                10..=19 => Die::from((successes + 1) * 4 + failures),
+                2..=9 => Die::from(successes * 4 + failures + 1),
+                _ => Die::from(successes * 4 + (failures + 2).min(3)),
+            })
+        });
+        assert!(saves.is_absorbing(-1));
+        assert!(saves.is_absorbing(12));
+        assert!(!saves.is_absorbing(0));
+        let ends = saves.absorption_chances(0).unwrap();
+        assert!((ends.iter().map(|prob| prob.chance).sum::<f64>() - 1.0).abs() < 1e-9);
+        let dead: f64 = ends
+            .iter()
+            .filter(|prob| prob.value >= 0 && prob.value % 4 == 3)
+            .map(|prob| prob.chance)
+            .sum();
+        assert!(dead > 0.35 && dead < 0.45);
+        let expected = saves.expected_steps_to_absorption(0).unwrap();
+        assert!(expected > 2.0 && expected < 5.0);
+        let times = saves.absorption_times(0, 10);
+        assert_eq!(times.get_min(), 1);
+        assert_eq!(times.get_max(), 5);
+        assert!((times.get_mean() - expected).abs() < 1e-9);
+        let capped = saves.absorption_times(0, 2);
+        assert_eq!(capped.get_max(), 2);
+        assert!((capped.get_chance(2) - (1.0 - times.get_chance(1))).abs() < 1e-9);
+        assert_eq!(saves.absorption_times(0, 0), Die::from(0));
+        assert!((saves.absorption_times(0, 0).get_chance(0) - 1.0).abs() < 1e-12);
+        assert_eq!(saves.absorption_chances(-1), Some(Die::from(-1)));
+        assert_eq!(saves.expected_steps_to_absorption(12), Some(0.0));
+        assert_eq!(saves.stationary(), None);
+
+        // flipping a coin between two states forever
+        let flip = MarkovChain::from_fn(0, |_| Die::from_values(&[0, 1]));
+        assert_eq!(flip.absorption_chances(0), None);
+        let stationary = flip.stationary().unwrap();
+        assert!((stationary.get_mean() - 0.5).abs() < 1e-12);
+        let sticky = MarkovChain::from_fn(0, |state| {
+            if state == 0 {
+                Die::from_values(&[0, 0, 0, 1])
+            } else {
+                Die::from_values(&[0, 1])
+            }
+        });
+        let stationary = sticky.stationary().unwrap();
+        assert!((stationary.get_probabilities()[0].chance - 2.0 / 3.0).abs() < 1e-12);
+        let after = sticky.distribution_after(&Die::from(1), 50);
+        assert!((after.get_probabilities()[0].chance - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
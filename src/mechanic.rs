@@ -0,0 +1,782 @@
+use crate::drop_initializer::drop_by_condition;
+use crate::exploding_initializer::exploding_helper;
+use crate::{
+    Die, DropType, ExplodingCondition, ExplosionStats, NormalInitializer, Probability,
+    ProbabilityDistribution, Step,
+};
+use std::collections::HashMap;
+
+/// A rule turning a pool of [dice][`Die`] into a single result, like summing, keeping the
+/// highest or counting successes.
+///
+/// Mechanics working on single dice, like [exploding][`ExplodeDice`] or
+/// [rerolling][`RerollDice`], sum up the pool after modifying every die, but can be combined with
+/// any other mechanic via [`PerDie`]. Closures taking a pool are mechanics as well.
+///
+/// Mechanics that don't depend on [`Die`] itself, like keeping or dropping dice, work on any
+/// [probability distribution][`ProbabilityDistribution`] `P` with values `V`. The
+/// [drop][`crate::DropInitializer`] and [exploding][`crate::ExplodingInitializer`] initializers
+/// are built on top of those.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, DropType, ExplodeDice, ExplodingCondition, KeepDice, Mechanic, NormalInitializer, PerDie, ProbabilityDistribution };
+/// // 4d6, explode on 6 up to twice, keep the highest three
+/// let mechanic = PerDie::new(
+///     ExplodeDice::new(ExplodingCondition::Equal, 6, 2),
+///     KeepDice::new(3, DropType::High),
+/// );
+/// let result = mechanic.apply(&vec![Die::new(6); 4]);
+/// assert_eq!(result.get_min(), 3);
+/// assert_eq!(result.get_max(), 54);
+///
+/// let sum_plus_one = |pool: &[Die]| Die::sum_all(pool).add_flat(1);
+/// assert_eq!(sum_plus_one.apply(&[Die::new(4), Die::new(8)]).get_max(), 13);
+/// ```
+pub trait Mechanic<V = i32, P = Die> {
+    /// Evaluates the given pool into a single result.
+    fn apply(&self, pool: &[P]) -> P;
+}
+
+impl<F> Mechanic for F
+where
+    F: Fn(&[Die]) -> Die,
+{
+    fn apply(&self, pool: &[Die]) -> Die {
+        self(pool)
+    }
+}
+
+/// Sums up every die of the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SumDice;
+
+impl Mechanic for SumDice {
+    fn apply(&self, pool: &[Die]) -> Die {
+        Die::sum_all(pool)
+    }
+}
+
+/// Keeps `amount` dice from the specified end of the pool and sums them up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepDice {
+    amount: usize,
+    keep_condition: DropType,
+}
+
+impl KeepDice {
+    /// Creates a new mechanic keeping `amount` dice from the given end.
+    pub fn new(amount: usize, keep_condition: DropType) -> Self {
+        KeepDice {
+            amount,
+            keep_condition,
+        }
+    }
+}
+
+impl<V, P> Mechanic<V, P> for KeepDice
+where
+    P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+    V: Copy + Ord + From<i32> + Step + std::iter::Sum,
+{
+    fn apply(&self, pool: &[P]) -> P {
+        let drop_condition = match self.keep_condition {
+            DropType::High => DropType::Low,
+            DropType::Low => DropType::High,
+        };
+        drop_by_condition(pool, drop_condition, pool.len().saturating_sub(self.amount))
+    }
+}
+
+/// Drops `amount` dice from the specified end of the pool and sums up the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropDice {
+    amount: usize,
+    drop_condition: DropType,
+}
+
+impl DropDice {
+    /// Creates a new mechanic dropping `amount` dice from the given end.
+    pub fn new(amount: usize, drop_condition: DropType) -> Self {
+        DropDice {
+            amount,
+            drop_condition,
+        }
+    }
+}
+
+impl<V, P> Mechanic<V, P> for DropDice
+where
+    P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+    V: Copy + Ord + From<i32> + Step + std::iter::Sum,
+{
+    fn apply(&self, pool: &[P]) -> P {
+        drop_by_condition(pool, self.drop_condition, self.amount)
+    }
+}
+
+/// Rolls every die of the pool again and adds the result whenever it fulfills the condition, up
+/// to `depth` times in a row or, if created [without a limit][`ExplodeDice::unbounded`], until
+/// the chance to explode again drops below a tolerance, then sums up the pool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExplodeDice {
+    exploding_condition: ExplodingCondition,
+    exploding_range: i32,
+    depth: usize,
+    tolerance: Option<f64>,
+}
+
+impl ExplodeDice {
+    /// Creates a new mechanic exploding on the given condition, at most `depth` times in a row.
+    pub fn new(
+        exploding_condition: ExplodingCondition,
+        exploding_range: i32,
+        depth: usize,
+    ) -> Self {
+        ExplodeDice {
+            exploding_condition,
+            exploding_range,
+            depth,
+            tolerance: None,
+        }
+    }
+
+    /// Creates a new mechanic exploding on the given condition as often as it is fulfilled, see
+    /// [`Die::explode_unbounded`].
+    ///
+    /// The [truncated mass][`Die::truncated_mass`] of the result is the chance of any die of the
+    /// pool to be cut off by `tolerance`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, ExplodeDice, ExplodingCondition, Mechanic, NormalInitializer, ProbabilityDistribution };
+    /// let mechanic = ExplodeDice::unbounded(ExplodingCondition::Equal, 6, 1e-9);
+    /// let result = mechanic.apply(&vec![Die::new(6); 2]);
+    /// assert!((result.get_mean() - 8.4).abs() < 1e-6);
+    /// assert!(result.truncated_mass() < 2e-9);
+    /// ```
+    pub fn unbounded(
+        exploding_condition: ExplodingCondition,
+        exploding_range: i32,
+        tolerance: f64,
+    ) -> Self {
+        ExplodeDice {
+            exploding_condition,
+            exploding_range,
+            depth: 0,
+            tolerance: Some(tolerance),
+        }
+    }
+
+    /// Returns how long the chains of explosions of the given die get under this mechanic.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, ExplodeDice, ExplodingCondition, NormalInitializer, ProbabilityDistribution };
+    /// let stats = ExplodeDice::new(ExplodingCondition::GreaterOrEqual, 5, 2).stats(&Die::new(6));
+    /// assert!((stats.expected_explosions - (1.0 / 3.0 + 1.0 / 9.0)).abs() < 1e-9);
+    /// assert_eq!(stats.chain_length.get_max(), 2);
+    /// ```
+    pub fn stats(&self, die: &Die) -> ExplosionStats {
+        if let Some(tolerance) = self.tolerance {
+            return die
+                .explode_unbounded_with_stats(
+                    self.exploding_condition,
+                    self.exploding_range,
+                    tolerance,
+                )
+                .1;
+        }
+        let exploding_chance = die
+            .get_probabilities()
+            .iter()
+            .filter(|prob| {
+                self.exploding_condition
+                    .matches(prob.value, self.exploding_range)
+            })
+            .fold(0.0, |acc, prob| acc + prob.chance);
+        let chain_length = (0..=self.depth)
+            .map(|length| Probability {
+                value: length as i32,
+                chance: exploding_chance.powi(length as i32)
+                    * if length < self.depth {
+                        1.0 - exploding_chance
+                    } else {
+                        1.0
+                    },
+            })
+            .filter(|prob| prob.chance > 0.0)
+            .collect();
+        ExplosionStats::new(Die::from_probabilities(chain_length))
+    }
+
+    fn explode(&self, die: &Die, depth: usize) -> Die {
+        if let Some(tolerance) = self.tolerance {
+            return die.explode_unbounded(
+                self.exploding_condition,
+                self.exploding_range,
+                tolerance,
+            );
+        }
+        if depth == 0 {
+            return die.clone();
+        }
+        let exploded = self.explode(die, depth - 1);
+        die.add_dependent(&|&value| {
+            if self
+                .exploding_condition
+                .matches(value, self.exploding_range)
+            {
+                exploded.clone()
+            } else {
+                Die::empty()
+            }
+        })
+    }
+}
+
+impl Mechanic for ExplodeDice {
+    fn apply(&self, pool: &[Die]) -> Die {
+        let exploded: Vec<_> = pool
+            .iter()
+            .map(|die| self.explode(die, self.depth))
+            .collect();
+        let kept_mass = exploded
+            .iter()
+            .fold(1.0, |acc, die| acc * (1.0 - die.truncated_mass()));
+        Die::sum_all(&exploded).with_truncated_mass(1.0 - kept_mass)
+    }
+}
+
+/// Adds `exploding` to every die of the pool whose result fulfills the condition, then sums up
+/// the pool.
+///
+/// Unlike [`ExplodeDice`], the explosion rolls the given distribution instead of the die itself
+/// and happens only once.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, ExplodeInto, ExplodingCondition, Mechanic, NormalInitializer, ProbabilityDistribution };
+/// // a d20 adding a d6 on a natural 20
+/// let mechanic = ExplodeInto::new(ExplodingCondition::Equal, 20, Die::new(6));
+/// let result = mechanic.apply(&[Die::new(20)]);
+/// assert_eq!(result.get_max(), 26);
+/// assert!((result.get_mean() - (10.5 + 3.5 / 20.0)).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplodeInto<V, P> {
+    exploding_condition: ExplodingCondition,
+    exploding_range: V,
+    exploding: P,
+}
+
+impl<V, P> ExplodeInto<V, P> {
+    /// Creates a new mechanic adding `exploding` on the given condition.
+    pub fn new(exploding_condition: ExplodingCondition, exploding_range: V, exploding: P) -> Self {
+        ExplodeInto {
+            exploding_condition,
+            exploding_range,
+            exploding,
+        }
+    }
+}
+
+impl<V, P> Mechanic<V, P> for ExplodeInto<V, P>
+where
+    P: Clone + NormalInitializer<V, P> + ProbabilityDistribution<V>,
+    V: Copy + Ord + From<i32>,
+{
+    fn apply(&self, pool: &[P]) -> P {
+        let explode = exploding_helper(
+            self.exploding_range,
+            self.exploding_condition,
+            self.exploding.clone(),
+        );
+        pool.iter()
+            .map(|die| die.add_dependent(&explode))
+            .reduce(|sum, die| sum.add_independent(&die))
+            .unwrap_or_else(P::empty)
+    }
+}
+
+/// Rerolls every die of the pool whose result fulfills `should_reroll`, then sums up the pool.
+///
+/// Rerolls only once if `once` is set, otherwise rerolls until the result doesn't fulfill the
+/// condition anymore. Dice which would always be rerolled are kept as they are. Rerolling until
+/// the condition fails is calculated in closed form, so unlike [`ExplodeDice::unbounded`] it
+/// needs no tolerance and nothing gets truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RerollDice<F> {
+    should_reroll: F,
+    once: bool,
+}
+
+impl<F> RerollDice<F>
+where
+    F: Fn(&i32) -> bool,
+{
+    /// Creates a new mechanic rerolling results fulfilling `should_reroll`.
+    pub fn new(should_reroll: F, once: bool) -> Self {
+        RerollDice {
+            should_reroll,
+            once,
+        }
+    }
+
+    fn reroll(&self, die: &Die) -> Die {
+        if self.once {
+            return die.conditional_chain(&mut |&value| {
+                if (self.should_reroll)(&value) {
+                    die.clone()
+                } else {
+                    Die::from(value)
+                }
+            });
+        }
+        let kept: Vec<_> = die
+            .get_probabilities()
+            .iter()
+            .filter(|prob| !(self.should_reroll)(&prob.value))
+            .copied()
+            .collect();
+        if kept.is_empty() {
+            return die.clone();
+        }
+        let total = kept.iter().fold(0.0, |acc, prob| acc + prob.chance);
+        Die::from_probabilities(
+            kept.into_iter()
+                .map(|prob| Probability {
+                    value: prob.value,
+                    chance: prob.chance / total,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<F> Mechanic for RerollDice<F>
+where
+    F: Fn(&i32) -> bool,
+{
+    fn apply(&self, pool: &[Die]) -> Die {
+        Die::sum_all(&pool.iter().map(|die| self.reroll(die)).collect::<Vec<_>>())
+    }
+}
+
+/// Counts how many dice of the pool roll a success, as decided by `is_success`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountSuccesses<F> {
+    is_success: F,
+}
+
+impl<F> CountSuccesses<F>
+where
+    F: Fn(&i32) -> bool,
+{
+    /// Creates a new mechanic counting results fulfilling `is_success`.
+    pub fn new(is_success: F) -> Self {
+        CountSuccesses { is_success }
+    }
+}
+
+impl<F> Mechanic for CountSuccesses<F>
+where
+    F: Fn(&i32) -> bool,
+{
+    fn apply(&self, pool: &[Die]) -> Die {
+        Die::sum_all(
+            &pool
+                .iter()
+                .map(|die| {
+                    die.conditional_chain(&mut |value| Die::from((self.is_success)(value) as i32))
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Counts how many different values show up among the dice of the pool, like for set
+/// collection mechanics.
+///
+/// Pools of identical dice are handled face by face, splitting the remaining dice binomially,
+/// while mixed pools keep track of every set of values seen so far.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ CountDistinct, Die, Mechanic, NormalInitializer, ProbabilityDistribution };
+/// let distinct = CountDistinct.apply(&vec![Die::new(6); 3]);
+/// assert_eq!(distinct.get_min(), 1);
+/// assert_eq!(distinct.get_max(), 3);
+/// assert!((distinct.get_probabilities()[2].chance - 120.0 / 216.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CountDistinct;
+
+impl CountDistinct {
+    /// Splits the dice among the faces one at a time, each taking a binomial share of the dice
+    /// still left, tracking the remaining dice and the faces that got at least one.
+    fn count_identical(die: &Die, amount: usize) -> Die {
+        let probabilities = die.get_probabilities();
+        // chances of (remaining dice, distinct faces)
+        let mut states = HashMap::from([((amount as u32, 0), 1.0)]);
+        let mut remaining_chance = 1.0;
+        for (index, prob) in probabilities.iter().enumerate() {
+            // the last face takes every die left, regardless of rounding errors
+            let share = if index + 1 == probabilities.len() {
+                1.0
+            } else {
+                (prob.chance / remaining_chance).clamp(0.0, 1.0)
+            };
+            remaining_chance -= prob.chance;
+            let mut next = HashMap::with_capacity(states.len());
+            for ((remaining, distinct), chance) in states {
+                for taken in Die::binomial(remaining, share).get_probabilities() {
+                    let seen = distinct + i32::from(taken.value > 0);
+                    *next
+                        .entry((remaining - taken.value as u32, seen))
+                        .or_insert(0.0) += chance * taken.chance;
+                }
+            }
+            states = next;
+        }
+        let mut accumulated = HashMap::new();
+        for ((_, distinct), chance) in states {
+            *accumulated.entry(distinct).or_insert(0.0) += chance;
+        }
+        from_accumulated(accumulated)
+    }
+}
+
+impl Mechanic for CountDistinct {
+    fn apply(&self, pool: &[Die]) -> Die {
+        let Some(first) = pool.first() else {
+            return Die::empty();
+        };
+        let identical = pool.iter().all(|die| {
+            die.get_probabilities().len() == first.get_probabilities().len()
+                && die
+                    .get_probabilities()
+                    .iter()
+                    .zip(first.get_probabilities())
+                    .all(|(a, b)| a.value == b.value && a.chance == b.chance)
+        });
+        if identical {
+            CountDistinct::count_identical(first, pool.len())
+        } else {
+            from_accumulated(seen_values(pool).into_iter().fold(
+                HashMap::new(),
+                |mut accumulated, (seen, chance)| {
+                    *accumulated.entry(seen.len() as i32).or_insert(0.0) += chance;
+                    accumulated
+                },
+            ))
+        }
+    }
+}
+
+/// Finds the longest run of consecutive values among the dice of the pool, like a straight in
+/// Yahtzee. Repeated values don't break or extend a run.
+///
+/// See [`PoolBuilder::straight_chance`][`crate::PoolBuilder::straight_chance`] for the chance of
+/// a run of at least a given length.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, LongestRun, Mechanic, NormalInitializer, ProbabilityDistribution };
+/// let longest = LongestRun.apply(&vec![Die::new(6); 5]);
+/// assert_eq!(longest.get_max(), 5);
+/// // large straight, 1-5 or 2-6 in any order
+/// assert!((longest.get_probabilities()[4].chance - 240.0 / 7776.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LongestRun;
+
+impl Mechanic for LongestRun {
+    fn apply(&self, pool: &[Die]) -> Die {
+        if pool.is_empty() {
+            return Die::empty();
+        }
+        let mut accumulated = HashMap::new();
+        for (seen, chance) in seen_values(pool) {
+            let mut longest = 0;
+            let mut current = 0;
+            let mut previous: Option<i32> = None;
+            for &value in &seen {
+                current = match previous {
+                    Some(previous) if previous.checked_add(1) == Some(value) => current + 1,
+                    _ => 1,
+                };
+                longest = longest.max(current);
+                previous = Some(value);
+            }
+            *accumulated.entry(longest).or_insert(0.0) += chance;
+        }
+        from_accumulated(accumulated)
+    }
+}
+
+/// Rolls the dice of the pool one at a time, tracking the chance of every sorted set of values
+/// seen so far.
+fn seen_values(pool: &[Die]) -> HashMap<Vec<i32>, f64> {
+    let mut states: HashMap<Vec<i32>, f64> = HashMap::from([(Vec::new(), 1.0)]);
+    for die in pool {
+        let mut next = HashMap::with_capacity(states.len());
+        for (seen, chance) in &states {
+            for prob in die.get_probabilities() {
+                let mut seen = seen.clone();
+                if let Err(index) = seen.binary_search(&prob.value) {
+                    seen.insert(index, prob.value);
+                }
+                *next.entry(seen).or_insert(0.0) += chance * prob.chance;
+            }
+        }
+        states = next;
+    }
+    states
+}
+
+fn from_accumulated(accumulated: HashMap<i32, f64>) -> Die {
+    Die::from_probabilities(
+        accumulated
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
+            .collect(),
+    )
+}
+
+/// Applies `each` to every die of the pool on its own, then `then` to the resulting pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerDie<A, B> {
+    each: A,
+    then: B,
+}
+
+impl<A, B> PerDie<A, B>
+where
+    A: Mechanic,
+    B: Mechanic,
+{
+    /// Creates a new mechanic applying `each` to every die before applying `then`.
+    pub fn new(each: A, then: B) -> Self {
+        PerDie { each, then }
+    }
+}
+
+impl<A, B> Mechanic for PerDie<A, B>
+where
+    A: Mechanic,
+    B: Mechanic,
+{
+    fn apply(&self, pool: &[Die]) -> Die {
+        self.then.apply(
+            &pool
+                .iter()
+                .map(|die| self.each.apply(std::slice::from_ref(die)))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Collection of named [mechanics][`Mechanic`], so systems can be looked up and applied by name.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, DropType, KeepDice, MechanicRegistry, NormalInitializer, ProbabilityDistribution };
+/// let mut registry = MechanicRegistry::new();
+/// registry.register("advantage", KeepDice::new(1, DropType::High));
+/// let advantage = registry.apply("advantage", &[Die::new(20), Die::new(20)]).unwrap();
+/// assert!((advantage.get_mean() - 13.825).abs() < 1e-9);
+/// assert!(registry.apply("disadvantage", &[Die::new(20)]).is_none());
+/// ```
+#[derive(Default)]
+pub struct MechanicRegistry {
+    mechanics: HashMap<String, Box<dyn Mechanic>>,
+}
+
+impl MechanicRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the given mechanic under `name`, replacing any previous one.
+    pub fn register<M>(&mut self, name: &str, mechanic: M)
+    where
+        M: Mechanic + 'static,
+    {
+        self.mechanics.insert(name.to_string(), Box::new(mechanic));
+    }
+
+    /// Returns the mechanic registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&dyn Mechanic> {
+        self.mechanics.get(name).map(|mechanic| mechanic.as_ref())
+    }
+
+    /// Applies the mechanic registered under `name` to the given pool, if there is one.
+    pub fn apply(&self, name: &str, pool: &[Die]) -> Option<Die> {
+        self.get(name).map(|mechanic| mechanic.apply(pool))
+    }
+
+    /// Returns the names of all registered mechanics, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.mechanics.keys().map(String::as_str)
+    }
+}
+
+impl std::fmt::Debug for MechanicRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MechanicRegistry")
+            .field("mechanics", &self.mechanics.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{assert_same_chances, ALLOWED_ERROR};
+    use crate::{DropInitializer, ExplodingInitializer};
+
+    #[test]
+    fn mechanics() {
+        let pool = vec![Die::new(6); 2];
+        assert_same_chances(
+            SumDice.apply(&pool).get_probabilities(),
+            (&Die::new(6) + &Die::new(6)).get_probabilities(),
+        );
+        assert_same_chances(
+            KeepDice::new(1, DropType::Low)
+                .apply(&pool)
+                .get_probabilities(),
+            DropDice::new(1, DropType::High)
+                .apply(&pool)
+                .get_probabilities(),
+        );
+        let exploding = ExplodeDice::new(ExplodingCondition::Equal, 6, 1).apply(&[Die::new(6)]);
+        assert_eq!(exploding.get_max(), 12);
+        assert!((exploding.get_mean() - 3.5 * 7.0 / 6.0).abs() < ALLOWED_ERROR);
+        assert_eq!(exploding.truncated_mass(), 0.0);
+        let unbounded = ExplodeDice::unbounded(ExplodingCondition::Equal, 2, 0.3)
+            .apply(&[Die::new(2), Die::new(2)]);
+        assert_eq!(unbounded.get_max(), 6);
+        assert!((unbounded.truncated_mass() - (1.0 - 0.75 * 0.75)).abs() < 1e-12);
+        assert!(unbounded.is_approximate());
+        let rerolled = RerollDice::new(|&value| value < 3, false).apply(&[Die::new(6)]);
+        assert_same_chances(
+            rerolled.get_probabilities(),
+            Die::from_values(&[3, 4, 5, 6]).get_probabilities(),
+        );
+        let rerolled_once = RerollDice::new(|&value| value == 1, true).apply(&[Die::new(4)]);
+        assert!((rerolled_once.get_mean() - 2.875).abs() < ALLOWED_ERROR);
+        assert_same_chances(
+            RerollDice::new(|_: &i32| true, false)
+                .apply(&[Die::new(4)])
+                .get_probabilities(),
+            Die::new(4).get_probabilities(),
+        );
+        assert_same_chances(
+            CountSuccesses::new(|&value| value >= 5)
+                .apply(&vec![Die::new(6); 4])
+                .get_probabilities(),
+            Die::binomial(4, 1.0 / 3.0).get_probabilities(),
+        );
+
+        let identical = CountDistinct.apply(&vec![Die::new(4); 3]);
+        let mixed = CountDistinct.apply(&[Die::new(4), Die::new(4), Die::from_range(1, 4)]);
+        for (identical, mixed) in identical
+            .get_probabilities()
+            .iter()
+            .zip(mixed.get_probabilities())
+        {
+            assert_eq!(identical.value, mixed.value);
+            assert!((identical.chance - mixed.chance).abs() < 1e-12);
+        }
+        // 4 of the 64 rolls show a single value, 24 show three different ones
+        assert!((identical.get_probabilities()[0].chance - 4.0 / 64.0).abs() < 1e-12);
+        assert!((identical.get_probabilities()[2].chance - 24.0 / 64.0).abs() < 1e-12);
+        let weighted = CountDistinct.apply(&vec![Die::from_values(&[1, 1, 1, 2]); 2]);
+        assert!((weighted.get_probabilities()[0].chance - 0.625).abs() < 1e-12);
+        assert_same_chances(
+            CountDistinct
+                .apply(&[Die::new(2), Die::from_range(3, 4)])
+                .get_probabilities(),
+            Die::from(2).get_probabilities(),
+        );
+        assert_same_chances(
+            CountDistinct.apply(&[]).get_probabilities(),
+            Die::empty().get_probabilities(),
+        );
+
+        let runs = LongestRun.apply(&[Die::new(2), Die::from_range(3, 4), Die::from(10)]);
+        assert_same_chances(
+            runs.get_probabilities(),
+            Die::from_values(&[1, 1, 1, 2]).get_probabilities(),
+        );
+        assert_eq!(LongestRun.apply(&vec![Die::new(6); 2]).get_max(), 2);
+        assert_same_chances(
+            LongestRun.apply(&[]).get_probabilities(),
+            Die::empty().get_probabilities(),
+        );
+    }
+
+    #[test]
+    fn initializers() {
+        let pool = vec![Die::new(6); 4];
+        assert_same_chances(
+            Die::new_drop(6, 4, 1, DropType::Low).get_probabilities(),
+            DropDice::new(1, DropType::Low)
+                .apply(&pool)
+                .get_probabilities(),
+        );
+        assert_same_chances(
+            Die::keep_from_pool(&pool, 3, DropType::High).get_probabilities(),
+            KeepDice::new(3, DropType::High)
+                .apply(&pool)
+                .get_probabilities(),
+        );
+        assert_same_chances(
+            Die::new_exploding(6, 6, ExplodingCondition::Equal, Die::new(6)).get_probabilities(),
+            ExplodeDice::new(ExplodingCondition::Equal, 6, 1)
+                .apply(&[Die::new(6)])
+                .get_probabilities(),
+        );
+        assert_same_chances(
+            ExplodeInto::new(ExplodingCondition::Equal, 2, Die::new(2))
+                .apply(&pool[..0])
+                .get_probabilities(),
+            Die::empty().get_probabilities(),
+        );
+    }
+
+    #[test]
+    fn per_die() {
+        let successes = PerDie::new(
+            RerollDice::new(|&value| value == 1, false),
+            CountSuccesses::new(|&value| value == 2),
+        )
+        .apply(&vec![Die::new(2); 3]);
+        assert_same_chances(
+            successes.get_probabilities(),
+            Die::from(3).get_probabilities(),
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = MechanicRegistry::new();
+        registry.register("sum", SumDice);
+        registry.register("first", |pool: &[Die]| pool[0].clone());
+        let mut names: Vec<_> = registry.names().collect();
+        names.sort();
+        assert_eq!(names, vec!["first", "sum"]);
+        assert_same_chances(
+            registry
+                .apply("first", &[Die::new(4), Die::new(6)])
+                .unwrap()
+                .get_probabilities(),
+            Die::new(4).get_probabilities(),
+        );
+        assert!(registry.get("sum").is_some());
+        assert!(format!("{registry:?}").contains("sum"));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::common::*;
+use crate::{Die, FormatOptions, ProbabilityDistribution};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// Statistics of a single modifier of a [`ModifierSweep`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepRow {
+    /// The flat modifier applied to the base die.
+    pub modifier: i32,
+    /// Mean of the modified die.
+    pub mean: f64,
+    /// Smallest value of the modified die with a cumulative chance of at least `0.5`.
+    pub median: i32,
+    /// Chance of the modified die to roll at least the target.
+    pub success_chance: f64,
+}
+
+/// Table of statistics for a base die with a range of flat modifiers applied, like evaluating how
+/// much a `+1` to `+5` buff helps against a certain DC.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, ModifierSweep, NormalInitializer };
+/// let sweep = ModifierSweep::new(&Die::new(20), 0..=2, 15);
+/// assert_eq!(sweep.rows().len(), 3);
+/// assert_eq!(sweep.rows()[2].median, 12);
+/// assert!((sweep.rows()[2].success_chance - 0.4).abs() < 1e-9);
+/// assert!(sweep.to_csv().starts_with("modifier,mean,median,at_least_15\n0,10.5,"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModifierSweep {
+    target: i32,
+    rows: Vec<SweepRow>,
+}
+
+impl ModifierSweep {
+    /// Applies every modifier of the range to the given die and collects the statistics against
+    /// `target`.
+    pub fn new(die: &Die, modifiers: RangeInclusive<i32>, target: i32) -> Self {
+        ModifierSweep {
+            target,
+            rows: modifiers
+                .map(|modifier| {
+                    let modified = die.add_flat(modifier);
+                    SweepRow {
+                        modifier,
+                        mean: modified.get_mean(),
+                        median: modified
+                            .cdf_iter()
+                            .find(|&(_, cumulative)| cumulative >= 0.5 - ALLOWED_ERROR)
+                            .map_or(0, |(value, _)| value),
+                        success_chance: modified.success_chances([target])[0].1,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the target the success chances were calculated against.
+    pub fn target(&self) -> i32 {
+        self.target
+    }
+
+    /// Returns one row per modifier, in the order of the range.
+    pub fn rows(&self) -> &[SweepRow] {
+        &self.rows
+    }
+
+    /// Renders the table as CSV with a header row, using plain chances instead of percentages.
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("modifier,mean,median,at_least_{}\n", self.target);
+        for row in &self.rows {
+            let _ = writeln!(
+                out,
+                "{},{},{},{}",
+                row.modifier, row.mean, row.median, row.success_chance
+            );
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for ModifierSweep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let FormatOptions {
+            number_width,
+            decimals,
+            ..
+        } = FormatOptions::default();
+        writeln!(
+            f,
+            "{:>number_width$} : {:>number_width$} : {:>number_width$} : {:>number_width$}",
+            "Modifier",
+            "Mean",
+            "Median",
+            format!(">= {}", self.target)
+        )?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{:>+number_width$} : {:>number_width$.decimals$} : {:>number_width$} : {:>number_width$.decimals$}",
+                row.modifier,
+                row.mean,
+                row.median,
+                row.success_chance * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn sweeping() {
+        let sweep = ModifierSweep::new(&Die::new(4), -1..=1, 4);
+        assert_eq!(sweep.target(), 4);
+        let medians: Vec<_> = sweep.rows().iter().map(|row| row.median).collect();
+        assert_eq!(medians, vec![1, 2, 3]);
+        let chances: Vec<_> = sweep.rows().iter().map(|row| row.success_chance).collect();
+        assert_eq!(chances, vec![0.0, 0.25, 0.5]);
+        assert_eq!(
+            sweep.to_csv(),
+            "modifier,mean,median,at_least_4\n-1,1.5,1,0\n0,2.5,2,0.25\n1,3.5,3,0.5\n"
+        );
+        assert_eq!(sweep.to_string().lines().count(), 4);
+        assert!(sweep.to_string().lines().nth(3).unwrap().contains("+1"));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::common::values_to_probabilities;
-use crate::Probability;
+use crate::step::step_range;
+use crate::{Probability, Step};
 use core::cmp::Ordering;
+use core::ops::RangeInclusive;
 
 /// Extended initializer for [probability distributions][`crate::ProbabilityDistribution`].
 pub trait NormalInitializer<T, P: NormalInitializer<T, P>> {
@@ -21,23 +23,52 @@ pub trait NormalInitializer<T, P: NormalInitializer<T, P>> {
     /// with `n` being the amount of values.
     fn from_range(start: T, end: T) -> P
     where
-        T: Copy + Ord + From<i32>,
-        i32: From<T>,
+        T: Step,
     {
         match end.cmp(&start) {
             std::cmp::Ordering::Less => Self::from_range(end, start),
-            _ => {
-                let converted_start: i32 = start.into();
-                let converted_end: i32 = end.into();
-                Self::from_values(
-                    &(converted_start..=converted_end)
-                        .map(|val| val.into())
-                        .collect::<Vec<T>>(),
-                )
-            }
+            _ => Self::from_values(&step_range(start, end)),
         }
     }
 
+    /// Creates a new distribution of type `P` over the given range, with chances proportional to
+    /// the weight returned by `weight_fn` for each value. The weights get normalized, so they
+    /// don't need to add up to anything specific. Values with a weight of `0` or less are left
+    /// out.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, Probability, ProbabilityDistribution, NormalInitializer };
+    /// let triangular = Die::from_fn(1..=3, |&value| f64::from(value));
+    /// assert_eq!(
+    ///     triangular,
+    ///     Die::from_values(&[1, 2, 2, 3, 3, 3])
+    /// );
+    /// assert!((triangular.get_chance(3) - 0.5).abs() < 1e-12);
+    /// ```
+    fn from_fn<F>(range: RangeInclusive<T>, weight_fn: F) -> P
+    where
+        T: Step,
+        F: Fn(&T) -> f64,
+    {
+        let (start, end) = range.into_inner();
+        let weighted: Vec<(T, f64)> = step_range(start, end)
+            .into_iter()
+            .map(|value| (value, weight_fn(&value)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        let total = weighted.iter().fold(0.0, |acc, (_, weight)| acc + weight);
+        Self::from_probabilities(
+            weighted
+                .into_iter()
+                .map(|(value, weight)| Probability {
+                    value,
+                    chance: weight / total,
+                })
+                .collect(),
+        )
+    }
+
     /// Creates a new distribution of type `P` from the given values. Each value gets an equal
     /// amount of chance, but also compresses identical values to a singular
     /// [`probability`][`Probability`], to be specific `m/n` with `m` being the amount of times
@@ -52,15 +83,24 @@ pub trait NormalInitializer<T, P: NormalInitializer<T, P>> {
     /// Creates a new distribution of type `P` from the equivalent of the first value up to, and
     /// including, the given size. Gives every value created this way an equal amount of chance, to
     /// be specific `1/n` with `n` being the amount of values in the given range.
+    #[allow(clippy::new_ret_no_self)]
     fn new(size: T) -> P
     where
-        T: Copy + Ord + From<i32>,
-        i32: From<T>,
+        T: Step + From<i32>,
     {
-        match size.cmp(&0.into()) {
-            Ordering::Less => Self::from_range(size, (-1).into()),
-            Ordering::Equal => Self::empty(),
-            Ordering::Greater => Self::from_range(1.into(), size),
-        }
+        new_uniform(size)
+    }
+}
+
+/// Default implementation of [`NormalInitializer::new`], for implementors overriding it.
+pub(crate) fn new_uniform<T, P>(size: T) -> P
+where
+    P: NormalInitializer<T, P>,
+    T: Step + From<i32>,
+{
+    match size.cmp(&0.into()) {
+        Ordering::Less => P::from_range(size, (-1).into()),
+        Ordering::Equal => P::empty(),
+        Ordering::Greater => P::from_range(1.into(), size),
     }
 }
@@ -0,0 +1,66 @@
+/// Decides what happens when adding up values leaves the range of `i32`.
+///
+/// Only the `try_*` methods of [`Die`][`crate::Die`], like
+/// [`try_add_flat`][`crate::Die::try_add_flat`], take a policy. Everything else, including the
+/// operators and initializers, always saturates. To keep such values instead, switch to a
+/// [`WideDie`][`crate::WideDie`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Clamps the value to `i32::MIN` or `i32::MAX` respectively.
+    #[default]
+    Saturate,
+    /// Stops the calculation and returns an [`OverflowError`].
+    Error,
+}
+
+impl OverflowPolicy {
+    /// Converts the given, possibly out of range, value back into an `i32`.
+    pub(crate) fn apply(self, value: i64) -> Result<i32, OverflowError> {
+        match (self, i32::try_from(value)) {
+            (_, Ok(value)) => Ok(value),
+            (OverflowPolicy::Saturate, Err(_)) => {
+                Ok(value.clamp(i32::MIN.into(), i32::MAX.into()) as i32)
+            }
+            (OverflowPolicy::Error, Err(_)) => Err(OverflowError { value }),
+        }
+    }
+
+    /// Adds up both values according to this policy.
+    pub(crate) fn add(self, lhs: i32, rhs: i32) -> Result<i32, OverflowError> {
+        self.apply(i64::from(lhs) + i64::from(rhs))
+    }
+}
+
+/// Error returned when a value leaves the range of `i32` under [`OverflowPolicy::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    /// The value that could not be represented.
+    pub value: i64,
+}
+
+impl std::fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value `{}` does not fit into an i32", self.value)
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policies() {
+        assert_eq!(OverflowPolicy::Saturate.add(1, 2), Ok(3));
+        assert_eq!(OverflowPolicy::Saturate.add(i32::MAX, 1), Ok(i32::MAX));
+        assert_eq!(OverflowPolicy::Saturate.add(i32::MIN, -1), Ok(i32::MIN));
+        assert_eq!(OverflowPolicy::Error.add(1, 2), Ok(3));
+        assert_eq!(
+            OverflowPolicy::Error.add(i32::MAX, 1),
+            Err(OverflowError {
+                value: i64::from(i32::MAX) + 1
+            })
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{OverflowError, OverflowPolicy, Probability};
+use std::collections::BTreeMap;
+
+/// Distribution over integers stored as a sparse polynomial, with each value being an exponent
+/// and its chance the coefficient.
+///
+/// Adding independent distributions is the same as multiplying their polynomials, which this
+/// does directly on the sorted terms instead of going through every pair and compressing after.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SparsePolynomial {
+    terms: Vec<(i32, f64)>,
+}
+
+impl SparsePolynomial {
+    /// The polynomial `1`, meaning a certain `0`.
+    pub(crate) fn one() -> Self {
+        SparsePolynomial {
+            terms: vec![(0, 1.0)],
+        }
+    }
+
+    pub(crate) fn from_probabilities(probabilities: &[Probability<i32>]) -> Self {
+        let mut terms = BTreeMap::new();
+        for prob in probabilities {
+            *terms.entry(prob.value).or_insert(0.0) += prob.chance;
+        }
+        SparsePolynomial {
+            terms: terms.into_iter().collect(),
+        }
+    }
+
+    pub(crate) fn into_probabilities(self) -> Vec<Probability<i32>> {
+        self.terms
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
+            .collect()
+    }
+
+    pub(crate) fn multiply(
+        &self,
+        other: &Self,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Self, OverflowError> {
+        let (Some(&(min, _)), Some(&(max, _)), Some(&(other_min, _)), Some(&(other_max, _))) = (
+            self.terms.first(),
+            self.terms.last(),
+            other.terms.first(),
+            other.terms.last(),
+        ) else {
+            return Ok(SparsePolynomial { terms: Vec::new() });
+        };
+        let lowest = i64::from(min) + i64::from(other_min);
+        let highest = i64::from(max) + i64::from(other_max);
+        let width = (highest - lowest + 1) as usize;
+        let in_range = lowest >= i64::from(i32::MIN) && highest <= i64::from(i32::MAX);
+
+        // dense accumulation is cheaper as long as the result isn't mostly gaps
+        if in_range && width <= 4 * self.terms.len() * other.terms.len() {
+            let mut coefficients = vec![None; width];
+            for &(value, chance) in &self.terms {
+                for &(other_value, other_chance) in &other.terms {
+                    let index = (i64::from(value) + i64::from(other_value) - lowest) as usize;
+                    *coefficients[index].get_or_insert(0.0) += chance * other_chance;
+                }
+            }
+            Ok(SparsePolynomial {
+                terms: coefficients
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, coefficient)| {
+                        coefficient.map(|chance| ((lowest + index as i64) as i32, chance))
+                    })
+                    .collect(),
+            })
+        } else {
+            let mut terms = BTreeMap::new();
+            for &(value, chance) in &self.terms {
+                for &(other_value, other_chance) in &other.terms {
+                    *terms
+                        .entry(overflow_policy.add(value, other_value)?)
+                        .or_insert(0.0) += chance * other_chance;
+                }
+            }
+            Ok(SparsePolynomial {
+                terms: terms.into_iter().collect(),
+            })
+        }
+    }
+
+    /// Raises the polynomial to the `n`th power via repeated squaring.
+    pub(crate) fn power(
+        &self,
+        n: usize,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Self, OverflowError> {
+        let mut result = SparsePolynomial::one();
+        let mut squared = self.clone();
+        let mut remaining = n;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result.multiply(&squared, overflow_policy)?;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                squared = squared.multiply(&squared, overflow_policy)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polynomial(terms: &[(i32, f64)]) -> SparsePolynomial {
+        SparsePolynomial {
+            terms: terms.to_vec(),
+        }
+    }
+
+    #[test]
+    fn multiplying() {
+        let coin = polynomial(&[(0, 0.5), (1, 0.5)]);
+        let policy = OverflowPolicy::Error;
+        assert_eq!(
+            coin.multiply(&coin, policy),
+            Ok(polynomial(&[(0, 0.25), (1, 0.5), (2, 0.25)]))
+        );
+        let sparse = polynomial(&[(0, 0.5), (1000, 0.5)]);
+        assert_eq!(
+            sparse.multiply(&polynomial(&[(-1, 1.0)]), policy),
+            Ok(polynomial(&[(-1, 0.5), (999, 0.5)]))
+        );
+        assert_eq!(
+            sparse.multiply(&polynomial(&[]), policy),
+            Ok(polynomial(&[]))
+        );
+    }
+
+    #[test]
+    fn overflowing() {
+        let huge = polynomial(&[(0, 0.5), (i32::MAX, 0.5)]);
+        assert_eq!(
+            huge.multiply(&huge, OverflowPolicy::Saturate),
+            Ok(polynomial(&[(0, 0.25), (i32::MAX, 0.75)]))
+        );
+        assert!(huge.multiply(&huge, OverflowPolicy::Error).is_err());
+    }
+
+    #[test]
+    fn power() {
+        let coin = polynomial(&[(0, 0.5), (1, 0.5)]);
+        let policy = OverflowPolicy::Error;
+        assert_eq!(coin.power(0, policy), Ok(SparsePolynomial::one()));
+        assert_eq!(coin.power(1, policy), Ok(coin.clone()));
+        assert_eq!(
+            coin.power(3, policy),
+            Ok(polynomial(&[
+                (0, 0.125),
+                (1, 0.375),
+                (2, 0.375),
+                (3, 0.125)
+            ]))
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use crate::die::{describe_grouped, describe_repeat};
+use crate::streaming::Outcomes;
+use crate::{
+    CountDistinct, CountSuccesses, Die, DropInitializer, DropType, LongestRun, Mechanic,
+    NormalInitializer, Probability, ProbabilityDistribution,
+};
+use core::ops::ControlFlow;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Builder for pools made up of different [dice][`Die`] and a flat modifier.
+///
+/// Collects the dice first and decides how to evaluate them afterwards, so the same pool can be
+/// summed, reduced via keep/drop or counted for successes.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, DropType, NormalInitializer, PoolBuilder, ProbabilityDistribution };
+/// // 2d6 + 1d8 + 3
+/// let pool = PoolBuilder::new()
+///     .add(2, Die::new(6))
+///     .add(1, Die::new(8))
+///     .plus(3);
+/// assert_eq!(pool.sum().get_min(), 6);
+/// assert_eq!(pool.sum().get_max(), 23);
+/// assert_eq!(pool.keep(1, DropType::High).get_max(), 11);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PoolBuilder {
+    dice: Vec<Die>,
+    modifier: i32,
+}
+
+impl PoolBuilder {
+    /// Creates an empty pool without any dice and a modifier of `0`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `amount` copies of the given die to the pool.
+    pub fn add(mut self, amount: usize, die: Die) -> Self {
+        self.dice.extend(std::iter::repeat_n(die, amount));
+        self
+    }
+
+    /// Adds a flat amount to the modifier of the pool.
+    pub fn plus(mut self, modifier: i32) -> Self {
+        self.modifier += modifier;
+        self
+    }
+
+    /// Returns the dice currently in the pool.
+    pub fn dice(&self) -> &[Die] {
+        &self.dice
+    }
+
+    /// Returns the flat modifier of the pool.
+    pub fn modifier(&self) -> i32 {
+        self.modifier
+    }
+
+    /// Sums up every die in the pool and adds the modifier.
+    pub fn sum(&self) -> Die {
+        Die::sum_all(&self.dice)
+            .with_described(self.describe_dice())
+            .add_flat(self.modifier)
+    }
+
+    /// Keeps `keep_amount` dice from the specified end, sums them and adds the modifier.
+    pub fn keep(&self, keep_amount: usize, keep_condition: DropType) -> Die {
+        Die::keep_from_pool(&self.dice, keep_amount, keep_condition)
+            .with_described(self.describe_reduced("keep", keep_amount, keep_condition))
+            .add_flat(self.modifier)
+    }
+
+    /// Drops `drop_amount` dice from the specified end, sums the rest and adds the modifier.
+    pub fn drop(&self, drop_amount: usize, drop_condition: DropType) -> Die {
+        Die::drop_from_pool(&self.dice, drop_amount, drop_condition)
+            .with_described(self.describe_reduced("drop", drop_amount, drop_condition))
+            .add_flat(self.modifier)
+    }
+
+    /// Counts how many dice in the pool roll a success, as decided by `is_success`.
+    ///
+    /// The modifier is not applied, since it does not belong to any single die.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, PoolBuilder, ProbabilityDistribution };
+    /// let successes = PoolBuilder::new()
+    ///     .add(2, Die::new(6))
+    ///     .count_successes(&|&value| value >= 5);
+    /// assert_eq!(successes.get_max(), 2);
+    /// ```
+    pub fn count_successes<F>(&self, is_success: &F) -> Die
+    where
+        F: Fn(&i32) -> bool,
+    {
+        CountSuccesses::new(is_success).apply(&self.dice)
+    }
+
+    /// Counts how many different values show up among the dice in the pool, see
+    /// [`CountDistinct`].
+    ///
+    /// The modifier is not applied, since it does not belong to any single die.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, PoolBuilder, ProbabilityDistribution };
+    /// let distinct = PoolBuilder::new().add(5, Die::new(6)).plus(2).count_distinct();
+    /// assert_eq!(distinct.get_max(), 5);
+    /// ```
+    pub fn count_distinct(&self) -> Die {
+        CountDistinct.apply(&self.dice)
+    }
+
+    /// Returns the chance of the dice in the pool showing a run of at least `length`
+    /// consecutive values, like a small straight of `4` in Yahtzee. See [`LongestRun`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, PoolBuilder };
+    /// let yahtzee = PoolBuilder::new().add(5, Die::new(6));
+    /// assert!((yahtzee.straight_chance(5) - 240.0 / 7776.0).abs() < 1e-9);
+    /// assert!((yahtzee.straight_chance(4) - 1200.0 / 7776.0).abs() < 1e-9);
+    /// ```
+    pub fn straight_chance(&self, length: usize) -> f64 {
+        if length == 0 {
+            return 1.0;
+        }
+        let longest = LongestRun.apply(&self.dice);
+        longest
+            .get_probabilities()
+            .iter()
+            .filter(|prob| prob.value as usize >= length)
+            .map(|prob| prob.chance)
+            .sum::<f64>()
+            .min(1.0)
+    }
+
+    /// Evaluates the pool via the given [mechanic][`Mechanic`] and adds the modifier.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, DropType, KeepDice, NormalInitializer, PoolBuilder, ProbabilityDistribution };
+    /// let pool = PoolBuilder::new().add(2, Die::new(20)).plus(5);
+    /// assert_eq!(pool.apply(&KeepDice::new(1, DropType::High)), pool.keep(1, DropType::High));
+    /// ```
+    pub fn apply(&self, mechanic: &impl Mechanic) -> Die {
+        mechanic.apply(&self.dice).add_flat(self.modifier)
+    }
+
+    /// Iterates over every combination of rolls of the pool, reducing the rolled values, in the
+    /// order the dice were added, via `reduce` and adding the modifier.
+    ///
+    /// Never builds the distribution itself, so combined with
+    /// [`StreamingStats`][`crate::StreamingStats`] the statistics of pools with an enormous
+    /// support can be computed in constant memory. The amount of combinations still grows
+    /// exponentially with the size of the pool.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, PoolBuilder, StreamingStats };
+    /// let pool = PoolBuilder::new().add(3, Die::new(6));
+    /// let highest: StreamingStats = pool.outcomes(|rolled| *rolled.iter().max().unwrap()).collect();
+    /// assert!((highest.mean() - 4.958).abs() < 1e-3);
+    /// ```
+    pub fn outcomes<F>(&self, reduce: F) -> Outcomes<'_, F>
+    where
+        F: Fn(&[i32]) -> i32,
+    {
+        Outcomes::new(&self.dice, self.modifier, reduce)
+    }
+
+    /// Rolls the dice of the pool one after another in the order they were added, folding every
+    /// roll into a running total via `step`, starting at `init`, and adds the modifier to the
+    /// final total.
+    ///
+    /// Returning [`ControlFlow::Break`] from `step` stops rolling with the given total, leaving
+    /// the remaining dice unrolled, like busting on a 1 in push-your-luck games. Rolls leading to
+    /// the same total are merged before the next die, so this stays cheap even for large pools.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, PoolBuilder, ProbabilityDistribution };
+    /// use std::ops::ControlFlow;
+    /// // sum up to three d6, but rolling a 1 busts everything
+    /// let pool = PoolBuilder::new().add(3, Die::new(6));
+    /// let pig = pool.fold(0, |total, rolled| match rolled {
+    ///     1 => ControlFlow::Break(0),
+    ///     _ => ControlFlow::Continue(total + rolled),
+    /// });
+    /// assert!((pig.get_chance(0) - 91.0 / 216.0).abs() < 1e-9);
+    /// assert_eq!(pig.get_max(), 18);
+    /// ```
+    pub fn fold<F>(&self, init: i32, step: F) -> Die
+    where
+        F: Fn(i32, i32) -> ControlFlow<i32, i32>,
+    {
+        let mut running: HashMap<i32, f64> = HashMap::from([(init, 1.0)]);
+        let mut stopped: HashMap<i32, f64> = HashMap::new();
+        for die in &self.dice {
+            let mut next = HashMap::with_capacity(running.len());
+            for (&total, &chance) in &running {
+                for prob in die.get_probabilities() {
+                    let (totals, total) = match step(total, prob.value) {
+                        ControlFlow::Continue(total) => (&mut next, total),
+                        ControlFlow::Break(total) => (&mut stopped, total),
+                    };
+                    *totals.entry(total).or_insert(0.0) += chance * prob.chance;
+                }
+            }
+            running = next;
+        }
+        for (total, chance) in running {
+            *stopped.entry(total).or_insert(0.0) += chance;
+        }
+        Die::from_probabilities(
+            stopped
+                .into_iter()
+                .map(|(value, chance)| Probability { value, chance })
+                .collect(),
+        )
+        .add_flat(self.modifier)
+    }
+
+    /// Describes the dice of the pool like `2d6 + d8`, grouping consecutive copies, if every die
+    /// is [described][`Die::describe`].
+    fn describe_dice(&self) -> Option<String> {
+        let mut groups: Vec<(Cow<'_, str>, usize)> = Vec::new();
+        for die in &self.dice {
+            let description = die.describe()?;
+            match groups.last_mut() {
+                Some((last, amount)) if *last == description => *amount += 1,
+                _ => groups.push((description, 1)),
+            }
+        }
+        if groups.is_empty() {
+            return None;
+        }
+        Some(
+            groups
+                .into_iter()
+                .map(|(description, amount)| match amount {
+                    1 => description.to_string(),
+                    _ => describe_repeat(&description, amount),
+                })
+                .collect::<Vec<_>>()
+                .join(" + "),
+        )
+    }
+
+    /// Describes keeping or dropping dice of the pool, like `4d6 drop lowest 1`.
+    fn describe_reduced(&self, action: &str, amount: usize, condition: DropType) -> Option<String> {
+        self.describe_dice().map(|description| {
+            format!(
+                "{} {} {} {}",
+                describe_grouped(&description),
+                action,
+                match condition {
+                    DropType::High => "highest",
+                    DropType::Low => "lowest",
+                },
+                amount
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ALLOWED_ERROR;
+
+    #[test]
+    fn sum() {
+        let pool = PoolBuilder::new()
+            .add(2, Die::new(6))
+            .add(1, Die::new(8))
+            .plus(3);
+        assert_eq!(pool.dice().len(), 3);
+        assert_eq!(pool.modifier(), 3);
+        assert_eq!(
+            pool.sum(),
+            &(&(&Die::new(6) + &Die::new(6)) + &Die::new(8)) + 3
+        );
+        assert_eq!(PoolBuilder::new().sum(), Die::empty());
+    }
+
+    #[test]
+    fn keep_and_drop() {
+        let pool = PoolBuilder::new()
+            .add(1, Die::new(4))
+            .add(1, Die::new(2))
+            .add(1, Die::new(6))
+            .plus(-1);
+        assert_eq!(pool.keep(2, DropType::High), pool.drop(1, DropType::Low));
+        assert_eq!(pool.keep(2, DropType::High).get_min(), 1);
+        assert_eq!(pool.keep(2, DropType::High).get_max(), 9);
+    }
+
+    #[test]
+    fn count_successes() {
+        let successes = PoolBuilder::new()
+            .add(3, Die::new(6))
+            .plus(5)
+            .count_successes(&|&value| value == 6);
+        assert_eq!(successes.get_min(), 0);
+        assert_eq!(successes.get_max(), 3);
+        assert!((successes.get_mean() - 0.5).abs() < ALLOWED_ERROR);
+    }
+
+    #[test]
+    fn fold() {
+        let pool = PoolBuilder::new().add(4, Die::new(6)).plus(1);
+        let summed = pool.fold(0, |total, rolled| ControlFlow::Continue(total + rolled));
+        assert_eq!(summed, pool.sum());
+        assert!((summed.get_mean() - pool.sum().get_mean()).abs() < ALLOWED_ERROR);
+
+        // stop at the first 6, counting the dice rolled
+        let until_six = pool.fold(0, |rolled, value| match value {
+            6 => ControlFlow::Break(rolled + 1),
+            _ => ControlFlow::Continue(rolled + 1),
+        });
+        assert_eq!(until_six, Die::from_range(2, 5));
+        assert!((until_six.get_chance(2) - 1.0 / 6.0).abs() < ALLOWED_ERROR);
+        assert!((until_six.get_chance(5) - 125.0 / 216.0).abs() < ALLOWED_ERROR);
+
+        assert_eq!(
+            PoolBuilder::new()
+                .plus(2)
+                .fold(3, |_, _| ControlFlow::Break(0)),
+            Die::from(5)
+        );
+    }
+}
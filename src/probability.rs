@@ -1,4 +1,5 @@
-use crate::common::{BAR_LENGTH, DECIMAL_FORMAT, NUMBER_FORMAT};
+use crate::common::format_row;
+use crate::format_options::FormatOptions;
 use core::cmp::Ordering;
 use core::ops::{Add, Mul};
 
@@ -25,6 +26,10 @@ pub struct Probability<T> {
     pub value: T,
 }
 
+/// Adds up the values as plain `T` and multiplies the chances.
+///
+/// The values are not checked for overflows, use [`Probability::checked_add`] or
+/// [`Probability::saturating_add`] for that.
 impl<T> Add for Probability<T>
 where
     T: std::ops::Add<T, Output = T>,
@@ -39,6 +44,25 @@ where
     }
 }
 
+impl Probability<i32> {
+    /// Same as adding both probabilities, but returns `None` if the value overflows.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Probability {
+            value: self.value.checked_add(other.value)?,
+            chance: self.chance * other.chance,
+        })
+    }
+
+    /// Same as adding both probabilities, but clamps the value to `i32::MIN` or `i32::MAX`
+    /// instead of overflowing.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Probability {
+            value: self.value.saturating_add(other.value),
+            chance: self.chance * other.chance,
+        }
+    }
+}
+
 impl<T> Mul<f64> for Probability<T> {
     type Output = Probability<T>;
 
@@ -87,10 +111,8 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:>NUMBER_FORMAT$} : {:>NUMBER_FORMAT$.DECIMAL_FORMAT$} : {:-<BAR_LENGTH$}",
-            self.value,
-            self.chance * 100.0,
-            "#".repeat((self.chance * BAR_LENGTH as f64).floor() as usize)
+            "{}",
+            format_row(&self.value, self.chance, &FormatOptions::default())
         )
     }
 }
@@ -116,6 +138,27 @@ mod tests {
         )
     }
 
+    #[test]
+    fn adding_checked() {
+        let max = Probability {
+            value: i32::MAX,
+            chance: 0.5,
+        };
+        let one = Probability {
+            value: 1,
+            chance: 0.5,
+        };
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(
+            one.checked_add(one),
+            Some(Probability {
+                value: 2,
+                chance: 0.25
+            })
+        );
+        assert_eq!(max.saturating_add(one), max);
+    }
+
     #[test]
     fn multiplying() {
         assert_eq!(
@@ -1,19 +1,273 @@
+#[cfg(feature = "color")]
+use crate::colored::Colored;
 use crate::common::*;
+use crate::format_options::{FormatOptions, Formatted};
 use crate::probability::Probability;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 
+/// Result of comparing two distributions via [`dominates`][`ProbabilityDistribution::dominates`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DominanceResult {
+    /// Both distributions share the same cumulative distribution.
+    Equal,
+    /// Dominates on first order, meaning every threshold is at least as likely to be reached.
+    FirstOrder,
+    /// Only dominates on second order, meaning the accumulated cumulative distribution is never
+    /// larger.
+    SecondOrder,
+    /// Does not dominate.
+    None,
+}
+
+/// Result of a [chi-squared test][`ProbabilityDistribution::chi_squared_test`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquaredResult {
+    /// Sum of the squared differences between observed and expected counts, relative to the
+    /// expected counts.
+    pub statistic: f64,
+    /// Amount of possible outcomes minus one.
+    pub degrees_of_freedom: usize,
+    /// Chance to observe a statistic at least this large if the distribution is correct.
+    pub p_value: f64,
+}
+
 /// Base structure for mutating and evaluating different types of collections of
 /// [probabilities][`Probability`].
 pub trait ProbabilityDistribution<T> {
-    fn add_dependent<F>(&self, callback_fn: &F) -> Self
+    /// Adds the distribution returned by `callback_fn` for each value to that value.
+    ///
+    /// The callback may return any distribution over the same values, not only `Self`.
+    fn add_dependent<F, D>(&self, callback_fn: &F) -> Self
     where
-        F: Fn(&T) -> Self;
+        F: Fn(&T) -> D,
+        D: ProbabilityDistribution<T>;
     fn add_flat(&self, flat_increase: i32) -> Self;
     fn add_independent(&self, probability_distribution: &impl ProbabilityDistribution<T>) -> Self;
-    fn conditional_chain<F>(&self, callback_fn: &mut F) -> Self
+    /// Replaces each value with the distribution returned by `callback_fn` for it.
+    ///
+    /// The callback may return any distribution over the same values, not only `Self`.
+    fn conditional_chain<F, D>(&self, callback_fn: &mut F) -> Self
+    where
+        F: FnMut(&T) -> D,
+        D: ProbabilityDistribution<T>;
+    fn get_probabilities(&self) -> &[Probability<T>];
+
+    /// Runs a chi-squared goodness-of-fit test of the given observed counts against this
+    /// distribution.
+    ///
+    /// Each entry of `observed` pairs a value with the amount of times it was rolled. Observing a
+    /// value outside of this distribution results in an infinite statistic and a `p_value` of
+    /// `0.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let fair = Die::new(2).chi_squared_test(&[(1, 50), (2, 50)]);
+    /// assert_eq!(fair.statistic, 0.0);
+    /// assert_eq!(fair.degrees_of_freedom, 1);
+    /// assert!(fair.p_value > 0.99);
+    ///
+    /// let loaded = Die::new(2).chi_squared_test(&[(1, 20), (2, 80)]);
+    /// assert!(loaded.p_value < 0.01);
+    /// ```
+    fn chi_squared_test(&self, observed: &[(T, u64)]) -> ChiSquaredResult
+    where
+        T: Copy + PartialEq,
+    {
+        calc_chi_squared(self.get_probabilities(), observed)
+    }
+
+    /// Returns an iterator over the probabilities of this distribution as `(value, chance)`
+    /// tuples.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(
+    ///     Die::new(2).as_pairs().collect::<Vec<_>>(),
+    ///     vec![(1, 0.5), (2, 0.5)]
+    /// );
+    /// ```
+    fn as_pairs(&self) -> PairIter<'_, T> {
+        PairIter::new(self.get_probabilities())
+    }
+
+    /// Returns every value this distribution can result in, sorted from lowest to highest.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(Die::new(4).add_flat(1).support(), vec![2, 3, 4, 5]);
+    /// ```
+    fn support(&self) -> Vec<T>
+    where
+        T: Copy,
+    {
+        self.get_probabilities()
+            .iter()
+            .map(|prob| prob.value)
+            .collect()
+    }
+
+    /// Returns the chance of every value, in the same order as [`support`][`Self::support`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(Die::from_values(&[1, 1, 2, 4]).chances(), vec![0.5, 0.25, 0.25]);
+    /// ```
+    fn chances(&self) -> Vec<f64> {
+        self.get_probabilities()
+            .iter()
+            .map(|prob| prob.chance)
+            .collect()
+    }
+
+    /// Returns whether this distribution can result in the given value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert!(Die::new(6).contains_value(6));
+    /// assert!(!Die::new(6).contains_value(0));
+    /// ```
+    fn contains_value(&self, value: T) -> bool
+    where
+        T: Ord,
+    {
+        self.get_probabilities()
+            .binary_search_by(|prob| prob.value.cmp(&value))
+            .is_ok()
+    }
+
+    /// Returns the lowest value of this distribution, or `None` if it has no values at all.
+    ///
+    /// Unlike [`get_min`][`Self::get_min`], this never panics and doesn't need to look at more
+    /// than the first value.
+    fn support_min(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.get_probabilities().first().map(|prob| prob.value)
+    }
+
+    /// Returns the highest value of this distribution, or `None` if it has no values at all.
+    ///
+    /// Unlike [`get_max`][`Self::get_max`], this never panics and doesn't need to look at more
+    /// than the last value.
+    fn support_max(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.get_probabilities().last().map(|prob| prob.value)
+    }
+
+    /// Returns the amount of different values of this distribution.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let three_d6 = Die::new(6).repeat(3);
+    /// assert_eq!(three_d6.support_len(), 16);
+    /// assert_eq!(three_d6.support_min(), Some(3));
+    /// assert_eq!(three_d6.support_max(), Some(18));
+    /// ```
+    fn support_len(&self) -> usize {
+        self.get_probabilities().len()
+    }
+
+    /// Returns the mean and standard deviation of the normal distribution approximating this
+    /// distribution.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let (mu, sigma) = Die::new(6).approximate_normal();
+    /// assert_eq!(mu, 3.5);
+    /// assert!((sigma - 1.707825).abs() < 1e-6);
+    /// ```
+    fn approximate_normal(&self) -> (f64, f64)
+    where
+        Probability<T>: Ord,
+        T: Copy + std::ops::Mul<T, Output = T>,
+        f64: From<T>,
+    {
+        (self.get_mean(), self.get_standard_deviation())
+    }
+
+    /// Returns the largest difference between the cumulative distribution of this distribution
+    /// and the one of its [normal approximation][`ProbabilityDistribution::approximate_normal`].
+    ///
+    /// Assumes the values are integers and applies a continuity correction of `0.5`. The smaller
+    /// the result, the safer it is to use the approximation instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let one_d6 = Die::new(6);
+    /// let ten_d6 = Die::sum_all(&vec![Die::new(6); 10]);
+    /// assert!(ten_d6.normal_approximation_error() < one_d6.normal_approximation_error());
+    /// assert!(ten_d6.normal_approximation_error() < 0.01);
+    /// ```
+    fn normal_approximation_error(&self) -> f64
+    where
+        T: Copy + std::ops::Mul<T, Output = T>,
+        f64: From<T>,
+    {
+        calc_normal_approximation_error(self.get_probabilities())
+    }
+
+    /// Returns the probabilities of this distribution as a map from value to chance.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let chances = Die::new(4).to_map();
+    /// assert_eq!(chances[&3], 0.25);
+    /// assert_eq!(chances.get(&5), None);
+    /// ```
+    fn to_map(&self) -> HashMap<T, f64>
+    where
+        T: Copy + Eq + std::hash::Hash,
+    {
+        self.as_pairs().collect()
+    }
+
+    /// Returns the probabilities of this distribution as a map from value to chance, ordered by
+    /// value.
+    fn to_btreemap(&self) -> BTreeMap<T, f64>
+    where
+        T: Copy + Ord,
+    {
+        self.as_pairs().collect()
+    }
+
+    /// Checks whether this distribution stochastically dominates the given one.
+    ///
+    /// First-order dominance means this distribution is at least as likely to roll at least `x`
+    /// for every `x`, and strictly more likely for some. Second-order dominance is the weaker
+    /// check of comparing the accumulated area under both cumulative distributions instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, DominanceResult, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(Die::new(8).dominates(&Die::new(6)), DominanceResult::FirstOrder);
+    /// assert_eq!(Die::new(6).dominates(&Die::new(8)), DominanceResult::None);
+    /// ```
+    fn dominates(
+        &self,
+        probability_distribution: &impl ProbabilityDistribution<T>,
+    ) -> DominanceResult
     where
-        F: FnMut(&T) -> Self;
-    fn get_probabilities(&self) -> &Vec<Probability<T>>;
+        T: Copy + Ord,
+        f64: From<T>,
+    {
+        calc_dominance(
+            self.get_probabilities(),
+            probability_distribution.get_probabilities(),
+        )
+    }
 
     fn get_details(&self) -> String
     where
@@ -21,24 +275,160 @@ pub trait ProbabilityDistribution<T> {
         Probability<T>: Ord,
         f64: From<T>,
     {
+        self.get_details_with(&FormatOptions::default())
+    }
+
+    /// Same as [`get_details`][`ProbabilityDistribution::get_details`], but laid out according
+    /// to the given options.
+    fn get_details_with(&self, options: &FormatOptions) -> String
+    where
+        T: Copy + std::ops::Mul<T, Output = T> + std::fmt::Display,
+        Probability<T>: Ord,
+        f64: From<T>,
+    {
+        format_details(&detail_rows(self, options), options)
+    }
+
+    /// Returns a table of every value and the chance to roll at least that value, formatted the
+    /// same way as [`get_results`][`ProbabilityDistribution::get_results`].
+    fn get_at_least_table(&self) -> String
+    where
+        T: Copy + std::fmt::Display,
+    {
+        self.iter().zip(self.survival_iter()).fold(
+            String::new(),
+            |mut out, (prob, (value, survival))| {
+                let _ = writeln!(
+                    out,
+                    "{}",
+                    format_row(&value, survival + prob.chance, &FormatOptions::default())
+                );
+                out
+            },
+        )
+    }
+
+    /// Returns a table of every value and the chance to roll at most that value, formatted the
+    /// same way as [`get_results`][`ProbabilityDistribution::get_results`].
+    fn get_at_most_table(&self) -> String
+    where
+        T: Copy + std::fmt::Display,
+    {
+        self.cdf_iter()
+            .fold(String::new(), |mut out, (value, cumulative)| {
+                let _ = writeln!(
+                    out,
+                    "{}",
+                    format_row(&value, cumulative, &FormatOptions::default())
+                );
+                out
+            })
+    }
+
+    /// Returns a GitHub flavored markdown table of every value with the chance to roll exactly,
+    /// at least and at most that value, in percent.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(
+    ///     Die::new(2).to_markdown_table(),
+    ///     "| Value | Chance | At least | At most |\n\
+    ///      | ---: | ---: | ---: | ---: |\n\
+    ///      | 1 | 50.000% | 100.000% | 50.000% |\n\
+    ///      | 2 | 50.000% | 50.000% | 100.000% |\n"
+    /// );
+    /// ```
+    fn to_markdown_table(&self) -> String
+    where
+        T: Copy + std::fmt::Display,
+    {
+        let decimals = FormatOptions::default().decimals;
+        let mut out = String::from(
+            "| Value | Chance | At least | At most |\n| ---: | ---: | ---: | ---: |\n",
+        );
+        for ((prob, (_, survival)), (_, cumulative)) in
+            self.iter().zip(self.survival_iter()).zip(self.cdf_iter())
+        {
+            let _ = writeln!(
+                out,
+                "| {} | {:.decimals$}% | {:.decimals$}% | {:.decimals$}% |",
+                prob.value,
+                prob.chance * 100.0,
+                (survival + prob.chance) * 100.0,
+                cumulative * 100.0
+            );
+        }
+        out
+    }
+
+    /// Returns a LaTeX `tabular` of every value with the chance to roll exactly, at least and at
+    /// most that value, in percent with the given amount of decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(
+    ///     Die::new(2).to_latex_table(1),
+    ///     "\\begin{tabular}{rrrr}\n\
+    ///      \\hline\n\
+    ///      Value & Chance & At least & At most \\\\\n\
+    ///      \\hline\n\
+    ///      1 & 50.0\\% & 100.0\\% & 50.0\\% \\\\\n\
+    ///      2 & 50.0\\% & 50.0\\% & 100.0\\% \\\\\n\
+    ///      \\hline\n\
+    ///      \\end{tabular}\n"
+    /// );
+    /// ```
+    fn to_latex_table(&self, decimals: usize) -> String
+    where
+        T: Copy + std::fmt::Display,
+    {
+        let mut out = String::from(
+            "\\begin{tabular}{rrrr}\n\\hline\nValue & Chance & At least & At most \\\\\n\\hline\n",
+        );
+        for ((prob, (_, survival)), (_, cumulative)) in
+            self.iter().zip(self.survival_iter()).zip(self.cdf_iter())
+        {
+            let _ = writeln!(
+                out,
+                "{} & {:.decimals$}\\% & {:.decimals$}\\% & {:.decimals$}\\% \\\\",
+                prob.value,
+                prob.chance * 100.0,
+                (survival + prob.chance) * 100.0,
+                cumulative * 100.0
+            );
+        }
+        out.push_str("\\hline\n\\end{tabular}\n");
+        out
+    }
+
+    /// Returns a TikZ picture plotting the chance of every value in percent as a pgfplots bar
+    /// chart, with the given amount of decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let plot = Die::new(2).to_pgfplots(1);
+    /// assert!(plot.starts_with("\\begin{tikzpicture}\n\\begin{axis}[ybar"));
+    /// assert!(plot.contains("\\addplot coordinates {(1, 50.0) (2, 50.0)};\n"));
+    /// assert!(plot.ends_with("\\end{axis}\n\\end{tikzpicture}\n"));
+    /// ```
+    fn to_pgfplots(&self, decimals: usize) -> String
+    where
+        T: Copy + std::fmt::Display,
+    {
+        let coordinates: Vec<String> = self
+            .as_pairs()
+            .map(|(value, chance)| format!("({}, {:.decimals$})", value, chance * 100.0))
+            .collect();
         format!(
-            "\
-                {:<NAME_FORMAT$}{:>NUMBER_FORMAT$.DECIMAL_FORMAT$}\n\
-                {:<NAME_FORMAT$}{:>NUMBER_FORMAT$.DECIMAL_FORMAT$}\n\
-                {:<NAME_FORMAT$}{:>NUMBER_FORMAT$.DECIMAL_FORMAT$}\n\
-                {:<NAME_FORMAT$}{:>NUMBER_FORMAT$.DECIMAL_FORMAT$}\n\
-                {:<NAME_FORMAT$}{:>NUMBER_FORMAT$.DECIMAL_FORMAT$}\
-                ",
-            "Min",
-            self.get_min(),
-            "Max",
-            self.get_max(),
-            "Mean",
-            self.get_mean(),
-            "Variance",
-            self.get_variance(),
-            "Standard Deviation",
-            self.get_standard_deviation()
+            "\\begin{{tikzpicture}}\n\
+             \\begin{{axis}}[ybar, xlabel={{Value}}, ylabel={{Chance (\\%)}}, ymin=0]\n\
+             \\addplot coordinates {{{}}};\n\
+             \\end{{axis}}\n\
+             \\end{{tikzpicture}}\n",
+            coordinates.join(" ")
         )
     }
 
@@ -69,13 +459,35 @@ pub trait ProbabilityDistribution<T> {
 
     fn get_results(&self) -> String
     where
-        Probability<T>: std::fmt::Display,
+        T: std::fmt::Display,
+    {
+        self.get_results_with(&FormatOptions::default())
+    }
+
+    /// Same as [`get_results`][`ProbabilityDistribution::get_results`], but laid out according
+    /// to the given options.
+    fn get_results_with(&self, options: &FormatOptions) -> String
+    where
+        T: std::fmt::Display,
     {
         // TODO get rid of newline at end
-        self.iter().fold(String::new(), |mut out, prob| {
-            let _ = writeln!(out, "{prob}");
-            out
-        })
+        self.display_with(*options).to_string()
+    }
+
+    /// Returns an adapter displaying the results laid out according to the given options.
+    fn display_with(&self, options: FormatOptions) -> Formatted<'_, T> {
+        Formatted::new(self.get_probabilities(), options)
+    }
+
+    /// Returns an adapter displaying the results with ANSI colors and markers for the mean and
+    /// median, see [`Colored`].
+    #[cfg(feature = "color")]
+    fn display_colored(&self, options: FormatOptions) -> Colored<'_, T>
+    where
+        T: Copy,
+        f64: From<T>,
+    {
+        Colored::new(self.get_probabilities(), options)
     }
 
     fn get_standard_deviation(&self) -> f64
@@ -87,6 +499,192 @@ pub trait ProbabilityDistribution<T> {
         calc_standard_deviation(self.get_probabilities())
     }
 
+    /// Returns the value at risk at the level `alpha`, the smallest value that the worst `alpha`
+    /// share of the outcomes doesn't exceed, treating low values as bad.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not above `0.0` and at most `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// // the damage of 2d6 in the worst 10% of cases is at most 4
+    /// assert_eq!(Die::new(6).repeat(2).value_at_risk(0.1), 4);
+    /// ```
+    fn value_at_risk(&self, alpha: f64) -> T
+    where
+        T: Copy,
+    {
+        calc_value_at_risk(self.get_probabilities(), alpha)
+    }
+
+    /// Returns the conditional value at risk at the level `alpha`, the mean of the worst `alpha`
+    /// share of the outcomes, treating low values as bad.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not above `0.0` and at most `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let d10 = Die::new(10);
+    /// assert!((d10.conditional_value_at_risk(0.2) - 1.5).abs() < 1e-12);
+    /// assert!((d10.conditional_value_at_risk(1.0) - d10.get_mean()).abs() < 1e-12);
+    /// ```
+    fn conditional_value_at_risk(&self, alpha: f64) -> f64
+    where
+        T: Copy,
+        f64: From<T>,
+    {
+        calc_conditional_value_at_risk(self.get_probabilities(), alpha)
+    }
+
+    /// Returns the chance to roll exactly the given value, `0.0` if it can't be rolled at all.
+    ///
+    /// Looks the value up via binary search, relying on the probabilities being sorted by value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// let two_d6 = Die::new(6).repeat(2);
+    /// assert!((two_d6.get_chance(7) - 1.0 / 6.0).abs() < 1e-12);
+    /// assert_eq!(two_d6.get_chance(13), 0.0);
+    /// ```
+    fn get_chance(&self, value: T) -> f64
+    where
+        T: Ord,
+    {
+        let probabilities = self.get_probabilities();
+        probabilities
+            .binary_search_by(|prob| prob.value.cmp(&value))
+            .map_or(0.0, |index| probabilities[index].chance)
+    }
+
+    /// Returns the chance to roll higher than the given value, the complement of the cumulative
+    /// distribution.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(Die::new(4).get_survival(3), 0.25);
+    /// assert_eq!(Die::new(4).get_survival(4), 0.0);
+    /// ```
+    fn get_survival(&self, value: T) -> f64
+    where
+        T: PartialOrd,
+    {
+        self.get_probabilities()
+            .iter()
+            .filter(|prob| prob.value > value)
+            .fold(0.0, |acc, prob| acc + prob.chance)
+    }
+
+    /// Returns the chance to roll at least each of the given targets, in the order they were
+    /// given.
+    ///
+    /// The targets are sorted once and answered in a single pass over the probabilities, instead
+    /// of scanning them again for every target.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(
+    ///     Die::new(4).success_chances([3, 1, 5]),
+    ///     vec![(3, 0.5), (1, 1.0), (5, 0.0)]
+    /// );
+    /// ```
+    fn success_chances(&self, targets: impl IntoIterator<Item = T>) -> Vec<(T, f64)>
+    where
+        T: Copy + PartialOrd,
+    {
+        let targets: Vec<T> = targets.into_iter().collect();
+        let mut order: Vec<usize> = (0..targets.len()).collect();
+        order.sort_by(|&a, &b| {
+            targets[a]
+                .partial_cmp(&targets[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let probabilities = self.get_probabilities();
+        let total = probabilities
+            .iter()
+            .fold(0.0, |acc, prob| acc + prob.chance);
+        let mut results: Vec<(T, f64)> = targets.iter().map(|&target| (target, 0.0)).collect();
+        let mut below = 0.0;
+        let mut index = 0;
+        for position in order {
+            while index < probabilities.len() && probabilities[index].value < targets[position] {
+                below += probabilities[index].chance;
+                index += 1;
+            }
+            if index < probabilities.len() {
+                results[position].1 = total - below;
+            }
+        }
+        results
+    }
+
+    /// Returns the expected utility of this distribution, weighing every value by the payoff
+    /// `utility_fn` assigns to it instead of the value itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// // damage beyond the 4 hit points left is wasted
+    /// let damage = Die::new(8);
+    /// assert!((damage.expected_utility(|value| f64::from(value.min(4))) - 3.25).abs() < 1e-12);
+    /// ```
+    fn expected_utility<F>(&self, utility_fn: F) -> f64
+    where
+        F: Fn(T) -> f64,
+        T: Copy,
+    {
+        self.get_probabilities()
+            .iter()
+            .fold(0.0, |acc, prob| acc + prob.chance * utility_fn(prob.value))
+    }
+
+    /// Maps every value to the payoff `utility_fn` assigns to it, merging equal payoffs and
+    /// sorting them from lowest to highest.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, Probability, ProbabilityDistribution };
+    /// // failing below 3 is catastrophic, everything else is just as good
+    /// let utility = Die::new(4).map_to_utility(|value| if value < 3 { -10.0 } else { 1.0 });
+    /// assert_eq!(
+    ///     utility,
+    ///     vec![
+    ///         Probability { value: -10.0, chance: 0.5 },
+    ///         Probability { value: 1.0, chance: 0.5 },
+    ///     ]
+    /// );
+    /// assert!((utility[0].chance - 0.5).abs() < 1e-12);
+    /// assert!((utility[1].chance - 0.5).abs() < 1e-12);
+    /// ```
+    fn map_to_utility<F>(&self, utility_fn: F) -> Vec<Probability<f64>>
+    where
+        F: Fn(T) -> f64,
+        T: Copy,
+    {
+        let mut utilities: Vec<Probability<f64>> = self
+            .get_probabilities()
+            .iter()
+            .map(|prob| Probability {
+                value: utility_fn(prob.value),
+                chance: prob.chance,
+            })
+            .collect();
+        utilities.sort_by(|a, b| a.value.total_cmp(&b.value));
+        let mut merged: Vec<Probability<f64>> = Vec::with_capacity(utilities.len());
+        for prob in utilities {
+            match merged.last_mut() {
+                Some(last) if last.value == prob.value => last.chance += prob.chance,
+                _ => merged.push(prob),
+            }
+        }
+        merged
+    }
+
     fn get_variance(&self) -> f64
     where
         Probability<T>: Ord,
@@ -97,19 +695,49 @@ pub trait ProbabilityDistribution<T> {
     }
 
     /// Returns an iterator over the probabilities of this distribution.
-    fn iter(&self) -> ProbabilityIter<T> {
+    fn iter(&self) -> ProbabilityIter<'_, T> {
         ProbabilityIter::new(self.get_probabilities())
     }
+
+    /// Returns an iterator over the values of this distribution and their cumulative chances,
+    /// meaning the chance to roll the value or anything lower.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(
+    ///     Die::new(4).cdf_iter().collect::<Vec<_>>(),
+    ///     vec![(1, 0.25), (2, 0.5), (3, 0.75), (4, 1.0)]
+    /// );
+    /// ```
+    fn cdf_iter(&self) -> CumulativeIter<'_, T> {
+        CumulativeIter::new(self.get_probabilities())
+    }
+
+    /// Returns an iterator over the values of this distribution and their survival chances,
+    /// meaning the chance to roll anything higher than the value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution };
+    /// assert_eq!(
+    ///     Die::new(4).survival_iter().collect::<Vec<_>>(),
+    ///     vec![(1, 0.75), (2, 0.5), (3, 0.25), (4, 0.0)]
+    /// );
+    /// ```
+    fn survival_iter(&self) -> SurvivalIter<'_, T> {
+        SurvivalIter::new(self.get_probabilities())
+    }
 }
 
 /// Iterator over a list of probabilities.
 pub struct ProbabilityIter<'a, T> {
-    values: &'a Vec<Probability<T>>,
+    values: &'a [Probability<T>],
     index: usize,
 }
 
 impl<'a, T> ProbabilityIter<'a, T> {
-    pub fn new(probabilities: &'a Vec<Probability<T>>) -> Self {
+    pub fn new(probabilities: &'a [Probability<T>]) -> Self {
         ProbabilityIter {
             values: probabilities,
             index: 0,
@@ -130,3 +758,285 @@ impl<'a, T> Iterator for ProbabilityIter<'a, T> {
         }
     }
 }
+
+/// Iterator over a list of probabilities, yielding them as `(value, chance)` tuples.
+pub struct PairIter<'a, T> {
+    probabilities: ProbabilityIter<'a, T>,
+}
+
+impl<'a, T> PairIter<'a, T> {
+    pub fn new(probabilities: &'a [Probability<T>]) -> Self {
+        PairIter {
+            probabilities: ProbabilityIter::new(probabilities),
+        }
+    }
+}
+
+impl<T> Iterator for PairIter<'_, T>
+where
+    T: Copy,
+{
+    type Item = (T, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.probabilities
+            .next()
+            .map(|prob| (prob.value, prob.chance))
+    }
+}
+
+/// Iterator over a list of probabilities, yielding each value with its cumulative chance.
+pub struct CumulativeIter<'a, T> {
+    values: &'a [Probability<T>],
+    index: usize,
+    cumulative: f64,
+}
+
+impl<'a, T> CumulativeIter<'a, T> {
+    pub fn new(probabilities: &'a [Probability<T>]) -> Self {
+        CumulativeIter {
+            values: probabilities,
+            index: 0,
+            cumulative: 0.0,
+        }
+    }
+}
+
+impl<T> Iterator for CumulativeIter<'_, T>
+where
+    T: Copy,
+{
+    type Item = (T, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.values.len() {
+            let prob = &self.values[self.index];
+            self.cumulative += prob.chance;
+            self.index += 1;
+            Some((prob.value, self.cumulative))
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterator over a list of probabilities, yielding each value with its survival chance.
+pub struct SurvivalIter<'a, T> {
+    values: &'a [Probability<T>],
+    index: usize,
+    cumulative: f64,
+}
+
+impl<'a, T> SurvivalIter<'a, T> {
+    pub fn new(probabilities: &'a [Probability<T>]) -> Self {
+        SurvivalIter {
+            values: probabilities,
+            index: 0,
+            cumulative: 0.0,
+        }
+    }
+}
+
+impl<T> Iterator for SurvivalIter<'_, T>
+where
+    T: Copy,
+{
+    type Item = (T, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.values.len() {
+            let prob = &self.values[self.index];
+            self.cumulative += prob.chance;
+            self.index += 1;
+            Some((prob.value, (1.0 - self.cumulative).max(0.0)))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Die, NormalInitializer};
+
+    #[test]
+    fn pairs() {
+        let die = Die::from_values(&[4, 4, 8, 8]);
+        assert_eq!(die.as_pairs().collect::<Vec<_>>(), vec![(4, 0.5), (8, 0.5)]);
+    }
+
+    #[test]
+    fn maps() {
+        let die = Die::from_values(&[4, 4, 8, 8]);
+        assert_eq!(die.to_map(), HashMap::from([(4, 0.5), (8, 0.5)]));
+        assert_eq!(die.to_btreemap(), BTreeMap::from([(4, 0.5), (8, 0.5)]));
+    }
+
+    #[test]
+    fn cumulative() {
+        let cumulative: Vec<_> = Die::from_values(&[1, 3, 3, 3]).cdf_iter().collect();
+        assert_eq!(cumulative, vec![(1, 0.25), (3, 1.0)]);
+        assert_eq!(Die::empty().cdf_iter().count(), 1);
+    }
+
+    #[test]
+    fn survival() {
+        let die = Die::from_values(&[1, 3, 3, 3]);
+        assert_eq!(die.get_survival(0), 1.0);
+        assert_eq!(die.get_survival(1), 0.75);
+        assert_eq!(die.get_survival(2), 0.75);
+        assert_eq!(die.get_survival(3), 0.0);
+        assert_eq!(
+            die.survival_iter().collect::<Vec<_>>(),
+            vec![(1, 0.75), (3, 0.0)]
+        );
+    }
+
+    #[test]
+    fn success_chances() {
+        let die = Die::from_values(&[1, 3, 3, 3]);
+        assert_eq!(
+            die.success_chances([4, 0, 3, 2, 3]),
+            vec![(4, 0.0), (0, 1.0), (3, 0.75), (2, 0.75), (3, 0.75)]
+        );
+        assert!(die.success_chances([]).is_empty());
+    }
+
+    #[test]
+    fn decomposed() {
+        let die = Die::new(6).repeat(2);
+        let (support, chances) = (die.support(), die.chances());
+        assert_eq!(support, (2..=12).collect::<Vec<_>>());
+        assert_eq!(chances.len(), support.len());
+        for (value, chance) in support.into_iter().zip(chances) {
+            assert_eq!(die.get_chance(value), chance);
+        }
+        assert_eq!(die.support_len(), 11);
+        assert_eq!(die.support_min(), Some(die.get_min()));
+        assert_eq!(die.support_max(), Some(die.get_max()));
+        assert!(die.contains_value(7));
+        assert!(!die.contains_value(1));
+        assert!(!die.contains_value(13));
+    }
+
+    #[test]
+    fn chance_lookup() {
+        let die = Die::from_values(&[-2, 3, 3, 10]);
+        assert_eq!(die.get_chance(-2), 0.25);
+        assert_eq!(die.get_chance(3), 0.5);
+        assert_eq!(die.get_chance(10), 0.25);
+        assert_eq!(die.get_chance(0), 0.0);
+        assert_eq!(die.get_chance(11), 0.0);
+        assert_eq!(die.get_chance(-3), 0.0);
+    }
+
+    #[test]
+    fn tail_risk() {
+        let die = Die::from_values(&[1, 2, 2, 10]);
+        assert_eq!(die.value_at_risk(0.25), 1);
+        assert_eq!(die.value_at_risk(0.3), 2);
+        assert_eq!(die.value_at_risk(1.0), 10);
+        assert!((die.conditional_value_at_risk(0.25) - 1.0).abs() < 1e-12);
+        assert!((die.conditional_value_at_risk(0.5) - 1.5).abs() < 1e-12);
+        assert!((die.conditional_value_at_risk(0.3) - (0.25 + 0.05 * 2.0) / 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tail_risk_invalid_alpha() {
+        Die::new(6).value_at_risk(0.0);
+    }
+
+    #[test]
+    fn utility() {
+        let die = Die::new(6);
+        assert!((die.expected_utility(f64::from) - die.get_mean()).abs() < 1e-12);
+        assert!(
+            (die.expected_utility(|value| f64::from(value * value)) - 91.0 / 6.0).abs() < 1e-12
+        );
+        let utility = die.map_to_utility(|value| f64::from(value % 2));
+        assert_eq!(utility.len(), 2);
+        assert_eq!(utility[0].value, 0.0);
+        assert!((utility[1].chance - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tables() {
+        let die = Die::new(2);
+        assert_eq!(
+            die.get_at_least_table(),
+            format!(
+                "{}\n{}\n",
+                format_row(&1, 1.0, &FormatOptions::default()),
+                format_row(&2, 0.5, &FormatOptions::default())
+            )
+        );
+        assert_eq!(
+            die.get_at_most_table(),
+            format!(
+                "{}\n{}\n",
+                format_row(&1, 0.5, &FormatOptions::default()),
+                format_row(&2, 1.0, &FormatOptions::default())
+            )
+        );
+
+        let options = FormatOptions {
+            name_width: 5,
+            number_width: 4,
+            decimals: 1,
+            bar_length: 0,
+        };
+        assert_eq!(die.get_results_with(&options), "   1 : 50.0\n   2 : 50.0\n");
+        assert_eq!(
+            die.get_details_with(&options).lines().nth(2),
+            Some("Mean  1.5")
+        );
+        assert_eq!(die.get_results(), die.to_string());
+    }
+
+    #[test]
+    fn normal_approximation() {
+        let (mu, sigma) = Die::binomial(100, 0.5).approximate_normal();
+        assert!((mu - 50.0).abs() < 1e-9);
+        assert!((sigma - 5.0).abs() < 1e-9);
+        assert!(Die::binomial(100, 0.5).normal_approximation_error() < 0.001);
+        assert!((Die::coin().normal_approximation_error() - 0.022750132).abs() < 1e-6);
+        assert_eq!(Die::from(3).normal_approximation_error(), 0.0);
+    }
+
+    #[test]
+    fn chi_squared() {
+        let result =
+            Die::new(6).chi_squared_test(&[(1, 16), (2, 18), (3, 16), (4, 14), (5, 12), (6, 24)]);
+        assert!((result.statistic - 5.12).abs() < 1e-9);
+        assert_eq!(result.degrees_of_freedom, 5);
+        assert!((result.p_value - 0.401412).abs() < 1e-5);
+
+        let result = Die::new(6).chi_squared_test(&[(7, 1)]);
+        assert_eq!(result.statistic, f64::INFINITY);
+        assert_eq!(result.p_value, 0.0);
+    }
+
+    #[test]
+    fn dominance() {
+        assert_eq!(Die::new(6).dominates(&Die::new(6)), DominanceResult::Equal);
+        assert_eq!(
+            Die::new(6).add_flat(1).dominates(&Die::new(6)),
+            DominanceResult::FirstOrder
+        );
+        assert_eq!(
+            Die::new(6).dominates(&Die::new(6).add_flat(1)),
+            DominanceResult::None
+        );
+        // same mean, but less spread out
+        assert_eq!(
+            Die::from_values(&[3, 4]).dominates(&Die::new(6)),
+            DominanceResult::SecondOrder
+        );
+        assert_eq!(
+            Die::new(6).dominates(&Die::from_values(&[3, 4])),
+            DominanceResult::None
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{Die, ProbabilityDistribution};
+use std::sync::OnceLock;
+
+/// Amount of turns a [`Race`] is followed for by default.
+const DEFAULT_MAX_TURNS: u32 = 100;
+
+/// Two independent totals growing by a roll each turn, racing towards their targets, like a
+/// player rolling a d6 per turn against a doom track rolling a d4.
+///
+/// Both sides roll at the same time, so both may reach their target on the same turn, which
+/// counts as a tie. A side with a target of `0` or less has reached it from the start and
+/// finishes on the first turn.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, Race };
+/// let race = Race::new(Die::new(6), 20, Die::new(4), 12);
+/// let total = race.first_wins() + race.second_wins() + race.ties();
+/// assert!((total - 1.0).abs() < 1e-9);
+/// assert!(race.first_wins() < race.second_wins());
+/// assert_eq!(race.turns()[0].first, 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Race {
+    first: Die,
+    first_target: i32,
+    second: Die,
+    second_target: i32,
+    max_turns: u32,
+    turns: OnceLock<Vec<RaceTurn>>,
+}
+
+/// Chances of the race being decided on a single turn of a [`Race`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaceTurn {
+    /// The turn, starting at `1`.
+    pub turn: u32,
+    /// Chance of only the first side reaching its target on this turn.
+    pub first: f64,
+    /// Chance of only the second side reaching its target on this turn.
+    pub second: f64,
+    /// Chance of both sides reaching their targets on this turn.
+    pub tie: f64,
+}
+
+impl Race {
+    /// Creates a race of `first` rolled every turn towards `first_target` against `second`
+    /// rolled every turn towards `second_target`.
+    pub fn new(first: Die, first_target: i32, second: Die, second_target: i32) -> Self {
+        Race {
+            first,
+            first_target,
+            second,
+            second_target,
+            max_turns: DEFAULT_MAX_TURNS,
+            turns: OnceLock::new(),
+        }
+    }
+
+    /// Sets the amount of turns the race is followed for, `100` by default. Anything not decided
+    /// by then is left out of every chance.
+    pub fn max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = max_turns;
+        self.turns = OnceLock::new();
+        self
+    }
+
+    /// Returns the chances of the race being decided on every turn up to the maximum,
+    /// calculating them on first access.
+    pub fn turns(&self) -> &[RaceTurn] {
+        self.turns.get_or_init(|| self.calc_turns())
+    }
+
+    /// Returns the chance of the first side reaching its target before the second one does.
+    pub fn first_wins(&self) -> f64 {
+        self.turns().iter().map(|turn| turn.first).sum()
+    }
+
+    /// Returns the chance of the second side reaching its target before the first one does.
+    pub fn second_wins(&self) -> f64 {
+        self.turns().iter().map(|turn| turn.second).sum()
+    }
+
+    /// Returns the chance of both sides reaching their targets on the same turn.
+    pub fn ties(&self) -> f64 {
+        self.turns().iter().map(|turn| turn.tie).sum()
+    }
+
+    /// Returns the distribution of the turn the given side reaches its target on.
+    fn finishing_turn(&self, die: &Die, target: i32) -> Die {
+        if target <= 0 {
+            return Die::from(1);
+        }
+        // one more roll than turns, so everything not decided in time ends up past the last turn
+        die.rolls_to_reach(target, self.max_turns + 1)
+    }
+
+    fn calc_turns(&self) -> Vec<RaceTurn> {
+        let first = self.finishing_turn(&self.first, self.first_target);
+        let second = self.finishing_turn(&self.second, self.second_target);
+        let at = |die: &Die, turn: i32| die.get_survival(turn - 1) - die.get_survival(turn);
+        (1..=self.max_turns)
+            .map(|turn| {
+                let value = turn as i32;
+                let (first_now, second_now) = (at(&first, value), at(&second, value));
+                RaceTurn {
+                    turn,
+                    first: first_now * second.get_survival(value),
+                    second: second_now * first.get_survival(value),
+                    tie: first_now * second_now,
+                }
+            })
+            .collect()
+    }
+}
+
+impl PartialEq for Race {
+    fn eq(&self, other: &Self) -> bool {
+        self.first == other.first
+            && self.first_target == other.first_target
+            && self.second == other.second
+            && self.second_target == other.second_target
+            && self.max_turns == other.max_turns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn races() {
+        let coins = Race::new(Die::from_values(&[0, 1]), 1, Die::from_values(&[0, 1]), 1);
+        let turns = coins.turns();
+        assert_eq!(turns[0].turn, 1);
+        assert!((turns[0].first - 0.25).abs() < 1e-12);
+        assert!((turns[0].tie - 0.25).abs() < 1e-12);
+        assert!((turns[1].second - 1.0 / 16.0).abs() < 1e-12);
+        assert!((coins.first_wins() - 1.0 / 3.0).abs() < 1e-9);
+        assert!((coins.ties() - 1.0 / 3.0).abs() < 1e-9);
+
+        let certain = Race::new(Die::from(2), 4, Die::from(1), 4);
+        assert_eq!(certain.first_wins(), 1.0);
+        assert_eq!(certain.turns()[1].first, 1.0);
+
+        let short = coins.clone().max_turns(2);
+        assert_eq!(short.turns().len(), 2);
+        let decided = short.first_wins() + short.second_wins() + short.ties();
+        assert!((decided - 15.0 / 16.0).abs() < 1e-12);
+        assert_eq!(short, coins.max_turns(2));
+
+        let started = Race::new(Die::new(6), 0, Die::new(6), 3);
+        assert!((started.first_wins() - 1.0 / 3.0).abs() < 1e-12);
+        assert!((started.ties() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(
+            started.turns()[1],
+            RaceTurn {
+                turn: 2,
+                first: 0.0,
+                second: 0.0,
+                tie: 0.0
+            }
+        );
+        let both = Race::new(Die::new(6), -1, Die::new(6), 0);
+        assert_eq!(both.ties(), 1.0);
+    }
+}
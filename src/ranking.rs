@@ -0,0 +1,306 @@
+use crate::common::*;
+use crate::{Die, FormatOptions, ProbabilityDistribution};
+
+/// Scores closer together than this count as tied in [`choose_best`].
+const TIE_TOLERANCE: f64 = 1e-9;
+
+/// What to rank the dice of [`rank_by`] by, higher being better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    /// The mean of each die.
+    Mean,
+    /// The smallest value of each die with a cumulative chance of at least `0.5`.
+    Median,
+    /// The smallest value of each die with a cumulative chance of at least the given level.
+    Quantile(QuantileLevel),
+    /// The chance of each die to roll at least the given target, counting the target itself,
+    /// unlike [`get_survival`][`ProbabilityDistribution::get_survival`].
+    AtLeast(i32),
+    /// The average chance of each die to roll higher than every other die, with ties counting
+    /// half.
+    WinProbability,
+}
+
+/// Cumulative chance in `(0, 1]` a [`Criterion::Quantile`] looks for, like `0.1` for the value
+/// a die rolls at least 90% of the time.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct QuantileLevel(f64);
+
+impl QuantileLevel {
+    /// Creates a new level from the given cumulative chance.
+    ///
+    /// # Panics
+    /// Panics if `chance` is not in `(0, 1]`.
+    pub fn new(chance: f64) -> Self {
+        assert!(
+            chance > 0.0 && chance <= 1.0,
+            "quantile level has to be in (0, 1]"
+        );
+        QuantileLevel(chance)
+    }
+
+    /// Returns the cumulative chance of this level.
+    pub fn chance(self) -> f64 {
+        self.0
+    }
+}
+
+// levels are never NaN, so they are reflexive
+impl Eq for QuantileLevel {}
+
+/// Position of a single die in a [`Ranking`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankEntry {
+    /// Index of the die in the ranked slice.
+    pub index: usize,
+    /// Score of the die under the [`Criterion`].
+    pub score: f64,
+}
+
+/// Dice ordered from best to worst by a [`Criterion`], as returned by [`rank_by`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranking {
+    criterion: Criterion,
+    entries: Vec<RankEntry>,
+    names: Vec<Option<String>>,
+}
+
+impl Ranking {
+    /// Returns the criterion the dice were ranked by.
+    pub fn criterion(&self) -> Criterion {
+        self.criterion
+    }
+
+    /// Returns one entry per die, ordered from the best to the worst score.
+    pub fn entries(&self) -> &[RankEntry] {
+        &self.entries
+    }
+
+    /// Returns the [name][`Die::name`] of the die at the given index of the ranked slice, if any.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.names.get(index)?.as_deref()
+    }
+
+    /// Returns the best entry, if any dice were ranked.
+    pub fn best(&self) -> Option<RankEntry> {
+        self.entries.first().copied()
+    }
+}
+
+impl std::fmt::Display for Ranking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let FormatOptions {
+            number_width,
+            decimals,
+            ..
+        } = FormatOptions::default();
+        let header = match self.criterion {
+            Criterion::Mean => "Mean".to_string(),
+            Criterion::Median => "Median".to_string(),
+            Criterion::Quantile(level) => format!("{:.decimals$}%", level.chance() * 100.0),
+            Criterion::AtLeast(target) => format!(">= {}", target),
+            Criterion::WinProbability => "Win".to_string(),
+        };
+        writeln!(
+            f,
+            "{:>number_width$} : {:>number_width$} : {:>number_width$}",
+            "Rank", "Die", header
+        )?;
+        for (rank, entry) in self.entries.iter().enumerate() {
+            let score = match self.criterion {
+                Criterion::AtLeast(_) | Criterion::WinProbability => entry.score * 100.0,
+                Criterion::Mean | Criterion::Median | Criterion::Quantile(_) => entry.score,
+            };
+            writeln!(
+                f,
+                "{:>number_width$} : {:>number_width$} : {:>number_width$.decimals$}",
+                rank + 1,
+                self.name(entry.index)
+                    .map_or_else(|| entry.index.to_string(), str::to_string),
+                score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Ranks the given dice by `criterion`, from best to worst.
+///
+/// Dice with the same score keep their order from the slice. Named dice are shown by their
+/// [name][`Die::name`] when displaying the ranking, the others by their index.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ rank_by, Criterion, Die, NormalInitializer, ProbabilityDistribution };
+/// let builds = [Die::new(12), Die::new(6).repeat(2), Die::new(4).repeat(3)];
+/// let by_mean = rank_by(&builds, Criterion::Mean);
+/// assert_eq!(by_mean.best().unwrap().index, 2);
+/// let by_target = rank_by(&builds, Criterion::AtLeast(11));
+/// assert_eq!(by_target.best().unwrap().index, 0);
+/// ```
+pub fn rank_by(dice: &[Die], criterion: Criterion) -> Ranking {
+    let mut entries: Vec<RankEntry> = dice
+        .iter()
+        .enumerate()
+        .map(|(index, die)| RankEntry {
+            index,
+            score: score(dice, index, die, criterion),
+        })
+        .collect();
+    entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ranking {
+        criterion,
+        entries,
+        names: dice
+            .iter()
+            .map(|die| die.name().map(str::to_string))
+            .collect(),
+    }
+}
+
+/// Which of the dice given to [`choose_best`] score best, along with that score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Choice {
+    /// Indices of every die sharing the best score, in the order of the slice.
+    pub best: Vec<usize>,
+    /// The best score under the [`Criterion`].
+    pub score: f64,
+}
+
+impl Choice {
+    /// Returns whether more than one die shares the best score.
+    pub fn is_tie(&self) -> bool {
+        self.best.len() > 1
+    }
+}
+
+/// Chooses the dice scoring best by `criterion`, reporting every die tied for the best score.
+/// Returns `None` if no dice were given.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ choose_best, Criterion, Die, NormalInitializer, ProbabilityDistribution, QuantileLevel };
+/// let options = [Die::new(12), Die::new(6).repeat(2), Die::new(5).add_flat(4)];
+/// let safest = choose_best(&options, Criterion::Quantile(QuantileLevel::new(0.1))).unwrap();
+/// assert_eq!(safest.best, vec![2]);
+/// let average = choose_best(&options, Criterion::Mean).unwrap();
+/// assert!(average.is_tie());
+/// assert_eq!(average.best, vec![1, 2]);
+/// ```
+pub fn choose_best(dice: &[Die], criterion: Criterion) -> Option<Choice> {
+    let ranking = rank_by(dice, criterion);
+    let best = ranking.best()?;
+    let mut tied: Vec<usize> = ranking
+        .entries()
+        .iter()
+        .take_while(|entry| (best.score - entry.score).abs() < TIE_TOLERANCE)
+        .map(|entry| entry.index)
+        .collect();
+    tied.sort_unstable();
+    Some(Choice {
+        best: tied,
+        score: best.score,
+    })
+}
+
+fn quantile(die: &Die, chance: f64) -> f64 {
+    die.cdf_iter()
+        .find(|&(_, cumulative)| cumulative >= chance - ALLOWED_ERROR)
+        .map_or(0.0, |(value, _)| f64::from(value))
+}
+
+fn score(dice: &[Die], index: usize, die: &Die, criterion: Criterion) -> f64 {
+    match criterion {
+        Criterion::Mean => die.get_mean(),
+        Criterion::Median => quantile(die, 0.5),
+        Criterion::Quantile(level) => quantile(die, level.chance()),
+        Criterion::AtLeast(target) => die.success_chances([target])[0].1,
+        Criterion::WinProbability => {
+            if dice.len() < 2 {
+                return 0.0;
+            }
+            let total: f64 = dice
+                .iter()
+                .enumerate()
+                .filter(|&(other_index, _)| other_index != index)
+                .map(|(_, other)| {
+                    die.compare(other)
+                        .as_pairs()
+                        .map(|(value, chance)| match value {
+                            1 => chance,
+                            0 => chance / 2.0,
+                            _ => 0.0,
+                        })
+                        .sum::<f64>()
+                })
+                .sum();
+            total / (dice.len() - 1) as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn ranking() {
+        let dice = [Die::new(4), Die::new(8), Die::new(6)];
+        let order = |ranking: &Ranking| -> Vec<usize> {
+            ranking.entries().iter().map(|entry| entry.index).collect()
+        };
+        assert_eq!(order(&rank_by(&dice, Criterion::Mean)), vec![1, 2, 0]);
+        assert_eq!(order(&rank_by(&dice, Criterion::Median)), vec![1, 2, 0]);
+        assert_eq!(order(&rank_by(&dice, Criterion::AtLeast(5))), vec![1, 2, 0]);
+
+        let duel = rank_by(&[Die::new(2), Die::new(2)], Criterion::WinProbability);
+        assert_eq!(duel.criterion(), Criterion::WinProbability);
+        assert_eq!(order(&duel), vec![0, 1]);
+        assert_eq!(duel.entries()[0].score, 0.5);
+
+        let round_robin = rank_by(&dice, Criterion::WinProbability);
+        assert_eq!(order(&round_robin), vec![1, 2, 0]);
+        let total: f64 = round_robin.entries().iter().map(|entry| entry.score).sum();
+        assert!((total - 1.5).abs() < 1e-9);
+
+        assert_eq!(rank_by(&[], Criterion::Mean).best(), None);
+        let named = rank_by(
+            &[Die::new(4).with_name("dagger"), Die::new(6)],
+            Criterion::Mean,
+        );
+        assert_eq!(named.name(0), Some("dagger"));
+        assert_eq!(named.name(1), None);
+        assert!(named.to_string().contains("dagger"));
+        assert_eq!(
+            rank_by(&dice, Criterion::Mean).to_string().lines().count(),
+            4
+        );
+
+        let upper = Criterion::Quantile(QuantileLevel::new(0.9));
+        assert_eq!(order(&rank_by(&dice, upper)), vec![1, 2, 0]);
+        assert_eq!(rank_by(&dice, upper).entries()[0].score, 8.0);
+        assert_eq!(
+            rank_by(&dice, upper).to_string().lines().next(),
+            Some("      Rank :        Die :    90.000%")
+        );
+        assert_eq!(upper, Criterion::Quantile(QuantileLevel::new(0.9)));
+        // the target itself counts, rolling an 8 on a d8 is the only way to reach 8
+        let highest = rank_by(&dice, Criterion::AtLeast(8));
+        assert_eq!(order(&highest), vec![1, 0, 2]);
+        assert!((highest.entries()[0].score - 1.0 / 8.0).abs() < 1e-12);
+        let choice = choose_best(&dice, Criterion::AtLeast(4)).unwrap();
+        assert_eq!(choice.best, vec![1]);
+        assert!(!choice.is_tie());
+        assert!((choice.score - 5.0 / 8.0).abs() < 1e-12);
+        let tied = choose_best(&[Die::new(6), Die::new(4), Die::new(6)], Criterion::Mean).unwrap();
+        assert_eq!(tied.best, vec![0, 2]);
+        assert_eq!(choose_best(&[], Criterion::Mean), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile level has to be in (0, 1]")]
+    fn invalid_quantile_level() {
+        QuantileLevel::new(0.0);
+    }
+}
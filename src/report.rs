@@ -0,0 +1,178 @@
+use crate::{Die, ProbabilityDistribution};
+use std::fmt::Write;
+
+const CHART_WIDTH: f64 = 480.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+impl Die {
+    /// Renders a standalone HTML page with the stats, PMF and CDF charts and an at-least table of
+    /// this die, see [`Die::html_report_all`]. Uses the [name][`Die::name`] of the die if it has
+    /// one, `name` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// # use die_stats::{ Die, NormalInitializer };
+    /// let report = Die::new(6).to_html_report("d6");
+    /// assert!(report.starts_with("<!DOCTYPE html>"));
+    /// assert!(report.contains("<h2>d6</h2>"));
+    /// assert_eq!(report.matches("<svg").count(), 2);
+    /// ```
+    pub fn to_html_report(&self, name: &str) -> String {
+        Die::html_report_all(&[(self.name().unwrap_or(name), self)])
+    }
+
+    /// Renders a standalone HTML page with one section per named die.
+    ///
+    /// Each section holds a summary of the stats and [metadata][`Die::with_metadata`] of the die,
+    /// the chance of each value (PMF) and the chance to roll at most each value (CDF) as inline
+    /// SVG charts, and a table of the chances to roll at least each value. The page has no
+    /// external dependencies, so it can be shared as is.
+    pub fn html_report_all(dice: &[(&str, &Die)]) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n\
+             <html>\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <title>Dice report</title>\n\
+             <style>\n\
+             body { font-family: sans-serif; margin: 2em; }\n\
+             table { border-collapse: collapse; margin: 1em 0; }\n\
+             th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: right; }\n\
+             svg { display: block; margin: 1em 0; background: #fafafa; }\n\
+             </style>\n\
+             </head>\n\
+             <body>\n",
+        );
+        for (name, die) in dice {
+            write_section(&mut out, name, die);
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn write_section(out: &mut String, name: &str, die: &Die) {
+    let _ = writeln!(out, "<section>\n<h2>{}</h2>", escape(name));
+    out.push_str("<table>\n");
+    for (stat, value) in [
+        ("Min", die.get_min().to_string()),
+        ("Max", die.get_max().to_string()),
+        ("Mean", format!("{:.3}", die.get_mean())),
+        ("Variance", format!("{:.3}", die.get_variance())),
+        (
+            "Standard Deviation",
+            format!("{:.3}", die.get_standard_deviation()),
+        ),
+    ] {
+        let _ = writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", stat, value);
+    }
+    for (key, value) in die.metadata_entries() {
+        let _ = writeln!(
+            out,
+            "<tr><th>{}</th><td>{}</td></tr>",
+            escape(key),
+            escape(value)
+        );
+    }
+    out.push_str("</table>\n");
+
+    let pairs: Vec<(i32, f64)> = die.as_pairs().collect();
+    let cumulative: Vec<(i32, f64)> = die.cdf_iter().collect();
+    out.push_str("<h3>Chance</h3>\n");
+    write_bar_chart(out, &pairs);
+    out.push_str("<h3>At most</h3>\n");
+    write_line_chart(out, &cumulative);
+
+    out.push_str("<h3>At least</h3>\n<table>\n<tr><th>Value</th><th>Chance</th></tr>\n");
+    for (prob, (value, survival)) in die.iter().zip(die.survival_iter()) {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{:.3}%</td></tr>",
+            value,
+            (survival + prob.chance) * 100.0
+        );
+    }
+    out.push_str("</table>\n</section>\n");
+}
+
+/// Bars scaled to the most likely value.
+fn write_bar_chart(out: &mut String, pairs: &[(i32, f64)]) {
+    let max_chance = pairs.iter().map(|&(_, chance)| chance).fold(0.0, f64::max);
+    let width = CHART_WIDTH / pairs.len().max(1) as f64;
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+        CHART_WIDTH, CHART_HEIGHT
+    );
+    for (index, &(value, chance)) in pairs.iter().enumerate() {
+        let height = if max_chance > 0.0 {
+            chance / max_chance * CHART_HEIGHT
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            out,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"#4a7ab5\">\
+             <title>{}: {:.3}%</title></rect>",
+            index as f64 * width,
+            CHART_HEIGHT - height,
+            (width - 1.0).max(1.0),
+            height,
+            value,
+            chance * 100.0
+        );
+    }
+    out.push_str("</svg>\n");
+}
+
+/// Line through the cumulative chances, scaled from `0` to `1`.
+fn write_line_chart(out: &mut String, cumulative: &[(i32, f64)]) {
+    let step = CHART_WIDTH / cumulative.len().max(1) as f64;
+    let points: Vec<String> = cumulative
+        .iter()
+        .enumerate()
+        .map(|(index, &(_, chance))| {
+            format!(
+                "{:.2},{:.2}",
+                (index as f64 + 0.5) * step,
+                CHART_HEIGHT - chance * CHART_HEIGHT
+            )
+        })
+        .collect();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n\
+         <polyline points=\"{}\" fill=\"none\" stroke=\"#b5574a\" stroke-width=\"2\"/>\n\
+         </svg>",
+        CHART_WIDTH,
+        CHART_HEIGHT,
+        points.join(" ")
+    );
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalInitializer;
+
+    #[test]
+    fn reporting() {
+        let d4 = Die::new(4).with_metadata("<source>", "PHB");
+        let coin = Die::coin();
+        let report = Die::html_report_all(&[("d4 <b>", &d4), ("coin", &coin)]);
+        assert_eq!(report.matches("<section>").count(), 2);
+        assert!(report.contains("<h2>d4 &lt;b&gt;</h2>"));
+        assert!(report.contains("<tr><th>Mean</th><td>2.500</td></tr>"));
+        assert!(report.contains("<tr><th>&lt;source&gt;</th><td>PHB</td></tr>"));
+        assert!(report.contains("<tr><td>2</td><td>75.000%</td></tr>"));
+        assert!(report.contains("points=\"60.00,120.00 180.00,80.00 300.00,40.00 420.00,0.00\""));
+        assert!(report.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{Die, NormalInitializer, PoolBuilder, Probability, ProbabilityDistribution};
+use std::collections::{HashMap, HashSet};
+
+/// Most dice a pool may hold to have its rerolls optimized.
+const MAX_REROLL_DICE: usize = 16;
+
+/// The optimal way of rerolling dice of a pool, keeping the dice that maximize the expected
+/// score of the roll in the end, like between the rolls of a Yahtzee turn.
+///
+/// Every possible roll and amount of rerolls left is evaluated up front via expectimax, so
+/// looking up the best dice to keep afterwards is cheap. Identical dice next to each other in
+/// the pool are interchangeable, which keeps the amount of states down.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, PoolBuilder, ProbabilityDistribution };
+/// // a single d6 rerolled once, keeping a 4 or more
+/// let pool = PoolBuilder::new().add(1, Die::new(6));
+/// let policy = pool.optimize_rerolls(1, |dice| dice[0]);
+/// assert!((policy.expected_score() - 4.25).abs() < 1e-9);
+/// let (keep, expected) = policy.best_keep(&[3], 1).unwrap();
+/// assert_eq!(keep, vec![false]);
+/// assert!((expected - 3.5).abs() < 1e-9);
+/// assert_eq!(policy.best_keep(&[4], 1).unwrap().0, vec![true]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RerollPolicy {
+    groups: Vec<(Die, usize)>,
+    rerolls: usize,
+    // dice to keep and expected score of every sorted roll and amount of rerolls left
+    decisions: HashMap<(Vec<i32>, usize), (Vec<bool>, f64)>,
+    distribution: Die,
+}
+
+impl RerollPolicy {
+    /// Finds the optimal policy for `pool` with up to `rerolls` rerolls, where `score` scores
+    /// the dice of a roll before the modifier of the pool is added.
+    ///
+    /// The dice are handed to `score` in the order of the pool, except that identical dice next
+    /// to each other are sorted from lowest to highest.
+    ///
+    /// # Panics
+    /// Panics if the pool holds more than `16` dice.
+    pub fn new<F>(pool: &PoolBuilder, rerolls: usize, score: F) -> Self
+    where
+        F: Fn(&[i32]) -> i32,
+    {
+        assert!(
+            pool.dice().len() <= MAX_REROLL_DICE,
+            "at most {} dice can be rerolled",
+            MAX_REROLL_DICE
+        );
+        let mut groups: Vec<(Die, usize)> = Vec::new();
+        for die in pool.dice() {
+            match groups.last_mut() {
+                // `==` only compares the values, loaded dice have to stay apart from fair ones
+                Some((last, amount))
+                    if last.get_probabilities().len() == die.get_probabilities().len()
+                        && last
+                            .get_probabilities()
+                            .iter()
+                            .zip(die.get_probabilities())
+                            .all(|(a, b)| a.value == b.value && a.chance == b.chance) =>
+                {
+                    *amount += 1
+                }
+                _ => groups.push((die.clone(), 1)),
+            }
+        }
+        let mut solver = Solver {
+            rolls: groups
+                .iter()
+                .map(|(die, amount)| {
+                    (0..=*amount)
+                        .map(|rolled| sorted_rolls(die, rolled))
+                        .collect()
+                })
+                .collect(),
+            groups: &groups,
+            score: |dice: &[i32]| score(dice) + pool.modifier(),
+            decisions: HashMap::new(),
+        };
+
+        let mut states: HashMap<Vec<i32>, f64> = solver
+            .roll(&vec![Vec::new(); groups.len()])
+            .into_iter()
+            .collect();
+        let mut finals: HashMap<i32, f64> = HashMap::new();
+        for left in (0..=rerolls).rev() {
+            let mut next = HashMap::new();
+            for (state, chance) in states {
+                let keep = solver.decide(&state, left).0;
+                if keep.iter().all(|&kept| kept) {
+                    *finals.entry((solver.score)(&state)).or_insert(0.0) += chance;
+                    continue;
+                }
+                for (outcome, outcome_chance) in solver.roll(&solver.kept(&state, &keep)) {
+                    *next.entry(outcome).or_insert(0.0) += chance * outcome_chance;
+                }
+            }
+            states = next;
+        }
+        let decisions = solver.decisions;
+        RerollPolicy {
+            groups,
+            rerolls,
+            decisions,
+            distribution: Die::from_probabilities(
+                finals
+                    .into_iter()
+                    .map(|(value, chance)| Probability { value, chance })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the distribution of the final score when following the policy.
+    pub fn distribution(&self) -> &Die {
+        &self.distribution
+    }
+
+    /// Returns the expected final score when following the policy.
+    pub fn expected_score(&self) -> f64 {
+        self.distribution.get_mean()
+    }
+
+    /// Returns which of the `rolled` dice to keep with `rerolls_left` rerolls left, in the order
+    /// of the pool, together with the expected final score of doing so. Returns `None` if the
+    /// dice can't be rolled by the pool or more rerolls are left than the policy was made for.
+    pub fn best_keep(&self, rolled: &[i32], rerolls_left: usize) -> Option<(Vec<bool>, f64)> {
+        if rolled.len() != self.groups.iter().map(|(_, amount)| amount).sum::<usize>()
+            || rerolls_left > self.rerolls
+        {
+            return None;
+        }
+        // sort identical dice, remembering where every value came from
+        let mut order: Vec<usize> = Vec::with_capacity(rolled.len());
+        let mut start = 0;
+        for (_, amount) in &self.groups {
+            let mut positions: Vec<usize> = (start..start + amount).collect();
+            positions.sort_by_key(|&position| rolled[position]);
+            order.extend(positions);
+            start += amount;
+        }
+        let state: Vec<i32> = order.iter().map(|&position| rolled[position]).collect();
+        let (keep, expected) = self.decisions.get(&(state, rerolls_left))?;
+        let mut original = vec![false; rolled.len()];
+        for (&position, &kept) in order.iter().zip(keep) {
+            original[position] = kept;
+        }
+        Some((original, *expected))
+    }
+}
+
+impl PoolBuilder {
+    /// Finds the [optimal way of rerolling][`RerollPolicy`] the dice of this pool up to
+    /// `rerolls` times to maximize the expected `score`.
+    pub fn optimize_rerolls<F>(&self, rerolls: usize, score: F) -> RerollPolicy
+    where
+        F: Fn(&[i32]) -> i32,
+    {
+        RerollPolicy::new(self, rerolls, score)
+    }
+}
+
+/// Sorted outcomes of rolling some dice, with their chances.
+type Rolls = Vec<(Vec<i32>, f64)>;
+
+/// Memoized expectimax over the sorted rolls of a pool.
+struct Solver<'a, F> {
+    groups: &'a [(Die, usize)],
+    /// Every sorted outcome of rolling the given amount of dice of every group, with its chance.
+    rolls: Vec<Vec<Rolls>>,
+    score: F,
+    decisions: HashMap<(Vec<i32>, usize), (Vec<bool>, f64)>,
+}
+
+impl<F> Solver<'_, F>
+where
+    F: Fn(&[i32]) -> i32,
+{
+    /// Best dice to keep out of `state` with `rerolls` left, keeping everything meaning to stop.
+    fn decide(&mut self, state: &[i32], rerolls: usize) -> (Vec<bool>, f64) {
+        if let Some(decision) = self.decisions.get(&(state.to_vec(), rerolls)) {
+            return decision.clone();
+        }
+        let stop = f64::from((self.score)(state));
+        let mut best = (vec![true; state.len()], stop);
+        if rerolls > 0 {
+            let mut tried: HashSet<Vec<Vec<i32>>> = HashSet::new();
+            for mask in 0..(1u32 << state.len()) - 1 {
+                let keep: Vec<bool> = (0..state.len())
+                    .map(|index| mask & (1 << index) != 0)
+                    .collect();
+                let kept = self.kept(state, &keep);
+                if !tried.insert(kept.clone()) {
+                    continue;
+                }
+                let mut expected = 0.0;
+                for (outcome, chance) in self.roll(&kept) {
+                    expected += chance * self.decide(&outcome, rerolls - 1).1;
+                }
+                let kept_amount = keep.iter().filter(|&&kept| kept).count();
+                let best_amount = best.0.iter().filter(|&&kept| kept).count();
+                // prefer keeping more dice on ties
+                if expected > best.1 + 1e-12
+                    || (expected > best.1 - 1e-12 && kept_amount > best_amount)
+                {
+                    best = (keep, expected);
+                }
+            }
+        }
+        self.decisions
+            .insert((state.to_vec(), rerolls), best.clone());
+        best
+    }
+
+    /// Splits the kept dice of `state` up into the groups.
+    fn kept(&self, state: &[i32], keep: &[bool]) -> Vec<Vec<i32>> {
+        let mut start = 0;
+        self.groups
+            .iter()
+            .map(|(_, amount)| {
+                let kept = (start..start + amount)
+                    .filter(|&index| keep[index])
+                    .map(|index| state[index])
+                    .collect();
+                start += amount;
+                kept
+            })
+            .collect()
+    }
+
+    /// Every sorted outcome of rolling all dice besides the kept ones, with its chance.
+    fn roll(&self, kept: &[Vec<i32>]) -> Vec<(Vec<i32>, f64)> {
+        let mut outcomes = vec![(Vec::new(), 1.0)];
+        for (group, ((_, amount), kept)) in self.groups.iter().zip(kept).enumerate() {
+            let rolls = &self.rolls[group][amount - kept.len()];
+            let mut next = Vec::with_capacity(outcomes.len() * rolls.len());
+            for (outcome, chance) in &outcomes {
+                for (rolled, rolled_chance) in rolls {
+                    let mut values = kept.clone();
+                    values.extend_from_slice(rolled);
+                    values.sort_unstable();
+                    let mut outcome: Vec<i32> = outcome.clone();
+                    outcome.extend(values);
+                    next.push((outcome, chance * rolled_chance));
+                }
+            }
+            outcomes = next;
+        }
+        outcomes
+    }
+}
+
+/// Every outcome of rolling `amount` copies of `die` as sorted values, with its chance.
+pub(crate) fn sorted_rolls(die: &Die, amount: usize) -> Vec<(Vec<i32>, f64)> {
+    let mut rolls: HashMap<Vec<i32>, f64> = HashMap::from([(Vec::new(), 1.0)]);
+    for _ in 0..amount {
+        let mut next = HashMap::with_capacity(rolls.len() * die.get_probabilities().len());
+        for (outcome, chance) in &rolls {
+            for prob in die.get_probabilities() {
+                let mut outcome = outcome.clone();
+                let index = outcome.partition_point(|&value| value <= prob.value);
+                outcome.insert(index, prob.value);
+                *next.entry(outcome).or_insert(0.0) += chance * prob.chance;
+            }
+        }
+        rolls = next;
+    }
+    rolls.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rerolling() {
+        // without rerolls, the policy is just the scored pool
+        let pool = PoolBuilder::new().add(2, Die::new(6)).plus(1);
+        let sum = |dice: &[i32]| dice.iter().sum::<i32>();
+        let static_roll = pool.optimize_rerolls(0, sum);
+        assert_eq!(static_roll.distribution(), &pool.sum());
+        assert!((static_roll.expected_score() - 8.0).abs() < 1e-9);
+
+        // rerolling a d6 twice keeps 5s and 6s first, then 4s and up
+        let single = PoolBuilder::new()
+            .add(1, Die::new(6))
+            .optimize_rerolls(2, |dice| dice[0]);
+        assert!((single.expected_score() - 14.0 / 3.0).abs() < 1e-9);
+        assert_eq!(single.best_keep(&[4], 2).unwrap().0, vec![false]);
+        assert_eq!(single.best_keep(&[5], 2).unwrap().0, vec![true]);
+        assert_eq!(single.best_keep(&[7], 2), None);
+        assert_eq!(single.best_keep(&[5], 3), None);
+
+        // going for doubles, keeping one of a mixed pool
+        let mixed = PoolBuilder::new().add(1, Die::new(4)).add(2, Die::new(6));
+        let doubles = mixed.optimize_rerolls(1, |dice| {
+            i32::from(dice.windows(2).any(|pair| pair[0] == pair[1]) || dice[0] == dice[2])
+        });
+        let (keep, _) = doubles.best_keep(&[3, 6, 3], 1).unwrap();
+        assert_eq!(keep, vec![true, true, true]);
+        // keeping the d4 gives both d6 a chance to match it or each other
+        let (keep, expected) = doubles.best_keep(&[1, 6, 5], 1).unwrap();
+        assert_eq!(keep, vec![true, false, false]);
+        assert!((expected - 16.0 / 36.0).abs() < 1e-12);
+        let total: f64 = doubles.distribution().iter().map(|prob| prob.chance).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(doubles.expected_score() > 0.5);
+    }
+
+    #[test]
+    fn rerolling_loaded_dice() {
+        let loaded = Die::from_weights(&[(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 5)]);
+        let sum = |dice: &[i32]| dice.iter().sum::<i32>();
+        for pool in [
+            PoolBuilder::new()
+                .add(1, Die::new(6))
+                .add(1, loaded.clone()),
+            PoolBuilder::new()
+                .add(1, loaded.clone())
+                .add(1, Die::new(6)),
+        ] {
+            assert!((pool.optimize_rerolls(0, sum).expected_score() - 8.0).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Probability, ProbabilityDistribution};
+
+/// Draws values from a [probability distribution][`ProbabilityDistribution`] via inverse
+/// transform sampling.
+///
+/// Precomputes the cumulative chances once, so every draw afterwards is a binary search. The
+/// source of randomness is left to the caller: every draw takes a uniformly distributed number
+/// in `[0, 1)`, e.g. from the `rand` crate.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, Sampler };
+/// let sampler = Sampler::new(&Die::new(4));
+/// assert_eq!(sampler.sample(0.0), 1);
+/// assert_eq!(sampler.sample(0.3), 2);
+/// assert_eq!(sampler.sample(0.99), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sampler<T> {
+    values: Vec<T>,
+    cumulative: Vec<f64>,
+}
+
+impl<T> Sampler<T>
+where
+    T: Copy,
+{
+    /// Precomputes the cumulative chances of the given distribution.
+    pub fn new(probability_distribution: &impl ProbabilityDistribution<T>) -> Self {
+        Sampler::from_probabilities(probability_distribution.get_probabilities())
+    }
+
+    /// Precomputes the cumulative chances of the given [probabilities][`Probability`].
+    pub fn from_probabilities(probabilities: &[Probability<T>]) -> Self {
+        let mut sum = 0.0;
+        let (values, cumulative) = probabilities
+            .iter()
+            .map(|prob| {
+                sum += prob.chance;
+                (prob.value, sum)
+            })
+            .unzip();
+        Sampler { values, cumulative }
+    }
+
+    /// Returns the value belonging to the given uniformly distributed number in `[0, 1)`.
+    ///
+    /// Numbers outside of that range are clamped to the first or last value respectively.
+    ///
+    /// # Panics
+    /// Panics if the sampler was created without any probabilities.
+    pub fn sample(&self, uniform: f64) -> T {
+        let index = self
+            .cumulative
+            .partition_point(|&cumulative| cumulative <= uniform);
+        self.values[index.min(self.values.len() - 1)]
+    }
+
+    /// Returns an iterator drawing one value for each of the given uniformly distributed numbers.
+    pub fn sample_iter<'a, I>(&'a self, uniforms: I) -> impl Iterator<Item = T> + 'a
+    where
+        I: IntoIterator<Item = f64>,
+        I::IntoIter: 'a,
+    {
+        uniforms.into_iter().map(|uniform| self.sample(uniform))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Die, NormalInitializer};
+
+    #[test]
+    fn sampling() {
+        let sampler = Sampler::new(&Die::from_values(&[1, 2, 2, 5]));
+        assert_eq!(sampler.sample(-1.0), 1);
+        assert_eq!(sampler.sample(0.0), 1);
+        assert_eq!(sampler.sample(0.25), 2);
+        assert_eq!(sampler.sample(0.74), 2);
+        assert_eq!(sampler.sample(0.75), 5);
+        assert_eq!(sampler.sample(1.0), 5);
+        assert_eq!(
+            sampler.sample_iter([0.1, 0.5, 0.9]).collect::<Vec<_>>(),
+            vec![1, 2, 5]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/// Value types with a well defined next value, used to enumerate ranges of values like
+/// [`from_range`][`crate::NormalInitializer::from_range`] does.
+///
+/// Implemented for all primitive integers. Custom value types only need to implement this to be
+/// usable with the range based initializers.
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ NormalInitializer, ProbabilityDistribution, Step, WideDie };
+/// assert_eq!(5i64.successor(), Some(6));
+/// assert_eq!(i64::MAX.successor(), None);
+/// assert_eq!(WideDie::new(6i64).get_max(), 6);
+/// ```
+pub trait Step: Copy + Ord {
+    /// Returns the value directly following this one, or `None` if there is none.
+    fn successor(&self) -> Option<Self>;
+}
+
+macro_rules! impl_step {
+    ($($t:ty),*) => {
+        $(
+            impl Step for $t {
+                fn successor(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_step!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Collects every value between, and including, `start` and `end`.
+pub(crate) fn step_range<T>(start: T, end: T) -> Vec<T>
+where
+    T: Step,
+{
+    let mut values = Vec::new();
+    let mut current = Some(start);
+    while let Some(value) = current.filter(|value| *value <= end) {
+        values.push(value);
+        current = value.successor();
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        assert_eq!(step_range(-1, 2), vec![-1, 0, 1, 2]);
+        assert_eq!(step_range(3, 3), vec![3]);
+        assert_eq!(step_range(3, 2), Vec::<i32>::new());
+        assert_eq!(step_range(254u8, 255), vec![254, 255]);
+    }
+}
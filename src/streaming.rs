@@ -0,0 +1,201 @@
+use crate::drop_initializer::{prep, Combinations};
+use crate::Die;
+
+/// Accumulates the mean, variance, extremes and at-least chances of a distribution from a
+/// stream of `(value, chance)` outcomes, without ever storing the outcomes themselves.
+///
+/// Useful for pools whose support is too large to build a [`Die`] from, see
+/// [`PoolBuilder::outcomes`][`crate::PoolBuilder::outcomes`]. Outcomes may repeat values and may
+/// come in any order. The chances don't need to add up to `1`, every statistic is relative to
+/// the [total chance][`StreamingStats::total_chance`] seen so far.
+///
+/// # Examples
+/// ```
+/// # use die_stats::StreamingStats;
+/// let mut stats = StreamingStats::with_thresholds(&[3]);
+/// stats.extend([(1, 0.25), (4, 0.5), (2, 0.25)]);
+/// assert_eq!(stats.mean(), 2.75);
+/// assert_eq!(stats.at_least(3), Some(0.5));
+/// assert_eq!(stats.at_least(4), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StreamingStats {
+    total_chance: f64,
+    mean: f64,
+    squared_deviations: f64,
+    min: Option<i32>,
+    max: Option<i32>,
+    thresholds: Vec<i32>,
+    /// Chance of the values between each threshold and the next one.
+    buckets: Vec<f64>,
+}
+
+impl StreamingStats {
+    /// Creates an accumulator for the mean, variance and extremes only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an accumulator additionally tracking the chance to roll at least each of the
+    /// given thresholds.
+    pub fn with_thresholds(thresholds: &[i32]) -> Self {
+        let mut thresholds = thresholds.to_vec();
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        StreamingStats {
+            buckets: vec![0.0; thresholds.len()],
+            thresholds,
+            ..Self::default()
+        }
+    }
+
+    /// Adds a single outcome.
+    pub fn push(&mut self, value: i32, chance: f64) {
+        if chance <= 0.0 {
+            return;
+        }
+        self.total_chance += chance;
+        let deviation = f64::from(value) - self.mean;
+        self.mean += deviation * chance / self.total_chance;
+        self.squared_deviations += chance * deviation * (f64::from(value) - self.mean);
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        let reached = self
+            .thresholds
+            .partition_point(|&threshold| threshold <= value);
+        if reached > 0 {
+            self.buckets[reached - 1] += chance;
+        }
+    }
+
+    /// Returns the sum of the chances of every outcome seen so far.
+    pub fn total_chance(&self) -> f64 {
+        self.total_chance
+    }
+
+    /// Returns the mean of the outcomes seen so far, `0.0` if there were none.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the variance of the outcomes seen so far, `0.0` if there were none.
+    pub fn variance(&self) -> f64 {
+        if self.total_chance > 0.0 {
+            (self.squared_deviations / self.total_chance).max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the standard deviation of the outcomes seen so far.
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Returns the smallest value seen so far.
+    pub fn min(&self) -> Option<i32> {
+        self.min
+    }
+
+    /// Returns the largest value seen so far.
+    pub fn max(&self) -> Option<i32> {
+        self.max
+    }
+
+    /// Returns the chance to roll at least `threshold`, if it was tracked via
+    /// [`StreamingStats::with_thresholds`].
+    pub fn at_least(&self, threshold: i32) -> Option<f64> {
+        let index = self.thresholds.binary_search(&threshold).ok()?;
+        if self.total_chance <= 0.0 {
+            return Some(0.0);
+        }
+        Some(self.buckets[index..].iter().sum::<f64>() / self.total_chance)
+    }
+}
+
+impl Extend<(i32, f64)> for StreamingStats {
+    fn extend<I: IntoIterator<Item = (i32, f64)>>(&mut self, outcomes: I) {
+        for (value, chance) in outcomes {
+            self.push(value, chance);
+        }
+    }
+}
+
+impl FromIterator<(i32, f64)> for StreamingStats {
+    fn from_iter<I: IntoIterator<Item = (i32, f64)>>(outcomes: I) -> Self {
+        let mut stats = StreamingStats::new();
+        stats.extend(outcomes);
+        stats
+    }
+}
+
+/// Iterator over every combination of rolls of a pool, as returned by
+/// [`PoolBuilder::outcomes`][`crate::PoolBuilder::outcomes`].
+///
+/// Yields one `(value, chance)` pair per combination, reducing the rolled values in pool order
+/// via the given function. Only the current combination is kept in memory.
+#[derive(Debug, Clone)]
+pub struct Outcomes<'a, F> {
+    combinations: Combinations<'a, i32>,
+    /// An empty pool still has the single combination of rolling nothing.
+    empty_pool: bool,
+    reduce: F,
+    modifier: i32,
+}
+
+impl<'a, F> Outcomes<'a, F>
+where
+    F: Fn(&[i32]) -> i32,
+{
+    pub(crate) fn new(dice: &'a [Die], modifier: i32, reduce: F) -> Self {
+        Outcomes {
+            combinations: prep(dice),
+            empty_pool: dice.is_empty(),
+            reduce,
+            modifier,
+        }
+    }
+}
+
+impl<F> Iterator for Outcomes<'_, F>
+where
+    F: Fn(&[i32]) -> i32,
+{
+    type Item = (i32, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rolled, chance) = match self.combinations.next() {
+            Some(combination) => combination,
+            None if std::mem::take(&mut self.empty_pool) => (Vec::new(), 1.0),
+            None => return None,
+        };
+        Some(((self.reduce)(&rolled).saturating_add(self.modifier), chance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DropType, NormalInitializer, PoolBuilder, ProbabilityDistribution};
+
+    #[test]
+    fn streaming() {
+        let pool = PoolBuilder::new().add(4, Die::new(6)).plus(1);
+        let kept = pool.keep(3, DropType::High);
+        let mut stats = StreamingStats::with_thresholds(&[16, 10, 16]);
+        stats.extend(
+            pool.outcomes(|rolled| rolled.iter().sum::<i32>() - rolled.iter().min().unwrap()),
+        );
+        assert!((stats.total_chance() - 1.0).abs() < 1e-9);
+        assert!((stats.mean() - kept.get_mean()).abs() < 1e-9);
+        assert!((stats.variance() - kept.get_variance()).abs() < 1e-9);
+        assert_eq!((stats.min(), stats.max()), (Some(4), Some(19)));
+        assert!((stats.at_least(16).unwrap() - kept.get_survival(15)).abs() < 1e-9);
+        assert!((stats.at_least(10).unwrap() - kept.get_survival(9)).abs() < 1e-9);
+        assert_eq!(stats.at_least(11), None);
+
+        assert_eq!(pool.outcomes(|rolled| rolled[0]).count(), 6usize.pow(4));
+        let empty: StreamingStats = PoolBuilder::new().outcomes(|_| 3).collect();
+        assert_eq!((empty.mean(), empty.variance()), (3.0, 0.0));
+    }
+}
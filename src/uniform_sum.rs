@@ -0,0 +1,227 @@
+use crate::Probability;
+use core::cmp::Ordering;
+use std::collections::VecDeque;
+
+/// Sum of `n` dice, each rolling one of `faces` consecutive values starting at `start` with equal
+/// chance.
+///
+/// The amount of ways to roll each offset `k` from the minimum is the coefficient of `x^k` in
+/// `((1 - x^faces) / (1 - x))^n`, which expands to the inclusion-exclusion formula
+/// `Σ_j (-1)^j · C(n, j) · C(k - j·faces + n - 1, n - 1)`. Differentiating the closed form turns
+/// that into a recurrence over just three earlier coefficients,
+///
+/// `(k + 1)·c[k + 1] = (k + n)·c[k] + (n·(faces - 1) + faces - k)·c[k - faces]
+///     - (n·faces + faces - 1 - k)·c[k + 1 - faces]`,
+///
+/// which is evaluated exactly on big integers and only divided by `faces^n` at the very end.
+/// Returns `None` if the sum could leave the range of `i32`.
+pub(crate) fn uniform_sum(start: i32, faces: u32, n: usize) -> Option<Vec<Probability<i32>>> {
+    let n_wide = i64::try_from(n).ok()?;
+    let lowest = i64::from(start).checked_mul(n_wide)?;
+    let spread = i64::from(faces).checked_sub(1)?.checked_mul(n_wide)?;
+    let highest = lowest.checked_add(spread)?;
+    if lowest < i64::from(i32::MIN) || highest > i64::from(i32::MAX) || faces == 0 || n == 0 {
+        return None;
+    }
+    let (faces, n) = (u64::from(faces), n as u64);
+
+    let mut total = BigUint::from(1);
+    for _ in 0..n {
+        total.mul_small(faces);
+    }
+
+    // Only the lower half is needed, the sum is symmetric around its center. Just the last
+    // `faces + 1` coefficients are kept around for the recurrence.
+    let half = spread as u64 / 2;
+    let mut ways = Vec::with_capacity(half as usize + 1);
+    let mut recent: VecDeque<BigUint> = VecDeque::with_capacity(faces as usize + 1);
+    let mut current = BigUint::from(1);
+    for k in 0.. {
+        ways.push(current.ratio(&total));
+        if k == half {
+            break;
+        }
+        // coefficient `offset` steps before the current one, if there is one
+        let earlier = |offset: u64| {
+            recent
+                .len()
+                .checked_sub(offset as usize)
+                .map(|position| &recent[position])
+        };
+        let mut next = current.clone();
+        next.mul_small(k + n);
+        if let Some(earlier) = earlier(faces) {
+            let mut term = earlier.clone();
+            term.mul_small(n * (faces - 1) + faces - k);
+            next.add(&term);
+        }
+        if let Some(earlier) = earlier(faces - 1) {
+            let mut term = earlier.clone();
+            term.mul_small(n * faces + faces - 1 - k);
+            next.sub(&term);
+        }
+        next.div_small(k + 1);
+        if recent.len() == faces as usize + 1 {
+            recent.pop_front();
+        }
+        recent.push_back(std::mem::replace(&mut current, next));
+    }
+
+    let width = spread as usize + 1;
+    Some(
+        (0..width)
+            .map(|offset| Probability {
+                value: (lowest + offset as i64) as i32,
+                chance: ways[offset.min(width - 1 - offset)],
+            })
+            .collect(),
+    )
+}
+
+/// Minimal unsigned big integer with little endian `u32` limbs, supporting just enough for
+/// [`uniform_sum`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn from(value: u32) -> Self {
+        let mut big = BigUint { limbs: vec![value] };
+        big.trim();
+        big
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn mul_small(&mut self, factor: u64) {
+        let mut carry: u128 = 0;
+        for limb in &mut self.limbs {
+            let product = u128::from(*limb) * u128::from(factor) + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        while carry > 0 {
+            self.limbs.push(carry as u32);
+            carry >>= 32;
+        }
+        self.trim();
+    }
+
+    /// Divides by `divisor`, dropping the remainder.
+    fn div_small(&mut self, divisor: u64) {
+        let mut remainder: u128 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << 32) | u128::from(*limb);
+            *limb = (current / u128::from(divisor)) as u32;
+            remainder = current % u128::from(divisor);
+        }
+        self.trim();
+    }
+
+    fn add(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0;
+        for (index, limb) in self.limbs.iter_mut().enumerate() {
+            let sum =
+                u64::from(*limb) + u64::from(other.limbs.get(index).copied().unwrap_or(0)) + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// Subtracts `other`, which must not be larger than `self`.
+    fn sub(&mut self, other: &BigUint) {
+        debug_assert!(self.cmp(other) != Ordering::Less);
+        let mut borrow = 0;
+        for (index, limb) in self.limbs.iter_mut().enumerate() {
+            let subtrahend = i64::from(other.limbs.get(index).copied().unwrap_or(0)) + borrow;
+            let difference = i64::from(*limb) - subtrahend;
+            borrow = i64::from(difference < 0);
+            *limb = difference.rem_euclid(1 << 32) as u32;
+        }
+        self.trim();
+    }
+
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+
+    /// Leading 64 bits as a float, together with the amount of bits below them.
+    fn to_scaled_f64(&self) -> (f64, i32) {
+        let bits = self.limbs.len() * 32
+            - self
+                .limbs
+                .last()
+                .map_or(32, |limb| limb.leading_zeros() as usize);
+        let shift = bits.saturating_sub(64);
+        let mut leading: u64 = 0;
+        for bit in (shift..bits).rev() {
+            let limb = self.limbs[bit / 32];
+            leading = (leading << 1) | u64::from((limb >> (bit % 32)) & 1);
+        }
+        (leading as f64, shift as i32)
+    }
+
+    /// Returns `self / other` as a float.
+    fn ratio(&self, other: &BigUint) -> f64 {
+        let (numerator, numerator_shift) = self.to_scaled_f64();
+        let (denominator, denominator_shift) = other.to_scaled_f64();
+        numerator / denominator * 2f64.powi(numerator_shift - denominator_shift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::polynomial::SparsePolynomial;
+    use crate::OverflowPolicy;
+
+    #[test]
+    fn big_arithmetic() {
+        let mut big = BigUint::from(1);
+        for _ in 0..40 {
+            big.mul_small(10);
+        }
+        let copy = big.clone();
+        big.add(&copy);
+        big.sub(&copy);
+        assert_eq!(big, copy);
+        big.div_small(1_000_000_000_000_000_000);
+        assert!((big.ratio(&BigUint::from(1)) - 1e22).abs() < 1e7);
+        assert_eq!(copy.cmp(&big), Ordering::Greater);
+    }
+
+    #[test]
+    fn matches_convolution() {
+        for (start, faces, n) in [(1, 6, 1), (1, 6, 3), (-1, 3, 4), (0, 10, 7), (1, 2, 5)] {
+            let expected = SparsePolynomial::from_probabilities(&values_to_probabilities(
+                &(start..start + faces as i32).collect::<Vec<_>>(),
+            ))
+            .power(n, OverflowPolicy::Error)
+            .unwrap()
+            .into_probabilities();
+            let calculated = uniform_sum(start, faces, n).unwrap();
+            assert_eq!(calculated.len(), expected.len());
+            for (calculated, expected) in calculated.iter().zip(&expected) {
+                assert_eq!(calculated.value, expected.value);
+                assert!((calculated.chance - expected.chance).abs() < 1e-12);
+            }
+        }
+        assert_eq!(uniform_sum(i32::MAX, 2, 2), None);
+        assert_eq!(uniform_sum(1, 6, 0), None);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::common::*;
+use crate::{Die, NormalInitializer, OverflowError, Probability, ProbabilityDistribution};
+use core::ops::Add;
+use std::collections::HashMap;
+
+/// A [`Die`] with `i64` values, for results that don't fit into an `i32`.
+///
+/// Every [`Die`] can be widened without loss and narrowed back via [`WideDie::try_narrow`], so
+/// calculations that might overflow can switch to a wide die up front, instead of saturating or
+/// failing. Adding a [`Die`] to a wide die widens it on the fly, so only the first die of such a
+/// calculation has to be converted. Values of a wide die saturate at the bounds of `i64`.
+///
+/// Since `f64` can't be created from `i64` without loss, the statistics depending on it are
+/// implemented directly on this type instead of through
+/// [`ProbabilityDistribution`].
+///
+/// # Examples
+/// ```
+/// # use die_stats::{ Die, NormalInitializer, ProbabilityDistribution, WideDie };
+/// let huge = Die::new(6).add_flat(i32::MAX - 6);
+/// let wide = WideDie::from(huge.clone()) + huge;
+/// assert_eq!(wide.get_max(), 2 * i64::from(i32::MAX));
+/// assert!(wide.try_narrow().is_err());
+/// assert_eq!(wide.scale(3).get_max(), 6 * i64::from(i32::MAX));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WideDie {
+    probabilities: Vec<Probability<i64>>,
+}
+
+impl WideDie {
+    /// Converts this die back to a [`Die`], failing if any value doesn't fit into an `i32`.
+    pub fn try_narrow(&self) -> Result<Die, OverflowError> {
+        Ok(Die::from_probabilities(
+            self.probabilities
+                .iter()
+                .map(|prob| {
+                    Ok(Probability {
+                        value: i32::try_from(prob.value)
+                            .map_err(|_| OverflowError { value: prob.value })?,
+                        chance: prob.chance,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    /// Multiplies every value by `factor`, like doubling the damage of a large pool on a
+    /// critical hit.
+    pub fn scale(&self, factor: i64) -> WideDie {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for prob in &self.probabilities {
+            *accumulated
+                .entry(prob.value.saturating_mul(factor))
+                .or_insert(0.0) += prob.chance;
+        }
+        WideDie::from_accumulated(accumulated)
+    }
+
+    /// Returns the mean of this die, see [`ProbabilityDistribution::get_mean`].
+    pub fn get_mean(&self) -> f64 {
+        calc_mean(&self.as_f64())
+    }
+
+    /// Returns the variance of this die, see [`ProbabilityDistribution::get_variance`].
+    pub fn get_variance(&self) -> f64 {
+        calc_variance(&self.as_f64())
+    }
+
+    /// Returns the standard deviation of this die, see
+    /// [`ProbabilityDistribution::get_standard_deviation`].
+    pub fn get_standard_deviation(&self) -> f64 {
+        calc_standard_deviation(&self.as_f64())
+    }
+
+    fn as_f64(&self) -> Vec<Probability<f64>> {
+        self.probabilities
+            .iter()
+            .map(|prob| Probability {
+                value: prob.value as f64,
+                chance: prob.chance,
+            })
+            .collect()
+    }
+
+    fn from_accumulated(accumulated: HashMap<i64, f64>) -> WideDie {
+        if accumulated.is_empty() {
+            return WideDie::empty();
+        }
+        let mut probabilities: Vec<Probability<i64>> = accumulated
+            .into_iter()
+            .map(|(value, chance)| Probability { value, chance })
+            .collect();
+        probabilities.sort_unstable();
+        WideDie { probabilities }
+    }
+}
+
+impl NormalInitializer<i64, WideDie> for WideDie {
+    /// Creates a new wide die with the given [probabilities][`Probability<i64>`].
+    ///
+    /// Creates an [empty die][`WideDie::empty()`] if the chances don't add up to `1.0`.
+    fn from_probabilities(probabilities: Vec<Probability<i64>>) -> WideDie {
+        let sum = probabilities
+            .iter()
+            .fold(0.0, |acc, curr| acc + curr.chance);
+        if probabilities.is_empty() || sum >= 1.0 + ALLOWED_ERROR || sum <= 1.0 - ALLOWED_ERROR {
+            return WideDie::empty();
+        }
+        WideDie {
+            probabilities: compress_additive(&probabilities),
+        }
+    }
+}
+
+impl ProbabilityDistribution<i64> for WideDie {
+    fn get_probabilities(&self) -> &[Probability<i64>] {
+        &self.probabilities
+    }
+
+    fn add_independent(
+        &self,
+        probability_distribution: &impl ProbabilityDistribution<i64>,
+    ) -> Self {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for outer_prob in &self.probabilities {
+            for inner_prob in probability_distribution.get_probabilities() {
+                *accumulated
+                    .entry(outer_prob.value.saturating_add(inner_prob.value))
+                    .or_insert(0.0) += outer_prob.chance * inner_prob.chance;
+            }
+        }
+        WideDie::from_accumulated(accumulated)
+    }
+
+    fn add_dependent<F, D>(&self, callback_fn: &F) -> WideDie
+    where
+        F: Fn(&i64) -> D,
+        D: ProbabilityDistribution<i64>,
+    {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for outer_prob in &self.probabilities {
+            for inner_prob in callback_fn(&outer_prob.value).get_probabilities() {
+                *accumulated
+                    .entry(outer_prob.value.saturating_add(inner_prob.value))
+                    .or_insert(0.0) += outer_prob.chance * inner_prob.chance;
+            }
+        }
+        WideDie::from_accumulated(accumulated)
+    }
+
+    fn conditional_chain<F, D>(&self, callback_fn: &mut F) -> WideDie
+    where
+        F: FnMut(&i64) -> D,
+        D: ProbabilityDistribution<i64>,
+    {
+        let mut accumulated = HashMap::with_capacity(self.probabilities.len());
+        for outer_prob in &self.probabilities {
+            for inner_prob in callback_fn(&outer_prob.value).get_probabilities() {
+                *accumulated.entry(inner_prob.value).or_insert(0.0) +=
+                    outer_prob.chance * inner_prob.chance;
+            }
+        }
+        WideDie::from_accumulated(accumulated)
+    }
+
+    fn add_flat(&self, flat_increase: i32) -> WideDie {
+        WideDie {
+            probabilities: self
+                .probabilities
+                .iter()
+                .map(|prob| Probability {
+                    value: prob.value.saturating_add(flat_increase.into()),
+                    chance: prob.chance,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Die> for WideDie {
+    fn from(die: Die) -> Self {
+        WideDie::from(&die)
+    }
+}
+
+impl From<&Die> for WideDie {
+    fn from(die: &Die) -> Self {
+        WideDie {
+            probabilities: die
+                .get_probabilities()
+                .iter()
+                .map(|prob| Probability {
+                    value: prob.value.into(),
+                    chance: prob.chance,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for WideDie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_results())
+    }
+}
+
+impl Add<WideDie> for WideDie {
+    type Output = WideDie;
+
+    fn add(self, rhs: WideDie) -> Self::Output {
+        self.add_independent(&rhs)
+    }
+}
+
+impl<'a> Add<&'a WideDie> for &'a WideDie {
+    type Output = WideDie;
+
+    fn add(self, rhs: &'a WideDie) -> Self::Output {
+        self.add_independent(rhs)
+    }
+}
+
+impl Add<Die> for WideDie {
+    type Output = WideDie;
+
+    fn add(self, rhs: Die) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<'a> Add<&'a Die> for &'a WideDie {
+    type Output = WideDie;
+
+    fn add(self, rhs: &'a Die) -> Self::Output {
+        self.add_independent(&WideDie::from(rhs))
+    }
+}
+
+impl Add<WideDie> for Die {
+    type Output = WideDie;
+
+    fn add(self, rhs: WideDie) -> Self::Output {
+        &rhs + &self
+    }
+}
+
+impl<'a> Add<&'a WideDie> for &'a Die {
+    type Output = WideDie;
+
+    fn add(self, rhs: &'a WideDie) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl PartialEq for WideDie {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_probabilities() == other.get_probabilities()
+    }
+}
+
+impl Eq for WideDie {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DropInitializer, DropType};
+
+    #[test]
+    fn widening() {
+        let d6 = WideDie::from(Die::new(6));
+        assert_eq!(d6, WideDie::from_values(&[1, 2, 3, 4, 5, 6]));
+        assert_eq!(d6.try_narrow(), Ok(Die::new(6)));
+        assert!((d6.get_mean() - 3.5).abs() < ALLOWED_ERROR);
+
+        let huge = WideDie::from(Die::from(i32::MAX)).add_flat(1);
+        assert_eq!(huge.get_min(), i64::from(i32::MAX) + 1);
+        assert_eq!(
+            huge.try_narrow(),
+            Err(OverflowError {
+                value: i64::from(i32::MAX) + 1
+            })
+        );
+    }
+
+    #[test]
+    fn adding() {
+        let d2 = WideDie::from(Die::new(2));
+        let sum = &d2 + &d2;
+        assert_eq!(sum, WideDie::from_values(&[2, 3, 4]));
+        assert!((sum.get_variance() - 0.5).abs() < ALLOWED_ERROR);
+        assert_eq!(WideDie::new(2), d2);
+        assert_eq!(
+            WideDie::new_drop(2, 2, 1, DropType::Low),
+            WideDie::from_values(&[1, 2, 2, 2])
+        );
+        assert_eq!(
+            d2.conditional_chain(&mut |&value| WideDie::from_values(&[value * 10])),
+            WideDie::from_values(&[10, 20])
+        );
+    }
+
+    #[test]
+    fn promoting() {
+        let huge = Die::from(i32::MAX);
+        let sum = &WideDie::from(&huge) + &huge;
+        assert_eq!(sum, WideDie::from_values(&[2 * i64::from(i32::MAX)]));
+        assert_eq!(&huge + &WideDie::from(&huge), sum);
+        assert_eq!(
+            WideDie::from(Die::new(2)) + Die::new(2),
+            WideDie::from_values(&[2, 3, 4])
+        );
+        assert_eq!(
+            WideDie::from(Die::new(2)).scale(-3),
+            WideDie::from_values(&[-6, -3])
+        );
+        assert_eq!(sum.scale(i64::MAX).get_max(), i64::MAX);
+    }
+}